@@ -1,38 +1,435 @@
-use crate::models::session::{SearchMatch, SessionEntry, SessionMeta};
-use crate::services::session_parser;
+use crate::models::session::{
+    ArchiveOptions, ArchiveReport, BashCommand, BashHistoryOptions, ContextTimeline,
+    ConversationTree, DiskUsageReport, ExportOptions, FileSession, ForkedSession,
+    FullContentBlock, PermissionOptions, PermissionPreview, PermissionReport, ProjectMigrationOptions, ProjectMigrationReport,
+    RateLimitOptions, RateLimitReport, ReadDirection, RestoredSession, RetentionPolicy,
+    RetentionReport, SearchPage, SessionDiff, SessionFiles, SessionListOptions, SessionMeta,
+    SessionSubagents, SessionWindow, TimingOptions, TimingReport, ToolAnalytics,
+    ToolAnalyticsOptions, TrashReport, TurnRange,
+};
+use crate::services::{
+    bash_history, context_timeline, conversation_tree, disk_usage, file_history, permissions,
+    project_migration, rate_limits, session_archive, session_cleanup, session_diff, session_export,
+    session_fork, session_parser, session_reader, session_timing, subagents, tool_analytics,
+};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
 
 #[tauri::command]
 pub async fn list_sessions(
+    app: AppHandle,
     claude_dir: String,
+    options: Option<SessionListOptions>,
     use_wsl: Option<bool>,
     wsl_distro: Option<String>,
 ) -> Result<Vec<SessionMeta>, String> {
     let dir = resolve_path(&claude_dir, use_wsl.unwrap_or(false), wsl_distro.as_deref());
-    session_parser::list_all_sessions(&dir).await
+    let cache_dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| format!("Failed to resolve cache dir: {}", e))?;
+    let sessions = session_parser::list_all_sessions(&dir, &cache_dir).await?;
+    Ok(session_parser::apply_list_options(sessions, &options.unwrap_or_default()))
 }
 
 #[tauri::command]
 pub async fn read_session(
     session_path: String,
+    offset: Option<usize>,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    direction: Option<ReadDirection>,
     use_wsl: Option<bool>,
     wsl_distro: Option<String>,
-) -> Result<Vec<SessionEntry>, String> {
+) -> Result<SessionWindow, String> {
     let path = resolve_path(&session_path, use_wsl.unwrap_or(false), wsl_distro.as_deref());
-    session_parser::parse_session_file(&path).await
+    session_reader::read_window(
+        &path,
+        offset,
+        cursor.as_deref(),
+        limit,
+        direction.unwrap_or_default(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn read_content_block(
+    session_path: String,
+    entry_index: usize,
+    block_index: usize,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<FullContentBlock, String> {
+    let path = resolve_path(&session_path, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    session_reader::read_content_block(&path, entry_index, block_index).await
+}
+
+#[tauri::command]
+pub async fn read_session_tree(
+    session_path: String,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<ConversationTree, String> {
+    let path = resolve_path(&session_path, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    conversation_tree::read_tree(&path).await
+}
+
+#[tauri::command]
+pub async fn read_session_subagents(
+    session_path: String,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<SessionSubagents, String> {
+    let path = resolve_path(&session_path, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    subagents::read_subagents(&path).await
+}
+
+/// `output_path` vem do dialogo de salvar (path local, sem conversao WSL)
+#[tauri::command]
+pub async fn export_session(
+    session_path: String,
+    output_path: String,
+    options: ExportOptions,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<(), String> {
+    let path = resolve_path(&session_path, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    session_export::export_session(&path, Path::new(&output_path), &options).await
+}
+
+#[tauri::command]
+pub async fn export_sessions(
+    session_paths: Vec<String>,
+    output_path: String,
+    options: ExportOptions,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<u32, String> {
+    let paths: Vec<String> = session_paths
+        .iter()
+        .map(|p| resolve_path(p, use_wsl.unwrap_or(false), wsl_distro.as_deref()))
+        .collect();
+    session_export::export_sessions(&paths, Path::new(&output_path), &options).await
+}
+
+#[tauri::command]
+pub async fn read_context_timeline(
+    session_path: String,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<ContextTimeline, String> {
+    let path = resolve_path(&session_path, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    context_timeline::read_timeline(&path).await
+}
+
+#[tauri::command]
+pub async fn session_diff(
+    session_path: String,
+    range: Option<TurnRange>,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<SessionDiff, String> {
+    let path = resolve_path(&session_path, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    session_diff::session_diff(&path, range.unwrap_or_default()).await
+}
+
+/// `output_path` vem do dialogo de salvar (path local, sem conversao WSL)
+#[tauri::command]
+pub async fn export_session_patch(
+    session_path: String,
+    output_path: String,
+    range: Option<TurnRange>,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<SessionDiff, String> {
+    let path = resolve_path(&session_path, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    session_diff::export_patch(&path, Path::new(&output_path), range.unwrap_or_default()).await
 }
 
 #[tauri::command]
 pub async fn search_sessions(
+    app: AppHandle,
     claude_dir: String,
     query: String,
+    cursor: Option<String>,
+    limit: Option<usize>,
     use_wsl: Option<bool>,
     wsl_distro: Option<String>,
-) -> Result<Vec<SearchMatch>, String> {
+) -> Result<SearchPage, String> {
     if query.len() < 2 {
-        return Ok(Vec::new());
+        return Ok(SearchPage::default());
     }
     let dir = resolve_path(&claude_dir, use_wsl.unwrap_or(false), wsl_distro.as_deref());
-    session_parser::search_in_sessions(&dir, &query).await
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    session_parser::search_in_sessions(
+        &dir,
+        &query,
+        cursor.as_deref(),
+        limit,
+        &cache_dir,
+        &data_dir,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn session_files(
+    app: AppHandle,
+    claude_dir: String,
+    session_path: String,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<SessionFiles, String> {
+    let dir = resolve_path(&claude_dir, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    let path = resolve_path(&session_path, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    file_history::session_files(&dir, &path, &cache_dir, &data_dir).await
+}
+
+/// `file_path` e comparado com os paths gravados no transcript (sem conversao WSL)
+#[tauri::command]
+pub async fn sessions_for_file(
+    app: AppHandle,
+    claude_dir: String,
+    file_path: String,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<Vec<FileSession>, String> {
+    let dir = resolve_path(&claude_dir, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    file_history::sessions_for_file(&dir, &file_path, &cache_dir, &data_dir).await
+}
+
+#[tauri::command]
+pub async fn list_bash_commands(
+    app: AppHandle,
+    claude_dir: String,
+    options: Option<BashHistoryOptions>,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<Vec<BashCommand>, String> {
+    let dir = resolve_path(&claude_dir, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    bash_history::list_bash_commands(&dir, &options.unwrap_or_default(), &cache_dir, &data_dir).await
+}
+
+/// `options.session_path` vem da listagem e recebe a mesma conversao WSL do claude_dir
+#[tauri::command]
+pub async fn tool_analytics(
+    app: AppHandle,
+    claude_dir: String,
+    options: Option<ToolAnalyticsOptions>,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<ToolAnalytics, String> {
+    let use_wsl = use_wsl.unwrap_or(false);
+    let dir = resolve_path(&claude_dir, use_wsl, wsl_distro.as_deref());
+    let mut options = options.unwrap_or_default();
+    options.session_path = options
+        .session_path
+        .map(|p| resolve_path(&p, use_wsl, wsl_distro.as_deref()));
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    tool_analytics::tool_analytics(&dir, &options, &cache_dir, &data_dir).await
+}
+
+#[tauri::command]
+pub async fn session_timing(
+    app: AppHandle,
+    claude_dir: String,
+    options: Option<TimingOptions>,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<TimingReport, String> {
+    let use_wsl = use_wsl.unwrap_or(false);
+    let dir = resolve_path(&claude_dir, use_wsl, wsl_distro.as_deref());
+    let mut options = options.unwrap_or_default();
+    options.session_path = options
+        .session_path
+        .map(|p| resolve_path(&p, use_wsl, wsl_distro.as_deref()));
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    session_timing::session_timing(&dir, &options, &cache_dir, &data_dir).await
+}
+
+#[tauri::command]
+pub async fn list_rate_limits(
+    app: AppHandle,
+    claude_dir: String,
+    options: Option<RateLimitOptions>,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<RateLimitReport, String> {
+    let dir = resolve_path(&claude_dir, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    rate_limits::list_rate_limits(&dir, &options.unwrap_or_default(), &cache_dir, &data_dir).await
+}
+
+#[tauri::command]
+pub async fn permission_analysis(
+    app: AppHandle,
+    claude_dir: String,
+    options: Option<PermissionOptions>,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<PermissionReport, String> {
+    let use_wsl = use_wsl.unwrap_or(false);
+    let dir = resolve_path(&claude_dir, use_wsl, wsl_distro.as_deref());
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    let project_dir = |p: &str| PathBuf::from(resolve_path(p, use_wsl, wsl_distro.as_deref()));
+    permissions::permission_analysis(
+        &dir,
+        &options.unwrap_or_default(),
+        &cache_dir,
+        &data_dir,
+        project_dir,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn preview_permission_rules(
+    project_path: String,
+    allow: Vec<String>,
+    deny: Vec<String>,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<PermissionPreview, String> {
+    let dir = resolve_path(&project_path, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    permissions::preview_rules(Path::new(&dir), &allow, &deny).await
+}
+
+#[tauri::command]
+pub async fn apply_permission_rules(
+    project_path: String,
+    allow: Vec<String>,
+    deny: Vec<String>,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<PermissionPreview, String> {
+    let dir = resolve_path(&project_path, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    permissions::apply_rules(Path::new(&dir), &allow, &deny).await
+}
+
+#[tauri::command]
+pub async fn archive_sessions(
+    app: AppHandle,
+    claude_dir: String,
+    options: ArchiveOptions,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<ArchiveReport, String> {
+    let use_wsl = use_wsl.unwrap_or(false);
+    let dir = resolve_path(&claude_dir, use_wsl, wsl_distro.as_deref());
+    let mut options = options;
+    options.session_paths = options.session_paths.map(|paths| {
+        paths
+            .iter()
+            .map(|p| resolve_path(p, use_wsl, wsl_distro.as_deref()))
+            .collect()
+    });
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    session_archive::archive_sessions(&dir, &options, &cache_dir, &data_dir).await
+}
+
+#[tauri::command]
+pub async fn restore_session(
+    path: String,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<RestoredSession, String> {
+    let path = resolve_path(&path, use_wsl.unwrap_or(false), wsl_distro.as_deref());
+    session_archive::restore_session(&path).await
+}
+
+#[tauri::command]
+pub async fn trash_sessions(
+    app: AppHandle,
+    claude_dir: String,
+    session_paths: Vec<String>,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<TrashReport, String> {
+    let use_wsl = use_wsl.unwrap_or(false);
+    let dir = resolve_path(&claude_dir, use_wsl, wsl_distro.as_deref());
+    let paths: Vec<String> = session_paths
+        .iter()
+        .map(|p| resolve_path(p, use_wsl, wsl_distro.as_deref()))
+        .collect();
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    session_cleanup::trash_sessions(&dir, &paths, &cache_dir, &data_dir).await
+}
+
+#[tauri::command]
+pub async fn apply_retention_policy(
+    app: AppHandle,
+    claude_dir: String,
+    policy: RetentionPolicy,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<RetentionReport, String> {
+    let use_wsl = use_wsl.unwrap_or(false);
+    let dir = resolve_path(&claude_dir, use_wsl, wsl_distro.as_deref());
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    let project_dir = |p: &str| PathBuf::from(resolve_path(p, use_wsl, wsl_distro.as_deref()));
+    session_cleanup::apply_retention(&dir, &policy, &cache_dir, &data_dir, project_dir).await
+}
+
+#[tauri::command]
+pub async fn disk_usage(
+    app: AppHandle,
+    claude_dir: String,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<DiskUsageReport, String> {
+    let use_wsl = use_wsl.unwrap_or(false);
+    let dir = resolve_path(&claude_dir, use_wsl, wsl_distro.as_deref());
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    let project_dir = |p: &str| PathBuf::from(resolve_path(p, use_wsl, wsl_distro.as_deref()));
+    disk_usage::disk_usage(&dir, &cache_dir, &data_dir, project_dir).await
+}
+
+#[tauri::command]
+pub async fn fork_session(
+    app: AppHandle,
+    claude_dir: String,
+    session_path: String,
+    uuid: String,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<ForkedSession, String> {
+    let use_wsl = use_wsl.unwrap_or(false);
+    let dir = resolve_path(&claude_dir, use_wsl, wsl_distro.as_deref());
+    let path = resolve_path(&session_path, use_wsl, wsl_distro.as_deref());
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    session_fork::fork_session(&dir, &path, &uuid, &cache_dir, &data_dir).await
+}
+
+#[tauri::command]
+pub async fn migrate_project(
+    app: AppHandle,
+    claude_dir: String,
+    options: ProjectMigrationOptions,
+    use_wsl: Option<bool>,
+    wsl_distro: Option<String>,
+) -> Result<ProjectMigrationReport, String> {
+    let use_wsl = use_wsl.unwrap_or(false);
+    let dir = resolve_path(&claude_dir, use_wsl, wsl_distro.as_deref());
+    let (cache_dir, data_dir) = app_dirs(&app)?;
+    let project_dir = |p: &str| PathBuf::from(resolve_path(p, use_wsl, wsl_distro.as_deref()));
+    project_migration::migrate_project(&dir, &options, &cache_dir, &data_dir, project_dir).await
+}
+
+/// Diretorios de cache (listagem) e de dados (indice) do app
+fn app_dirs(app: &AppHandle) -> Result<(PathBuf, PathBuf), String> {
+    let cache_dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| format!("Failed to resolve cache dir: {}", e))?;
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve data dir: {}", e))?;
+    Ok((cache_dir, data_dir))
 }
 
 /// Converte path Linux para UNC Windows quando WSL mode ativo
@@ -6,7 +6,14 @@ use commands::account::read_account_info;
 use commands::auth::{detect_oauth_token, refresh_oauth_token};
 use commands::mcp::list_mcp_servers;
 use commands::platform::get_platform_info;
-use commands::sessions::{list_sessions, read_session, search_sessions};
+use commands::sessions::{
+    apply_permission_rules, apply_retention_policy, archive_sessions, disk_usage, export_session,
+    export_session_patch, export_sessions, fork_session, list_bash_commands, list_rate_limits,
+    list_sessions, migrate_project, permission_analysis, preview_permission_rules,
+    read_content_block, read_context_timeline, read_session, read_session_subagents,
+    read_session_tree, restore_session, search_sessions, session_diff, session_files,
+    session_timing, sessions_for_file, tool_analytics, trash_sessions,
+};
 use commands::stats::{read_global_stats, read_project_stats};
 use commands::terminal::resume_session;
 use commands::tray::{clear_tray_display, exit_app, update_tray_icon, update_tray_tooltip};
@@ -89,6 +96,30 @@ pub fn run() {
             fetch_usage,
             list_sessions,
             read_session,
+            read_content_block,
+            read_session_tree,
+            read_session_subagents,
+            export_session,
+            export_sessions,
+            session_files,
+            sessions_for_file,
+            session_diff,
+            read_context_timeline,
+            export_session_patch,
+            list_bash_commands,
+            tool_analytics,
+            session_timing,
+            list_rate_limits,
+            permission_analysis,
+            preview_permission_rules,
+            apply_permission_rules,
+            archive_sessions,
+            restore_session,
+            trash_sessions,
+            apply_retention_policy,
+            disk_usage,
+            fork_session,
+            migrate_project,
             search_sessions,
             resume_session,
             get_platform_info,
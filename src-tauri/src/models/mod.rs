@@ -2,4 +2,5 @@ pub mod account;
 pub mod mcp;
 pub mod session;
 pub mod stats;
+pub mod transcript;
 pub mod usage;
@@ -1,30 +1,882 @@
+use crate::models::transcript::{ContentBlock, TranscriptEntry};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SessionMeta {
     pub id: String,
     pub project_path: String,
     pub project_display: String,
+    pub file_path: String,
+    /// Titulo da sessao: ultimo resumo do Claude Code, primeiro prompt ou ultima resposta
     pub summary: String,
+    /// Primeiro prompt real do usuario (sem slash commands, hooks e avisos)
+    pub first_prompt: Option<String>,
+    /// Ultimo entry `summary` da sessao
+    pub latest_summary: Option<String>,
+    pub first_timestamp: String,
     pub last_timestamp: String,
+    /// Tempo de relogio entre o primeiro e o ultimo entry
+    pub duration_ms: u64,
     pub message_count: u32,
+    /// Transcripts de subagentes (Task tool) ligados a sessao
+    pub subagent_count: u32,
+    /// Tokens e custo do transcript da sessao (sem subagentes)
+    pub tokens: TokenTotals,
+    /// Modelos distintos na ordem do primeiro uso
+    pub models: Vec<String>,
+    /// Trocas de modelo no meio da sessao (ex: /model)
+    pub model_switches: u32,
+    /// Transcript comprimido (`.jsonl.zst`/`.jsonl.gz`); precisa de restore para `claude --resume`
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// Campo de ordenacao de list_sessions
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionSortKey {
+    #[default]
+    LastActive,
+    Started,
+    Duration,
+    Cost,
+    Tokens,
+    Messages,
+}
+
+/// Ordenacao e filtros de list_sessions
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SessionListOptions {
+    #[serde(default)]
+    pub sort_by: SessionSortKey,
+    /// Ordem crescente (padrao: decrescente)
+    #[serde(default)]
+    pub ascending: bool,
+    /// Substring do id de algum modelo usado (ex: "opus")
+    pub model: Option<String>,
+    /// Substring do path do projeto
+    pub project: Option<String>,
+    pub min_cost_usd: Option<f64>,
+    pub min_tokens: Option<u64>,
+    /// Datas YYYY-MM-DD (inclusivas) da ultima atividade
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
+
+/// Sentido da leitura paginada de uma sessao
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadDirection {
+    #[default]
+    Forward,
+    Backward,
+}
+
+/// Formato de exportacao de uma sessao
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Opcoes de export_session / export_sessions
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportOptions {
+    #[serde(default)]
+    pub format: ExportFormat,
+    #[serde(default)]
+    pub include_thinking: bool,
+    #[serde(default)]
+    pub include_tool_results: bool,
+    /// Inclui sidechains e transcripts de subagentes
+    #[serde(default)]
+    pub include_sidechains: bool,
+}
+
+/// Janela de entries de uma sessao (read_session paginado)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionWindow {
+    pub entries: Vec<TranscriptEntry>,
+    /// entry_index do primeiro entry da janela
+    pub start_index: u32,
+    pub total_entries: u32,
+    pub has_before: bool,
+    pub has_after: bool,
 }
 
+/// Bloco completo (sem truncamento) de um entry, com o `toolUseResult` completo do entry
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct SessionEntry {
-    #[serde(rename = "type")]
+pub struct FullContentBlock {
+    pub block: ContentBlock,
+    pub tool_use_result: Option<Value>,
+}
+
+/// Entry com uuid na arvore da conversa
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TreeNode {
+    pub uuid: String,
+    pub parent_uuid: Option<String>,
+    pub entry_index: u32,
     pub entry_type: String,
-    pub message: serde_json::Value,
+    pub timestamp: String,
+    /// "active", "abandoned" ou "sidechain"
+    pub branch: String,
+}
+
+/// Ramo fora do caminho ativo (fork abandonado ou sidechain)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConversationBranch {
+    /// Entry mais recente do ramo
+    pub leaf_uuid: String,
+    /// Entry de onde o ramo sai (None quando o ramo nao se liga a nenhum entry conhecido)
+    pub fork_uuid: Option<String>,
+    /// uuids do ramo em ordem de arquivo
+    pub uuids: Vec<String>,
+    pub message_count: u32,
+    pub first_timestamp: String,
+    pub last_timestamp: String,
+    /// Primeiro texto do ramo
+    pub preview: String,
+}
+
+/// Arvore da conversa reconstruida de uuid/parentUuid
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ConversationTree {
+    pub active_leaf: Option<String>,
+    /// uuids da raiz ate a folha ativa
+    pub active_path: Vec<String>,
+    pub abandoned: Vec<ConversationBranch>,
+    pub sidechains: Vec<ConversationBranch>,
+    pub nodes: Vec<TreeNode>,
+}
+
+/// Tokens e custo estimado (precos publicos por modelo)
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct TokenTotals {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+    pub cost_usd: f64,
+    /// Tokens de modelos sem preco conhecido, inclusive cache (nao entram em cost_usd)
+    pub unpriced_tokens: u64,
+}
+
+impl TokenTotals {
+    /// Soma de todos os tokens (input, output e cache)
+    pub fn total(&self) -> u64 {
+        self.input_tokens
+            + self.output_tokens
+            + self.cache_creation_input_tokens
+            + self.cache_read_input_tokens
+    }
+
+    pub fn add(&mut self, other: &TokenTotals) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_creation_input_tokens += other.cache_creation_input_tokens;
+        self.cache_read_input_tokens += other.cache_read_input_tokens;
+        self.cost_usd += other.cost_usd;
+        self.unpriced_tokens += other.unpriced_tokens;
+    }
+}
+
+/// Execucao de um subagente (Task tool) dentro de uma sessao
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubagentRun {
+    pub agent_id: String,
+    pub file_path: String,
+    /// `subagent_type` do tool_use ("unknown" quando o tool_use nao foi encontrado)
+    pub subagent_type: String,
+    pub description: Option<String>,
+    /// tool_use que criou o subagente no transcript pai
+    pub tool_use_id: Option<String>,
+    pub parent_entry_index: Option<u32>,
+    pub first_timestamp: String,
+    pub last_timestamp: String,
+    pub message_count: u32,
+    pub models: Vec<String>,
+    pub tokens: TokenTotals,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubagentTypeTotals {
+    pub subagent_type: String,
+    pub runs: u32,
+    pub tokens: TokenTotals,
+}
+
+/// Subagentes de uma sessao com custo separado do transcript principal
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionSubagents {
+    pub session_id: String,
+    /// Tokens do proprio transcript da sessao
+    pub session_tokens: TokenTotals,
+    /// Soma de todos os subagentes
+    pub subagent_tokens: TokenTotals,
+    pub runs: Vec<SubagentRun>,
+    /// Totais por subagent_type, mais caros primeiro
+    pub by_type: Vec<SubagentTypeTotals>,
+}
+
+/// Arquivo lido ou modificado numa sessao (Read/Edit/Write/MultiEdit/NotebookEdit)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TouchedFile {
+    pub file_path: String,
+    pub reads: u32,
+    pub modifications: u32,
+    pub first_timestamp: String,
+    pub last_timestamp: String,
+}
+
+/// Arquivos tocados por uma sessao, em ordem do primeiro acesso
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SessionFiles {
+    /// Arquivos com ao menos uma modificacao bem-sucedida
+    pub modified: Vec<TouchedFile>,
+    /// Arquivos so lidos
+    pub read: Vec<TouchedFile>,
+}
+
+/// Sessao que leu ou modificou um arquivo
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileSession {
+    pub session: SessionMeta,
+    pub file: TouchedFile,
+}
+
+/// Intervalo de turnos (prompts reais do usuario, a partir de 1, inclusivo)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct TurnRange {
+    #[serde(default)]
+    pub from_turn: Option<u32>,
+    #[serde(default)]
+    pub to_turn: Option<u32>,
+}
+
+/// Diff unificado de um arquivo reconstruido dos tool_use Edit/MultiEdit/Write
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileDiff {
+    pub file_path: String,
+    pub diff: String,
+    pub additions: u32,
+    pub deletions: u32,
+    pub edit_count: u32,
+    pub created: bool,
+    /// false quando o conteudo original nao era conhecido e o diff tem so os trechos editados
+    pub complete: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct SessionDiff {
+    pub session_id: String,
+    pub turn_count: u32,
+    pub files: Vec<FileDiff>,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+/// Filtros de list_bash_commands
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BashHistoryOptions {
+    /// Substring do path do projeto
+    pub project: Option<String>,
+    #[serde(default)]
+    pub failed_only: bool,
+    /// Substring do comando ou da descricao
+    pub query: Option<String>,
+    /// Datas YYYY-MM-DD (inclusivas)
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// Comando Bash executado pelo agente
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BashCommand {
+    pub session_id: String,
+    pub session_path: String,
+    pub project_path: String,
+    pub project_display: String,
+    /// Transcript do subagente que rodou o comando (None = transcript da sessao);
+    /// `entry_index` se refere a esse arquivo
+    pub subagent_path: Option<String>,
+    pub entry_index: u32,
+    pub timestamp: String,
+    pub command: String,
+    pub description: Option<String>,
+    pub cwd: Option<String>,
+    /// Saida truncada do tool_result (None se o comando nao terminou)
+    pub output: Option<String>,
+    pub is_error: bool,
+    pub exit_code: Option<i32>,
+    pub interrupted: bool,
+}
+
+/// Escopo de tool_analytics: uma sessao, um projeto (substring do path) ou tudo, num intervalo de datas
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ToolAnalyticsOptions {
+    pub session_path: Option<String>,
+    pub project: Option<String>,
+    /// Datas YYYY-MM-DD (inclusivas)
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
+
+/// Uso de uma ferramenta: volume, falhas, tamanho do resultado e latencia tool_use -> tool_result
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ToolStats {
+    pub tool_name: String,
+    /// "builtin", "mcp" ou "server" (executada pela API, ex: web_search)
+    pub category: String,
+    pub mcp_server: Option<String>,
+    pub calls: u32,
+    pub errors: u32,
+    /// errors / results (0 quando nenhum resultado foi gravado)
+    pub error_rate: f64,
+    pub results: u32,
+    /// Tamanho medio do resultado em caracteres
+    pub avg_result_chars: f64,
+    pub total_result_chars: u64,
+    pub avg_latency_ms: Option<f64>,
+    pub max_latency_ms: Option<f64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ToolAnalytics {
+    pub total_calls: u32,
+    pub total_errors: u32,
+    pub tools: Vec<ToolStats>,
+}
+
+/// Ocupacao do contexto numa resposta do assistente (input + cache read + cache creation)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContextPoint {
+    pub entry_index: u32,
+    pub uuid: Option<String>,
+    pub timestamp: String,
+    pub model: String,
+    pub context_tokens: u64,
+    pub output_tokens: u64,
+    pub context_window: u64,
+    /// context_tokens / context_window
+    pub occupancy: f64,
+    /// Crescimento desde a resposta anterior (0 na primeira apos o inicio ou um compact)
+    pub delta_tokens: i64,
+}
+
+/// Compactacao da conversa (`/compact` manual ou auto-compact)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CompactBoundary {
+    pub entry_index: u32,
+    pub uuid: Option<String>,
+    pub timestamp: String,
+    /// "manual" ou "auto"
+    pub trigger: Option<String>,
+    /// Tokens de contexto antes da compactacao
+    pub pre_tokens: Option<u64>,
+}
+
+/// Turno que mais aumentou o contexto, com o entry que provavelmente causou o aumento
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContextBloat {
+    /// Resposta do assistente onde o aumento apareceu
+    pub entry_index: u32,
+    pub delta_tokens: i64,
+    pub cause_entry_index: Option<u32>,
+    /// "tool_result", "prompt" ou "assistant"
+    pub cause: String,
+    pub tool_name: Option<String>,
+    pub cause_chars: u64,
+    pub preview: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ContextTimeline {
+    pub session_id: String,
+    pub points: Vec<ContextPoint>,
+    pub compactions: Vec<CompactBoundary>,
+    /// Maiores aumentos, do maior para o menor
+    pub top_bloat: Vec<ContextBloat>,
+    pub peak_tokens: u64,
+    pub context_window: u64,
+}
+
+/// Escopo de session_timing e limite de pausa que separa tempo ativo de ocioso
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TimingOptions {
+    pub session_path: Option<String>,
+    /// Substring do path do projeto
+    pub project: Option<String>,
+    /// Datas YYYY-MM-DD (inclusivas)
+    pub since: Option<String>,
+    pub until: Option<String>,
+    /// Intervalos entre entries acima deste limite contam como ociosos (padrao: 5 minutos)
+    pub idle_threshold_secs: Option<u64>,
+}
+
+/// Turno: do prompt do usuario ate a ultima resposta do assistente antes do proximo prompt
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TurnTiming {
+    pub entry_index: u32,
+    pub timestamp: String,
+    pub latency_ms: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct TimingTotals {
+    pub sessions: u32,
+    pub turns: u32,
+    /// Soma das latencias dos turnos (tempo que o usuario esperou pelo agente)
+    pub wait_ms: u64,
+    pub avg_latency_ms: u64,
+    pub max_latency_ms: u64,
+    pub active_ms: u64,
+    pub idle_ms: u64,
+}
+
+impl TimingTotals {
+    pub fn add(&mut self, other: &TimingTotals) {
+        self.sessions += other.sessions;
+        self.turns += other.turns;
+        self.wait_ms += other.wait_ms;
+        self.max_latency_ms = self.max_latency_ms.max(other.max_latency_ms);
+        self.active_ms += other.active_ms;
+        self.idle_ms += other.idle_ms;
+        self.avg_latency_ms = self.wait_ms.checked_div(self.turns as u64).unwrap_or(0);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionTiming {
+    pub session_id: String,
+    pub file_path: String,
+    pub project_display: String,
+    pub totals: TimingTotals,
+    /// Turnos individuais (so quando o escopo e uma sessao)
+    pub turns: Vec<TurnTiming>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectTiming {
+    pub project_path: String,
+    pub project_display: String,
+    pub totals: TimingTotals,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DayTiming {
+    /// YYYY-MM-DD (UTC, como os timestamps do transcript)
+    pub date: String,
+    pub totals: TimingTotals,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct TimingReport {
+    pub idle_threshold_ms: u64,
+    pub totals: TimingTotals,
+    /// Mais tempo ativo primeiro
+    pub sessions: Vec<SessionTiming>,
+    pub projects: Vec<ProjectTiming>,
+    /// Em ordem de data
+    pub days: Vec<DayTiming>,
+}
+
+/// Filtros de list_rate_limits
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RateLimitOptions {
+    /// Substring do path do projeto
+    pub project: Option<String>,
+    /// Datas YYYY-MM-DD (inclusivas)
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
+
+/// Limite atingido, como registrado no transcript
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RateLimitHit {
+    pub session_id: String,
+    pub file_path: String,
+    pub project_path: String,
+    pub project_display: String,
+    /// Transcript do subagente onde o erro ocorreu (None = transcript da sessao);
+    /// `entry_index` se refere a esse arquivo
+    pub subagent_path: Option<String>,
+    pub entry_index: u32,
+    pub timestamp: String,
+    /// "usage_limit" (limite do plano) ou "rate_limit" (429 da API)
+    pub kind: String,
+    pub model: Option<String>,
+    pub message: String,
+    /// Horario absoluto do reset, quando conhecido
+    pub reset_at: Option<String>,
+    /// Reset como escrito na mensagem (ex: "3pm (America/Sao_Paulo)")
+    pub reset_hint: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RateLimitDay {
+    pub date: String,
+    pub usage_limits: u32,
+    pub rate_limits: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct RateLimitReport {
+    pub usage_limits: u32,
+    pub rate_limits: u32,
+    /// Mais recentes primeiro
+    pub hits: Vec<RateLimitHit>,
+    /// Em ordem de data
+    pub days: Vec<RateLimitDay>,
+}
+
+/// Filtros de permission_analysis
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PermissionOptions {
+    /// Substring do path do projeto
+    pub project: Option<String>,
+    /// Datas YYYY-MM-DD (inclusivas)
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
+
+/// Desfechos das chamadas que casam com uma regra de permissao
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct PermissionRuleStats {
+    /// Regra no formato de `permissions.allow` (ex: `Bash(npm test:*)`); vazia sem prefixo seguro
+    pub rule: String,
+    pub tool_name: String,
+    /// Primeira invocacao vista (comando, URL ou arquivo)
+    pub example: String,
+    /// Executadas: aprovadas no prompt ou liberadas sem prompt (modo ou regra existente)
+    pub executed: u32,
+    pub rejected: u32,
+    /// Canceladas com Esc durante o prompt ou a execucao
+    pub interrupted: u32,
+    pub last_seen: String,
+    /// "allow" ou "deny" quando uma regra dos settings ja cobre esta
+    pub existing: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ProjectPermissions {
+    pub project_path: String,
+    pub project_display: String,
+    pub executed: u32,
+    pub rejected: u32,
+    /// Chamadas canceladas e turnos interrompidos ("[Request interrupted by user]")
+    pub interruptions: u32,
+    /// Mais rejeitadas/interrompidas primeiro
+    pub rules: Vec<PermissionRuleStats>,
+    pub suggested_allow: Vec<String>,
+    pub suggested_deny: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct PermissionReport {
+    /// Mais rejeicoes e interrupcoes primeiro
+    pub projects: Vec<ProjectPermissions>,
+}
+
+/// `.claude/settings.json` do projeto antes e depois de adicionar regras
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PermissionPreview {
+    pub settings_path: String,
+    /// None quando o arquivo ainda nao existe
+    pub current: Option<String>,
+    pub proposed: String,
+    /// Diff unificado de current para proposed
+    pub diff: String,
+    /// Regras novas (ja presentes nao contam)
+    pub added: u32,
+}
+
+/// Compressao usada em archive_sessions
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveFormat {
+    #[default]
+    Zstd,
+    Gzip,
+}
+
+/// Sessoes a arquivar: selecionadas e/ou sem atividade ha `older_than_days` dias
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ArchiveOptions {
+    pub session_paths: Option<Vec<String>>,
+    /// Substring do path do projeto
+    pub project: Option<String>,
+    pub older_than_days: Option<u32>,
     #[serde(default)]
+    pub format: ArchiveFormat,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchivedSession {
+    pub session_id: String,
+    pub source: String,
+    pub archive: String,
+    pub original_bytes: u64,
+    pub archived_bytes: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ArchiveReport {
+    pub archived: Vec<ArchivedSession>,
+    /// Sessoes que falharam (o original fica intacto)
+    pub errors: Vec<String>,
+    pub original_bytes: u64,
+    pub archived_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RestoredSession {
+    pub session_id: String,
+    pub file_path: String,
+    pub bytes: u64,
+}
+
+/// Sessoes movidas para a lixeira do sistema (transcript, subagentes, file-history e todos)
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct TrashReport {
+    pub session_ids: Vec<String>,
+    pub files: u32,
+    pub freed_bytes: u64,
+    pub errors: Vec<String>,
+}
+
+/// O que a politica de retencao faz com as sessoes selecionadas
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    #[default]
+    Trash,
+    Archive,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionReason {
+    /// Sem atividade ha mais dias que o limite
+    Expired,
+    /// Pasta do projeto nao existe mais
+    MissingProject,
+}
+
+/// Regras de retencao; uma sessao entra se casar com qualquer regra ativa
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RetentionPolicy {
+    pub older_than_days: Option<u32>,
+    /// Usa `cleanupPeriodDays` do ~/.claude/settings.json (padrao do Claude Code: 30) como limite
+    #[serde(default)]
+    pub use_cleanup_period: bool,
+    #[serde(default)]
+    pub missing_projects: bool,
+    /// Substring do path do projeto
+    pub project: Option<String>,
+    #[serde(default)]
+    pub action: RetentionAction,
+    #[serde(default)]
+    pub format: ArchiveFormat,
+    /// So calcula o relatorio, sem alterar arquivos
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RetentionCandidate {
+    pub session_id: String,
+    pub project_path: String,
+    pub project_display: String,
+    pub file_path: String,
+    pub last_timestamp: String,
+    pub archived: bool,
+    pub reasons: Vec<RetentionReason>,
+    /// Arquivos removidos junto com a sessao (so no trash)
+    pub files: Vec<String>,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    pub action: RetentionAction,
+    /// `cleanupPeriodDays` efetivo do Claude Code
+    pub cleanup_period_days: u32,
+    /// Limite de idade aplicado (None sem regra de idade)
+    pub max_age_days: Option<u32>,
+    pub candidates: Vec<RetentionCandidate>,
+    /// Tamanho atual dos candidatos; no archive e o maximo que pode ser liberado
+    pub total_bytes: u64,
+    pub processed: u32,
+    pub freed_bytes: u64,
+    pub errors: Vec<String>,
+}
+
+/// No da arvore de uso de disco (pastas ate uma profundidade fixa; arquivos so na raiz)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiskNode {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    pub bytes: u64,
+    pub files: u32,
+    /// Maiores primeiro
+    pub children: Vec<DiskNode>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectDiskUsage {
+    /// Nome da pasta em projects/
+    pub slug: String,
+    pub project_path: String,
+    pub project_display: String,
+    pub bytes: u64,
+    pub files: u32,
+    pub sessions: u32,
+    /// A pasta do projeto nao existe mais
+    pub orphaned: bool,
+    pub last_modified: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LargeSessionFile {
+    pub path: String,
+    pub session_id: String,
+    pub project_display: String,
+    pub bytes: u64,
+    pub archived: bool,
+    /// Transcript de subagente
+    pub subagent: bool,
+    pub modified: Option<String>,
+}
+
+/// Tamanho total do diretorio do Claude num dia (gravado a cada analise de uso de disco)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiskSnapshot {
+    pub date: String,
+    pub bytes: u64,
+    pub files: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiskUsageReport {
+    pub tree: DiskNode,
+    pub projects: Vec<ProjectDiskUsage>,
+    pub largest_files: Vec<LargeSessionFile>,
+    /// Snapshots diarios, do mais antigo ao de hoje (crescimento real entre analises)
+    pub history: Vec<DiskSnapshot>,
+    pub orphaned_bytes: u64,
+}
+
+/// Sessao nova criada a partir de um entry de outra
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ForkedSession {
+    pub session_id: String,
+    pub source_session_id: String,
+    pub file_path: String,
+    /// Entries copiados (caminho da raiz ate o entry escolhido)
+    pub entries: u32,
+}
+
+/// Projeto movido ou renomeado: sessoes de `old_path` (e subpastas) passam para `new_path`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProjectMigrationOptions {
+    pub old_path: String,
+    pub new_path: String,
+    /// So calcula o relatorio, sem alterar arquivos
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Pasta de projects/ que muda de slug
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectFolderMove {
+    pub old_path: String,
+    pub new_path: String,
+    pub old_slug: String,
+    pub new_slug: String,
+    pub sessions: u32,
+    /// Transcripts da pasta (sessoes e subagentes)
+    pub files: u32,
+    /// Entries com `cwd` reescrito
+    pub rewritten_entries: u32,
+    /// A pasta de destino ja existe (ex: o Claude ja rodou no path novo); os arquivos sao juntados
+    pub merge: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectMigrationReport {
+    pub dry_run: bool,
+    pub moves: Vec<ProjectFolderMove>,
+    /// .claude.json atualizado
+    pub config_path: Option<String>,
+    /// Chaves de `projects` no .claude.json que mudam de path
+    pub config_keys: Vec<String>,
+    pub old_path_exists: bool,
+    pub new_path_exists: bool,
+    /// Copia das pastas e do .claude.json feita antes da migracao
+    pub backup_dir: Option<String>,
+    pub errors: Vec<String>,
+}
+
+/// Local de um match dentro da sessao (para o viewer pular direto ao entry)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchHit {
+    /// Posicao do entry na sessao (mesma numeracao de read_session)
+    pub entry_index: u32,
+    pub uuid: Option<String>,
+    pub entry_type: String,
     pub timestamp: String,
+    /// Escopo onde o match ocorreu: "text", "tool_use" ou "tool_result"
+    pub scope: String,
+    pub snippet: String,
+    /// Transcript do subagente onde o match ocorreu (None = transcript da sessao);
+    /// `entry_index` se refere a esse arquivo
+    pub subagent_path: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SearchMatch {
     pub session: SessionMeta,
+    /// Trecho do hit mais relevante
     pub matched_text: String,
     pub entry_type: String,
+    /// Numero de entries da sessao que casaram com a busca
     pub match_count: u32,
+    /// Escopo do hit mais relevante
+    pub matched_scope: String,
+    /// Filtros da consulta que a sessao satisfez (ex: "tool:Bash")
+    pub matched_filters: Vec<String>,
+    /// Todos os hits da sessao, em ordem de entry
+    pub hits: Vec<SearchHit>,
+}
+
+/// Pagina de resultados de busca
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct SearchPage {
+    pub results: Vec<SearchMatch>,
+    pub total_sessions: u32,
+    pub total_hits: u32,
+    /// O indice devolveu mais que `MAX_INDEX_HITS` entries; so os primeiros foram agrupados
+    pub truncated: bool,
+    /// Cursor para a proxima pagina (None quando nao ha mais resultados)
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -0,0 +1,421 @@
+use serde::de::{self, DeserializeOwned, Deserializer};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Linha do transcript JSONL do Claude Code.
+/// Campos desconhecidos ficam em `extra` e tipos desconhecidos (de entry ou de bloco)
+/// sao mantidos como `Unknown(Value)`: serializar devolve o mesmo conteudo lido.
+#[derive(Debug, Clone)]
+pub enum TranscriptEntry {
+    User(MessageEntry),
+    Assistant(MessageEntry),
+    System(SystemEntry),
+    Summary(SummaryEntry),
+    /// Tipo desconhecido (ex: file-history-snapshot) ou entry fora do schema esperado
+    Unknown(Value),
+}
+
+/// Campos comuns a entries de conversa (user, assistant, system)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    /// Raiz da cadeia grava `"parentUuid": null`; serializado como null para manter a chave
+    #[serde(default)]
+    pub parent_uuid: Option<String>,
+    /// Pai logico quando a cadeia e reiniciada (ex: compact_boundary tem parentUuid nulo)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logical_parent_uuid: Option<String>,
+    #[serde(default)]
+    pub is_sidechain: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub is_meta: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Entry user ou assistant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageEntry {
+    #[serde(flatten)]
+    pub meta: EntryMeta,
+    pub message: Message,
+    /// Resultado estruturado da ferramenta (so em entries user com tool_result)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_use_result: Option<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemEntry {
+    #[serde(flatten)]
+    pub meta: EntryMeta,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtype: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummaryEntry {
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leaf_uuid: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Mensagem da API (campo `message` dos entries user/assistant)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Message {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub content: MessageContent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Conteudo de uma mensagem: texto simples ou lista de blocos
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MessageContent {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+impl Default for MessageContent {
+    fn default() -> Self {
+        MessageContent::Blocks(Vec::new())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Usage {
+    #[serde(default, deserialize_with = "zero_if_null")]
+    pub input_tokens: u64,
+    #[serde(default, deserialize_with = "zero_if_null")]
+    pub output_tokens: u64,
+    #[serde(default, deserialize_with = "zero_if_null")]
+    pub cache_creation_input_tokens: u64,
+    #[serde(default, deserialize_with = "zero_if_null")]
+    pub cache_read_input_tokens: u64,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Bloco de conteudo de uma mensagem
+#[derive(Debug, Clone)]
+pub enum ContentBlock {
+    Text(TextBlock),
+    Thinking(ThinkingBlock),
+    ToolUse(ToolUseBlock),
+    /// Ferramenta executada pela API (ex: web_search), mesmo formato de tool_use
+    ServerToolUse(ToolUseBlock),
+    ToolResult(ToolResultBlock),
+    Image(ImageBlock),
+    Unknown(Value),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextBlock {
+    #[serde(default)]
+    pub text: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThinkingBlock {
+    #[serde(default)]
+    pub thinking: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolUseBlock {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub input: Value,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolResultBlock {
+    #[serde(default)]
+    pub tool_use_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<ToolResultContent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_error: Option<bool>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Conteudo de um tool_result: texto simples ou lista de blocos (text, image)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ToolResultContent {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageBlock {
+    #[serde(default)]
+    pub source: Value,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl TranscriptEntry {
+    /// Valor do campo `type` da linha
+    pub fn entry_type(&self) -> &str {
+        match self {
+            TranscriptEntry::User(_) => "user",
+            TranscriptEntry::Assistant(_) => "assistant",
+            TranscriptEntry::System(_) => "system",
+            TranscriptEntry::Summary(_) => "summary",
+            TranscriptEntry::Unknown(value) => value.get("type").and_then(|t| t.as_str()).unwrap_or(""),
+        }
+    }
+
+    pub fn meta(&self) -> Option<&EntryMeta> {
+        match self {
+            TranscriptEntry::User(e) | TranscriptEntry::Assistant(e) => Some(&e.meta),
+            TranscriptEntry::System(e) => Some(&e.meta),
+            _ => None,
+        }
+    }
+
+    /// Entry user/assistant com a mensagem da API
+    pub fn message_entry(&self) -> Option<&MessageEntry> {
+        match self {
+            TranscriptEntry::User(e) | TranscriptEntry::Assistant(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub fn message(&self) -> Option<&Message> {
+        self.message_entry().map(|e| &e.message)
+    }
+
+    pub fn message_entry_mut(&mut self) -> Option<&mut MessageEntry> {
+        match self {
+            TranscriptEntry::User(e) | TranscriptEntry::Assistant(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub fn uuid(&self) -> Option<&str> {
+        match self {
+            TranscriptEntry::Unknown(value) => value.get("uuid").and_then(|u| u.as_str()),
+            _ => self.meta()?.uuid.as_deref(),
+        }
+    }
+
+    pub fn timestamp(&self) -> Option<&str> {
+        match self {
+            TranscriptEntry::Unknown(value) => value.get("timestamp").and_then(|t| t.as_str()),
+            _ => self.meta()?.timestamp.as_deref(),
+        }
+    }
+
+    pub fn cwd(&self) -> Option<&str> {
+        self.meta()?.cwd.as_deref().filter(|c| !c.is_empty())
+    }
+}
+
+impl Message {
+    /// Blocos da mensagem (vazio quando o conteudo e texto simples)
+    pub fn blocks(&self) -> &[ContentBlock] {
+        match &self.content {
+            MessageContent::Blocks(blocks) => blocks,
+            MessageContent::Text(_) => &[],
+        }
+    }
+
+    pub fn blocks_mut(&mut self) -> &mut [ContentBlock] {
+        match &mut self.content {
+            MessageContent::Blocks(blocks) => blocks,
+            MessageContent::Text(_) => &mut [],
+        }
+    }
+
+    /// Texto completo da mensagem (texto simples ou blocos de texto unidos por linha)
+    pub fn text(&self) -> String {
+        match &self.content {
+            MessageContent::Text(text) => text.clone(),
+            MessageContent::Blocks(blocks) => join_text(blocks),
+        }
+    }
+
+    /// Primeiro trecho de texto da mensagem
+    pub fn first_text(&self) -> Option<&str> {
+        match &self.content {
+            MessageContent::Text(text) => Some(text),
+            MessageContent::Blocks(blocks) => blocks.iter().find_map(|b| match b {
+                ContentBlock::Text(t) => Some(t.text.as_str()),
+                _ => None,
+            }),
+        }
+    }
+}
+
+impl ToolResultBlock {
+    /// Conteudo textual do resultado (blocos de texto unidos por linha)
+    pub fn text(&self) -> String {
+        match &self.content {
+            Some(ToolResultContent::Text(text)) => text.clone(),
+            Some(ToolResultContent::Blocks(blocks)) => join_text(blocks),
+            None => String::new(),
+        }
+    }
+}
+
+fn join_text(blocks: &[ContentBlock]) -> String {
+    blocks
+        .iter()
+        .filter_map(|b| match b {
+            ContentBlock::Text(t) => Some(t.text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// --- Serde: variantes decididas pelo campo `type`, com fallback lossless ---
+
+/// Struct de uma variante conhecida; o `type` cai em `extra` e e removido
+trait Tagged: DeserializeOwned + Serialize {
+    fn extra_mut(&mut self) -> &mut Map<String, Value>;
+}
+
+macro_rules! impl_tagged {
+    ($($ty:ty),*) => {
+        $(impl Tagged for $ty {
+            fn extra_mut(&mut self) -> &mut Map<String, Value> {
+                &mut self.extra
+            }
+        })*
+    };
+}
+
+impl_tagged!(MessageEntry, SystemEntry, SummaryEntry, TextBlock, ThinkingBlock, ToolUseBlock, ToolResultBlock, ImageBlock);
+
+fn from_tagged<T: Tagged>(value: &Value) -> Option<T> {
+    let mut parsed = T::deserialize(value).ok()?;
+    parsed.extra_mut().remove("type");
+    Some(parsed)
+}
+
+fn serialize_tagged<S: Serializer, T: Tagged>(serializer: S, tag: &str, inner: &T) -> Result<S::Ok, S::Error> {
+    let mut value = serde_json::to_value(inner).map_err(ser::Error::custom)?;
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("type".to_string(), Value::String(tag.to_string()));
+    }
+    value.serialize(serializer)
+}
+
+impl<'de> Deserialize<'de> for TranscriptEntry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if !value.is_object() {
+            return Err(de::Error::custom("transcript entry must be a JSON object"));
+        }
+
+        let parsed = match value.get("type").and_then(|t| t.as_str()) {
+            Some("user") => from_tagged(&value).map(TranscriptEntry::User),
+            Some("assistant") => from_tagged(&value).map(TranscriptEntry::Assistant),
+            Some("system") => from_tagged(&value).map(TranscriptEntry::System),
+            Some("summary") => from_tagged(&value).map(TranscriptEntry::Summary),
+            _ => None,
+        };
+        Ok(parsed.unwrap_or(TranscriptEntry::Unknown(value)))
+    }
+}
+
+impl Serialize for TranscriptEntry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            TranscriptEntry::User(e) => serialize_tagged(serializer, "user", e),
+            TranscriptEntry::Assistant(e) => serialize_tagged(serializer, "assistant", e),
+            TranscriptEntry::System(e) => serialize_tagged(serializer, "system", e),
+            TranscriptEntry::Summary(e) => serialize_tagged(serializer, "summary", e),
+            TranscriptEntry::Unknown(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ContentBlock {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+
+        let parsed = match value.get("type").and_then(|t| t.as_str()) {
+            Some("text") => from_tagged(&value).map(ContentBlock::Text),
+            Some("thinking") => from_tagged(&value).map(ContentBlock::Thinking),
+            Some("tool_use") => from_tagged(&value).map(ContentBlock::ToolUse),
+            Some("server_tool_use") => from_tagged(&value).map(ContentBlock::ServerToolUse),
+            Some("tool_result") => from_tagged(&value).map(ContentBlock::ToolResult),
+            Some("image") => from_tagged(&value).map(ContentBlock::Image),
+            _ => None,
+        };
+        Ok(parsed.unwrap_or(ContentBlock::Unknown(value)))
+    }
+}
+
+impl Serialize for ContentBlock {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ContentBlock::Text(b) => serialize_tagged(serializer, "text", b),
+            ContentBlock::Thinking(b) => serialize_tagged(serializer, "thinking", b),
+            ContentBlock::ToolUse(b) => serialize_tagged(serializer, "tool_use", b),
+            ContentBlock::ServerToolUse(b) => serialize_tagged(serializer, "server_tool_use", b),
+            ContentBlock::ToolResult(b) => serialize_tagged(serializer, "tool_result", b),
+            ContentBlock::Image(b) => serialize_tagged(serializer, "image", b),
+            ContentBlock::Unknown(value) => value.serialize(serializer),
+        }
+    }
+}
+
+fn zero_if_null<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    Ok(Option::<u64>::deserialize(deserializer)?.unwrap_or(0))
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
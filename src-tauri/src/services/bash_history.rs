@@ -0,0 +1,56 @@
+use crate::models::session::{BashCommand, BashHistoryOptions, SessionMeta};
+use crate::services::index_bash::{self, BashFilter};
+use crate::services::session_parser;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Comandos retornados quando o frontend nao informa limite
+const DEFAULT_LIMIT: usize = 500;
+
+/// Historico de comandos Bash de todas as sessoes, mais recentes primeiro
+pub async fn list_bash_commands(
+    claude_dir: &str,
+    options: &BashHistoryOptions,
+    cache_dir: &Path,
+    data_dir: &Path,
+) -> Result<Vec<BashCommand>, String> {
+    let sessions = session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+
+    let session_paths =
+        session_parser::session_paths_for_project(&sessions, options.project.as_deref());
+    let filter = BashFilter {
+        session_paths,
+        failed_only: options.failed_only,
+        text: options.query.clone(),
+        since: options.since.clone(),
+        until: options.until.clone(),
+        limit: options.limit.unwrap_or(DEFAULT_LIMIT),
+    };
+    let rows = index_bash::query(data_dir, filter).await?;
+
+    let by_path: HashMap<&str, &SessionMeta> =
+        sessions.iter().map(|s| (s.file_path.as_str(), s)).collect();
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let session = by_path.get(row.session_path.as_str())?;
+            Some(BashCommand {
+                session_id: session.id.clone(),
+                session_path: row.session_path,
+                project_path: session.project_path.clone(),
+                project_display: session.project_display.clone(),
+                subagent_path: row.subagent_path,
+                entry_index: row.entry_index,
+                timestamp: row.timestamp,
+                command: row.command,
+                description: row.description,
+                cwd: row.cwd,
+                output: row.output,
+                is_error: row.is_error,
+                exit_code: row.exit_code,
+                interrupted: row.interrupted,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,228 @@
+use crate::models::session::{CompactBoundary, ContextBloat, ContextPoint, ContextTimeline};
+use crate::models::transcript::{ContentBlock, TranscriptEntry};
+use crate::services::{conversation_tree, pricing, session_archive, session_parser};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Aumentos de contexto destacados por sessao
+const TOP_BLOAT: usize = 10;
+
+/// Tamanho do preview do entry que causou um aumento
+const PREVIEW_CHARS: usize = 160;
+
+/// Maior entry entre duas respostas do assistente (candidato a causa do aumento de contexto)
+struct Cause {
+    entry_index: u32,
+    kind: &'static str,
+    tool_name: Option<String>,
+    chars: u64,
+    preview: String,
+}
+
+/// Ocupacao do contexto a cada resposta do assistente, compactacoes e turnos que mais incharam o contexto
+pub async fn read_timeline(session_path: &str) -> Result<ContextTimeline, String> {
+    let path = Path::new(session_path);
+    let entries = session_parser::read_transcript(path).await?;
+    let session_id = session_archive::session_id_from_path(path);
+    Ok(build_timeline(session_id, &entries))
+}
+
+fn build_timeline(session_id: String, entries: &[TranscriptEntry]) -> ContextTimeline {
+    let active: HashSet<String> = conversation_tree::build_tree(entries)
+        .active_path
+        .into_iter()
+        .collect();
+
+    let mut timeline = ContextTimeline {
+        session_id,
+        ..Default::default()
+    };
+    let mut tool_names: HashMap<&str, &str> = HashMap::new();
+    let mut seen_messages: HashSet<&str> = HashSet::new();
+    let mut previous: Option<u64> = None;
+    let mut cause: Option<Cause> = None;
+    let mut bloat: Vec<ContextBloat> = Vec::new();
+
+    for (entry_index, entry) in entries.iter().enumerate() {
+        let entry_index = entry_index as u32;
+        let Some(meta) = entry.meta() else {
+            continue;
+        };
+        // Sidechains e ramos abandonados tem contexto proprio
+        if meta.is_sidechain
+            || meta
+                .uuid
+                .as_ref()
+                .is_some_and(|uuid| !active.contains(uuid))
+        {
+            continue;
+        }
+
+        if let Some(boundary) = compact_boundary(entry, entry_index) {
+            timeline.compactions.push(boundary);
+            previous = None;
+            cause = None;
+            continue;
+        }
+
+        let Some(message_entry) = entry.message_entry() else {
+            continue;
+        };
+        let message = &message_entry.message;
+
+        if let TranscriptEntry::Assistant(_) = entry {
+            // Cada bloco da resposta vira um entry com o mesmo usage: so o primeiro conta
+            let first_block = message
+                .id
+                .as_deref()
+                .is_none_or(|id| seen_messages.insert(id));
+            if let (true, Some(usage)) = (first_block, &message.usage) {
+                let model = message.model.as_deref().unwrap_or("");
+                let context_tokens = usage.input_tokens
+                    + usage.cache_read_input_tokens
+                    + usage.cache_creation_input_tokens;
+                // "<synthetic>" (erros locais) nao passa pela API
+                if !model.starts_with('<') && context_tokens > 0 {
+                    let delta = previous.map_or(0, |p| context_tokens as i64 - p as i64);
+                    if delta > 0 {
+                        bloat.push(to_bloat(entry_index, delta, cause.take()));
+                    }
+                    let window = pricing::context_window(model, context_tokens);
+                    timeline.points.push(ContextPoint {
+                        entry_index,
+                        uuid: meta.uuid.clone(),
+                        timestamp: meta.timestamp.clone().unwrap_or_default(),
+                        model: model.to_string(),
+                        context_tokens,
+                        output_tokens: usage.output_tokens,
+                        context_window: window,
+                        occupancy: context_tokens as f64 / window as f64,
+                        delta_tokens: delta,
+                    });
+                    previous = Some(context_tokens);
+                    cause = None;
+                }
+            }
+        }
+
+        // Conteudo que entra no contexto da proxima resposta
+        if message_entry.meta.is_meta {
+            continue;
+        }
+        for block in message.blocks() {
+            let candidate = match block {
+                ContentBlock::ToolUse(tool) => {
+                    tool_names.insert(tool.id.as_str(), tool.name.as_str());
+                    let input = tool.input.to_string();
+                    Some(("assistant", Some(tool.name.clone()), input))
+                }
+                ContentBlock::Text(text) if matches!(entry, TranscriptEntry::Assistant(_)) => {
+                    Some(("assistant", None, text.text.clone()))
+                }
+                ContentBlock::Text(text) => Some(("prompt", None, text.text.clone())),
+                ContentBlock::ToolResult(result) => Some((
+                    "tool_result",
+                    tool_names
+                        .get(result.tool_use_id.as_str())
+                        .map(|name| name.to_string()),
+                    result.text(),
+                )),
+                _ => None,
+            };
+            if let Some((kind, tool_name, text)) = candidate {
+                consider(&mut cause, entry_index, kind, tool_name, &text);
+            }
+        }
+        if message.blocks().is_empty() {
+            let kind = match entry {
+                TranscriptEntry::Assistant(_) => "assistant",
+                _ => "prompt",
+            };
+            consider(&mut cause, entry_index, kind, None, &message.text());
+        }
+    }
+
+    timeline.peak_tokens = timeline
+        .points
+        .iter()
+        .map(|p| p.context_tokens)
+        .max()
+        .unwrap_or(0);
+    timeline.context_window = timeline
+        .points
+        .iter()
+        .map(|p| p.context_window)
+        .max()
+        .unwrap_or(0);
+
+    bloat.sort_by_key(|b| std::cmp::Reverse(b.delta_tokens));
+    bloat.truncate(TOP_BLOAT);
+    timeline.top_bloat = bloat;
+    timeline
+}
+
+/// Entry `system` com subtype compact_boundary (`compactMetadata.trigger` e `preTokens`)
+fn compact_boundary(entry: &TranscriptEntry, entry_index: u32) -> Option<CompactBoundary> {
+    let TranscriptEntry::System(system) = entry else {
+        return None;
+    };
+    if system.subtype.as_deref() != Some("compact_boundary") {
+        return None;
+    }
+    let metadata = system.extra.get("compactMetadata");
+    Some(CompactBoundary {
+        entry_index,
+        uuid: system.meta.uuid.clone(),
+        timestamp: system.meta.timestamp.clone().unwrap_or_default(),
+        trigger: metadata
+            .and_then(|m| m.get("trigger"))
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        pre_tokens: metadata
+            .and_then(|m| m.get("preTokens"))
+            .and_then(|v| v.as_u64()),
+    })
+}
+
+fn consider(
+    cause: &mut Option<Cause>,
+    entry_index: u32,
+    kind: &'static str,
+    tool_name: Option<String>,
+    text: &str,
+) {
+    let chars = text.chars().count() as u64;
+    if chars == 0 || cause.as_ref().is_some_and(|c| c.chars >= chars) {
+        return;
+    }
+    *cause = Some(Cause {
+        entry_index,
+        kind,
+        tool_name,
+        chars,
+        preview: text.chars().take(PREVIEW_CHARS).collect(),
+    });
+}
+
+fn to_bloat(entry_index: u32, delta_tokens: i64, cause: Option<Cause>) -> ContextBloat {
+    match cause {
+        Some(cause) => ContextBloat {
+            entry_index,
+            delta_tokens,
+            cause_entry_index: Some(cause.entry_index),
+            cause: cause.kind.to_string(),
+            tool_name: cause.tool_name,
+            cause_chars: cause.chars,
+            preview: cause.preview,
+        },
+        None => ContextBloat {
+            entry_index,
+            delta_tokens,
+            cause_entry_index: None,
+            cause: "assistant".to_string(),
+            tool_name: None,
+            cause_chars: 0,
+            preview: String::new(),
+        },
+    }
+}
@@ -0,0 +1,194 @@
+use crate::models::session::{ConversationBranch, ConversationTree, TreeNode};
+use crate::models::transcript::TranscriptEntry;
+use crate::services::session_parser;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Tamanho do preview de um ramo
+const PREVIEW_CHARS: usize = 100;
+
+/// Entry da sessao com uuid, na ordem do arquivo
+struct Node<'a> {
+    entry_index: u32,
+    uuid: &'a str,
+    parent: Option<&'a str>,
+    is_sidechain: bool,
+    entry: &'a TranscriptEntry,
+}
+
+/// Le a sessao inteira e reconstroi a arvore da conversa
+pub async fn read_tree(path: &str) -> Result<ConversationTree, String> {
+    let entries = session_parser::read_transcript(Path::new(path)).await?;
+    Ok(build_tree(&entries))
+}
+
+/// Monta o DAG de uuid/parentUuid, escolhe a folha ativa e separa ramos abandonados e sidechains.
+/// A folha ativa e o ultimo entry user/assistant fora de sidechain, como no resume do Claude Code.
+pub fn build_tree(entries: &[TranscriptEntry]) -> ConversationTree {
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut by_uuid: HashMap<&str, usize> = HashMap::new();
+
+    for (entry_index, entry) in entries.iter().enumerate() {
+        let Some(meta) = entry.meta() else {
+            continue;
+        };
+        let Some(uuid) = meta.uuid.as_deref() else {
+            continue;
+        };
+        // uuid repetido (ex: historico copiado num resume): vale a primeira ocorrencia
+        if by_uuid.contains_key(uuid) {
+            continue;
+        }
+        by_uuid.insert(uuid, nodes.len());
+        nodes.push(Node {
+            entry_index: entry_index as u32,
+            uuid,
+            parent: meta
+                .parent_uuid
+                .as_deref()
+                .or(meta.logical_parent_uuid.as_deref()),
+            is_sidechain: meta.is_sidechain,
+            entry,
+        });
+    }
+
+    let parent_of = |idx: usize| nodes[idx].parent.and_then(|p| by_uuid.get(p).copied());
+
+    // Caminho ativo: sobe da folha ate a raiz (com protecao contra ciclos)
+    let active_leaf = nodes
+        .iter()
+        .rposition(|n| !n.is_sidechain && n.entry.message_entry().is_some());
+    let mut active_path = Vec::new();
+    let mut active: HashSet<usize> = HashSet::new();
+    let mut current = active_leaf;
+    while let Some(idx) = current {
+        if !active.insert(idx) {
+            break;
+        }
+        active_path.push(idx);
+        current = parent_of(idx);
+    }
+    active_path.reverse();
+
+    // Agrupa os demais entries pela raiz do ramo a que pertencem
+    let mut roots: HashMap<usize, usize> = HashMap::new();
+    let mut groups: Vec<(usize, Vec<usize>)> = Vec::new();
+    let mut group_of_root: HashMap<usize, usize> = HashMap::new();
+
+    for idx in 0..nodes.len() {
+        if active.contains(&idx) {
+            continue;
+        }
+        let root = branch_root(idx, &nodes, &active, &parent_of, &mut roots);
+        let group = *group_of_root.entry(root).or_insert_with(|| {
+            groups.push((root, Vec::new()));
+            groups.len() - 1
+        });
+        groups[group].1.push(idx);
+    }
+
+    let mut abandoned = Vec::new();
+    let mut sidechains = Vec::new();
+    let mut branch_of: HashMap<usize, &str> = HashMap::new();
+
+    for (root, members) in &groups {
+        let is_sidechain = nodes[*root].is_sidechain;
+        let kind = if is_sidechain { "sidechain" } else { "abandoned" };
+        for idx in members {
+            branch_of.insert(*idx, kind);
+        }
+
+        let branch = to_branch(&nodes, *root, members, parent_of(*root));
+        if is_sidechain {
+            sidechains.push(branch);
+        } else {
+            abandoned.push(branch);
+        }
+    }
+
+    let tree_nodes = nodes
+        .iter()
+        .enumerate()
+        .map(|(idx, node)| TreeNode {
+            uuid: node.uuid.to_string(),
+            parent_uuid: node.parent.map(String::from),
+            entry_index: node.entry_index,
+            entry_type: node.entry.entry_type().to_string(),
+            timestamp: node.entry.timestamp().unwrap_or_default().to_string(),
+            branch: branch_of.get(&idx).copied().unwrap_or("active").to_string(),
+        })
+        .collect();
+
+    ConversationTree {
+        active_leaf: active_leaf.map(|idx| nodes[idx].uuid.to_string()),
+        active_path: active_path.iter().map(|idx| nodes[*idx].uuid.to_string()).collect(),
+        abandoned,
+        sidechains,
+        nodes: tree_nodes,
+    }
+}
+
+/// Primeiro entry do ramo: sobe enquanto o pai existe, nao e ativo e tem o mesmo tipo (sidechain ou nao)
+fn branch_root(
+    idx: usize,
+    nodes: &[Node],
+    active: &HashSet<usize>,
+    parent_of: &impl Fn(usize) -> Option<usize>,
+    roots: &mut HashMap<usize, usize>,
+) -> usize {
+    let mut chain = Vec::new();
+    let mut current = idx;
+
+    let root = loop {
+        if let Some(root) = roots.get(&current) {
+            break *root;
+        }
+        chain.push(current);
+        match parent_of(current) {
+            Some(parent)
+                if !active.contains(&parent)
+                    && nodes[parent].is_sidechain == nodes[current].is_sidechain
+                    && !chain.contains(&parent) =>
+            {
+                current = parent
+            }
+            _ => break current,
+        }
+    };
+
+    for node in chain {
+        roots.insert(node, root);
+    }
+    root
+}
+
+fn to_branch(nodes: &[Node], root: usize, members: &[usize], fork: Option<usize>) -> ConversationBranch {
+    let timestamps: Vec<&str> = members
+        .iter()
+        .filter_map(|idx| nodes[*idx].entry.timestamp())
+        .collect();
+
+    let preview = members
+        .iter()
+        .filter_map(|idx| nodes[*idx].entry.message())
+        .map(|m| m.text())
+        .find(|t| !t.trim().is_empty())
+        .map(|t| t.chars().take(PREVIEW_CHARS).collect())
+        .unwrap_or_default();
+
+    // Membros estao em ordem de arquivo: o ultimo e a folha mais recente
+    let leaf = members.last().copied().unwrap_or(root);
+
+    ConversationBranch {
+        leaf_uuid: nodes[leaf].uuid.to_string(),
+        fork_uuid: fork.map(|idx| nodes[idx].uuid.to_string()),
+        uuids: members.iter().map(|idx| nodes[*idx].uuid.to_string()).collect(),
+        message_count: members
+            .iter()
+            .filter(|idx| nodes[**idx].entry.message_entry().is_some())
+            .count() as u32,
+        first_timestamp: timestamps.first().copied().unwrap_or_default().to_string(),
+        last_timestamp: timestamps.last().copied().unwrap_or_default().to_string(),
+        preview,
+    }
+}
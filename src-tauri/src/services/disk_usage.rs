@@ -0,0 +1,302 @@
+use crate::models::session::{
+    DiskNode, DiskSnapshot, DiskUsageReport, LargeSessionFile, ProjectDiskUsage,
+};
+use crate::services::project_resolver::{self, ProjectResolver};
+use crate::services::{session_archive, session_cleanup, session_parser, stats_reader, subagents};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Niveis de pasta na arvore (ex: projects/<slug>/<sessao>)
+const MAX_TREE_DEPTH: usize = 3;
+const MAX_LARGEST_FILES: usize = 25;
+
+/// Historico de tamanhos no diretorio de dados do app, por diretorio do Claude
+const HISTORY_FILE: &str = "disk-usage-history.json";
+
+#[derive(Default)]
+struct Node {
+    is_dir: bool,
+    bytes: u64,
+    files: u32,
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    /// Soma o arquivo em cada pasta do caminho ate MAX_TREE_DEPTH
+    fn add(&mut self, components: &[String], bytes: u64, depth: usize) {
+        self.bytes += bytes;
+        self.files += 1;
+        let Some((first, rest)) = components.split_first() else {
+            return;
+        };
+        if rest.is_empty() {
+            // Arquivos soltos so aparecem na raiz (history.jsonl, settings.json...)
+            if depth == 0 {
+                self.children
+                    .entry(first.clone())
+                    .or_default()
+                    .add(&[], bytes, depth + 1);
+            }
+        } else if depth < MAX_TREE_DEPTH {
+            let child = self.children.entry(first.clone()).or_default();
+            child.is_dir = true;
+            child.add(rest, bytes, depth + 1);
+        }
+    }
+
+    fn into_disk_node(self, name: String, path: &Path) -> DiskNode {
+        let mut children: Vec<DiskNode> = self
+            .children
+            .into_iter()
+            .map(|(name, node)| {
+                let child_path = path.join(&name);
+                node.into_disk_node(name, &child_path)
+            })
+            .collect();
+        children.sort_by_key(|c| std::cmp::Reverse(c.bytes));
+        DiskNode {
+            name,
+            path: path.to_string_lossy().to_string(),
+            is_dir: self.is_dir,
+            bytes: self.bytes,
+            files: self.files,
+            children,
+        }
+    }
+}
+
+/// Resultado da varredura do diretorio do Claude
+struct Scan {
+    tree: Node,
+    /// Ultima modificacao por pasta de projeto
+    project_mtimes: HashMap<String, SystemTime>,
+    largest: Vec<(PathBuf, u64, Option<SystemTime>)>,
+}
+
+/// Uso de disco do diretorio do Claude: arvore de tamanhos, projetos, maiores sessoes,
+/// historico de tamanho e pastas de projetos que nao existem mais
+pub async fn disk_usage(
+    claude_dir: &str,
+    cache_dir: &Path,
+    data_dir: &Path,
+    project_dir: impl Fn(&str) -> PathBuf,
+) -> Result<DiskUsageReport, String> {
+    let root = PathBuf::from(claude_dir);
+    if !root.is_dir() {
+        return Err(format!("Claude directory not found: {}", claude_dir));
+    }
+
+    let sessions = session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+    let resolver = ProjectResolver::load(&root).await;
+
+    let scan_root = root.clone();
+    let scan = tokio::task::spawn_blocking(move || scan(&scan_root))
+        .await
+        .map_err(|e| format!("Disk usage task failed: {}", e))?;
+
+    // Path do projeto de cada pasta de projects/ (vem das sessoes listadas)
+    let mut by_slug: HashMap<String, (String, String, u32)> = HashMap::new();
+    for session in &sessions {
+        let Some(slug) = Path::new(&session.file_path)
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        let entry = by_slug.entry(slug).or_insert_with(|| {
+            (
+                session.project_path.clone(),
+                session.project_display.clone(),
+                0,
+            )
+        });
+        entry.2 += 1;
+    }
+
+    let mut projects: Vec<ProjectDiskUsage> = scan
+        .tree
+        .children
+        .get("projects")
+        .map(|projects| {
+            projects
+                .children
+                .iter()
+                .filter(|(_, node)| node.is_dir)
+                .map(|(slug, node)| {
+                    let (project_path, project_display, sessions) =
+                        by_slug.get(slug).cloned().unwrap_or_else(|| {
+                            let path = resolver.resolve(slug, &[]);
+                            let display = project_resolver::display_name(&path);
+                            (path, display, 0)
+                        });
+                    ProjectDiskUsage {
+                        orphaned: session_cleanup::is_missing(&project_path, &project_dir),
+                        slug: slug.clone(),
+                        project_path,
+                        project_display,
+                        bytes: node.bytes,
+                        files: node.files,
+                        sessions,
+                        last_modified: scan.project_mtimes.get(slug).map(|t| to_rfc3339(*t)),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    projects.sort_by_key(|p| std::cmp::Reverse(p.bytes));
+
+    let display_by_dir: HashMap<PathBuf, String> = projects
+        .iter()
+        .map(|p| {
+            (
+                root.join("projects").join(&p.slug),
+                p.project_display.clone(),
+            )
+        })
+        .collect();
+    let largest_files = scan
+        .largest
+        .into_iter()
+        .map(|(path, bytes, modified)| {
+            let file_name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            // Sessao dona do arquivo: pasta de projeto e o primeiro nivel abaixo de projects/
+            let project_display = path
+                .ancestors()
+                .find_map(|a| display_by_dir.get(a))
+                .cloned()
+                .unwrap_or_default();
+            LargeSessionFile {
+                path: path.to_string_lossy().to_string(),
+                session_id: session_archive::session_id(&file_name).to_string(),
+                project_display,
+                bytes,
+                archived: session_archive::is_compressed(&path),
+                subagent: subagents::is_agent_file(&file_name),
+                modified: modified.map(to_rfc3339),
+            }
+        })
+        .collect();
+
+    let history = record_snapshot(
+        data_dir,
+        claude_dir,
+        DiskSnapshot {
+            date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            bytes: scan.tree.bytes,
+            files: scan.tree.files,
+        },
+    )
+    .await?;
+
+    Ok(DiskUsageReport {
+        orphaned_bytes: projects
+            .iter()
+            .filter(|p| p.orphaned)
+            .map(|p| p.bytes)
+            .sum(),
+        tree: scan.tree.into_disk_node(
+            root.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| claude_dir.to_string()),
+            &root,
+        ),
+        projects,
+        largest_files,
+        history,
+    })
+}
+
+fn scan(root: &Path) -> Scan {
+    let mut tree = Node {
+        is_dir: true,
+        ..Default::default()
+    };
+    let mut project_mtimes: HashMap<String, SystemTime> = HashMap::new();
+
+    stats_reader::walk_dir(root, &|_| true, &mut |path, metadata| {
+        let Ok(relative) = path.strip_prefix(root) else {
+            return;
+        };
+        let components: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+        tree.add(&components, metadata.len(), 0);
+
+        let Ok(modified) = metadata.modified() else {
+            return;
+        };
+        if let [first, slug, _, ..] = components.as_slice() {
+            if first == "projects" {
+                let latest = project_mtimes.entry(slug.clone()).or_insert(modified);
+                *latest = (*latest).max(modified);
+            }
+        }
+    });
+
+    // Maiores transcripts (sessoes e subagentes)
+    let mut largest: Vec<(PathBuf, u64, Option<SystemTime>)> =
+        stats_reader::collect_jsonl_files(&root.join("projects"))
+            .into_iter()
+            .filter_map(|path| {
+                let metadata = std::fs::metadata(&path).ok()?;
+                Some((path, metadata.len(), metadata.modified().ok()))
+            })
+            .collect();
+    largest.sort_by_key(|l| std::cmp::Reverse(l.1));
+    largest.truncate(MAX_LARGEST_FILES);
+
+    Scan {
+        tree,
+        project_mtimes,
+        largest,
+    }
+}
+
+/// Grava o snapshot de hoje (substitui o anterior do mesmo dia) e devolve o historico
+async fn record_snapshot(
+    data_dir: &Path,
+    claude_dir: &str,
+    snapshot: DiskSnapshot,
+) -> Result<Vec<DiskSnapshot>, String> {
+    let path = data_dir.join(HISTORY_FILE);
+    let mut all: BTreeMap<String, Vec<DiskSnapshot>> = tokio::fs::read_to_string(&path)
+        .await
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    let history = all.entry(claude_dir.to_string()).or_default();
+    if history
+        .last()
+        .is_some_and(|last| last.date == snapshot.date)
+    {
+        history.pop();
+    }
+    history.push(snapshot);
+    let history = history.clone();
+
+    tokio::fs::create_dir_all(data_dir)
+        .await
+        .map_err(|e| format!("Failed to create data dir: {}", e))?;
+    let content = serde_json::to_string(&all)
+        .map_err(|e| format!("Failed to serialize disk usage history: {}", e))?;
+    let tmp_path = data_dir.join(format!("{}.tmp", HISTORY_FILE));
+    tokio::fs::write(&tmp_path, content)
+        .await
+        .map_err(|e| format!("Failed to write disk usage history: {}", e))?;
+    tokio::fs::rename(&tmp_path, &path)
+        .await
+        .map_err(|e| format!("Failed to replace disk usage history: {}", e))?;
+
+    Ok(history)
+}
+
+fn to_rfc3339(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
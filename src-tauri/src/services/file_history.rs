@@ -0,0 +1,63 @@
+use crate::models::session::{FileSession, SessionFiles, SessionMeta, TouchedFile};
+use crate::services::index_file_touches::{self, TouchRow};
+use crate::services::session_parser;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Arquivos lidos e modificados por uma sessao
+pub async fn session_files(
+    claude_dir: &str,
+    session_path: &str,
+    cache_dir: &Path,
+    data_dir: &Path,
+) -> Result<SessionFiles, String> {
+    session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+    let rows = index_file_touches::query(data_dir, Some(session_path.to_string()), None).await?;
+
+    let mut files = SessionFiles::default();
+    for row in rows {
+        let file = to_touched_file(row);
+        if file.modifications > 0 {
+            files.modified.push(file);
+        } else {
+            files.read.push(file);
+        }
+    }
+    Ok(files)
+}
+
+/// Sessoes que leram ou modificaram `file_path`, em ordem do primeiro acesso.
+/// Path relativo casa pelo sufixo (ex: `src/main.rs`).
+pub async fn sessions_for_file(
+    claude_dir: &str,
+    file_path: &str,
+    cache_dir: &Path,
+    data_dir: &Path,
+) -> Result<Vec<FileSession>, String> {
+    let sessions = session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+    let rows = index_file_touches::query(data_dir, None, Some(file_path.to_string())).await?;
+
+    let by_path: HashMap<&str, &SessionMeta> =
+        sessions.iter().map(|s| (s.file_path.as_str(), s)).collect();
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let session = (*by_path.get(row.session_path.as_str())?).clone();
+            Some(FileSession {
+                session,
+                file: to_touched_file(row),
+            })
+        })
+        .collect())
+}
+
+fn to_touched_file(row: TouchRow) -> TouchedFile {
+    TouchedFile {
+        file_path: row.file_path,
+        reads: row.reads,
+        modifications: row.modifications,
+        first_timestamp: row.first_timestamp,
+        last_timestamp: row.last_timestamp,
+    }
+}
@@ -0,0 +1,184 @@
+use crate::models::transcript::{ContentBlock, MessageEntry};
+use crate::services::search_index::{self, like_escape, push_scope, where_clause};
+use regex::Regex;
+use rusqlite::{params, Connection};
+use std::path::Path;
+use std::sync::LazyLock;
+
+pub const TABLE: &str = "bash_commands";
+
+pub const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS bash_commands (
+         path TEXT NOT NULL,
+         tool_use_id TEXT NOT NULL,
+         entry_index INTEGER NOT NULL,
+         timestamp TEXT NOT NULL,
+         command TEXT NOT NULL,
+         description TEXT,
+         cwd TEXT,
+         output TEXT,
+         is_error INTEGER NOT NULL DEFAULT 0,
+         exit_code INTEGER,
+         interrupted INTEGER NOT NULL DEFAULT 0
+     );
+     CREATE INDEX IF NOT EXISTS bash_commands_path ON bash_commands (path, tool_use_id);
+     CREATE INDEX IF NOT EXISTS bash_commands_timestamp ON bash_commands (timestamp);";
+
+/// Caracteres de saida guardados por comando Bash
+const BASH_OUTPUT_CHARS: usize = 2000;
+
+/// Codigo de saida no texto de um tool_result de Bash com erro ("Exit code 1")
+static EXIT_CODE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^(?:Error: )?Exit code (\d+)").expect("valid exit code regex")
+});
+
+/// Comando Bash executado, com o resultado do tool_result correspondente
+#[derive(Debug, Clone)]
+pub struct BashRow {
+    pub session_path: String,
+    /// Transcript do subagente que rodou o comando (None = transcript da sessao)
+    pub subagent_path: Option<String>,
+    pub entry_index: u32,
+    pub timestamp: String,
+    pub command: String,
+    pub description: Option<String>,
+    pub cwd: Option<String>,
+    /// None enquanto o tool_result nao foi gravado
+    pub output: Option<String>,
+    pub is_error: bool,
+    pub exit_code: Option<i32>,
+    pub interrupted: bool,
+}
+
+/// Filtros de `query`
+#[derive(Debug, Clone, Default)]
+pub struct BashFilter {
+    /// Restringe a estas sessoes (None = todas)
+    pub session_paths: Option<Vec<String>>,
+    pub failed_only: bool,
+    /// Substring do comando ou da descricao
+    pub text: Option<String>,
+    /// Datas YYYY-MM-DD (inclusivas)
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub limit: usize,
+}
+
+/// Registra tool_use de Bash; o tool_result correspondente completa a linha com saida e erro
+pub fn record(
+    conn: &Connection,
+    path: &str,
+    entry_index: u32,
+    entry: &MessageEntry,
+) -> Result<(), String> {
+    let timestamp = entry.meta.timestamp.as_deref().unwrap_or("");
+
+    for block in entry.message.blocks() {
+        match block {
+            ContentBlock::ToolUse(tool) if tool.name == "Bash" => {
+                let Some(command) = tool.input.get("command").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let description = tool.input.get("description").and_then(|v| v.as_str());
+                conn.prepare_cached(
+                    "INSERT INTO bash_commands (path, tool_use_id, entry_index, timestamp, command, description, cwd)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                )
+                .and_then(|mut stmt| {
+                    stmt.execute(params![
+                        path,
+                        tool.id,
+                        entry_index,
+                        timestamp,
+                        command,
+                        description,
+                        entry.meta.cwd
+                    ])
+                })
+                .map_err(|e| format!("Failed to index bash command: {}", e))?;
+            }
+            ContentBlock::ToolResult(result) => {
+                let text = result.text();
+                let is_error = result.is_error == Some(true);
+                let exit_code = match EXIT_CODE.captures(&text) {
+                    Some(caps) => caps[1].parse::<i32>().ok(),
+                    None if !is_error => Some(0),
+                    None => None,
+                };
+                let interrupted = entry
+                    .tool_use_result
+                    .as_ref()
+                    .and_then(|r| r.get("interrupted"))
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                let output: String = text.chars().take(BASH_OUTPUT_CHARS).collect();
+                conn.prepare_cached(
+                    "UPDATE bash_commands SET output = ?3, is_error = ?4, exit_code = ?5, interrupted = ?6
+                     WHERE path = ?1 AND tool_use_id = ?2",
+                )
+                .and_then(|mut stmt| {
+                    stmt.execute(params![
+                        path,
+                        result.tool_use_id,
+                        output,
+                        is_error,
+                        exit_code,
+                        interrupted
+                    ])
+                })
+                .map_err(|e| format!("Failed to index bash result: {}", e))?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Comandos Bash mais recentes primeiro
+pub async fn query(data_dir: &Path, filter: BashFilter) -> Result<Vec<BashRow>, String> {
+    let mut conditions = Vec::new();
+    let mut values: Vec<String> = Vec::new();
+    if !push_scope(
+        &mut conditions,
+        &mut values,
+        filter.session_paths,
+        filter.since,
+        filter.until,
+    ) {
+        return Ok(Vec::new());
+    }
+    if filter.failed_only {
+        conditions.push("(is_error = 1 OR exit_code != 0)".to_string());
+    }
+    if let Some(text) = filter.text.as_deref().filter(|t| !t.trim().is_empty()) {
+        conditions.push("(command LIKE ? ESCAPE '!' OR description LIKE ? ESCAPE '!')".to_string());
+        let pattern = format!("%{}%", like_escape(text));
+        values.push(pattern.clone());
+        values.push(pattern);
+    }
+
+    let sql = format!(
+        "SELECT session_path, nullif(path, session_path), entry_index, timestamp, command, description, cwd,
+                output, is_error, exit_code, interrupted
+         FROM bash_commands JOIN files USING (path) {}
+         ORDER BY timestamp DESC, bash_commands.rowid DESC LIMIT {}",
+        where_clause(&conditions),
+        filter.limit
+    );
+    search_index::run_query(data_dir, "bash", sql, values, |row| {
+        Ok(BashRow {
+            session_path: row.get(0)?,
+            subagent_path: row.get(1)?,
+            entry_index: row.get(2)?,
+            timestamp: row.get(3)?,
+            command: row.get(4)?,
+            description: row.get(5)?,
+            cwd: row.get(6)?,
+            output: row.get(7)?,
+            is_error: row.get(8)?,
+            exit_code: row.get(9)?,
+            interrupted: row.get(10)?,
+        })
+    })
+    .await
+}
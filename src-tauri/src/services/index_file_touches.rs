@@ -0,0 +1,125 @@
+use crate::models::transcript::{ContentBlock, MessageEntry};
+use crate::services::search_index::{self, like_escape, where_clause};
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+pub const TABLE: &str = "file_touches";
+
+pub const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS file_touches (
+         path TEXT NOT NULL,
+         file_path TEXT NOT NULL,
+         modified INTEGER NOT NULL,
+         tool_use_id TEXT NOT NULL,
+         entry_index INTEGER NOT NULL,
+         timestamp TEXT NOT NULL
+     );
+     CREATE INDEX IF NOT EXISTS file_touches_file ON file_touches (file_path);
+     CREATE INDEX IF NOT EXISTS file_touches_path ON file_touches (path, tool_use_id);";
+
+/// Ferramentas que leem (false) ou modificam (true) um arquivo, com o campo do path no input
+const FILE_TOOLS: &[(&str, &str, bool)] = &[
+    ("Read", "file_path", false),
+    ("Edit", "file_path", true),
+    ("MultiEdit", "file_path", true),
+    ("Write", "file_path", true),
+    ("NotebookEdit", "notebook_path", true),
+];
+
+/// Arquivo lido/modificado por tool_use, agregado por sessao ou por arquivo
+#[derive(Debug, Clone)]
+pub struct TouchRow {
+    pub session_path: String,
+    pub file_path: String,
+    pub reads: u32,
+    pub modifications: u32,
+    pub first_timestamp: String,
+    pub last_timestamp: String,
+}
+
+/// Registra Read/Edit/Write/MultiEdit/NotebookEdit do entry.
+/// Um tool_result com erro desfaz o registro do tool_use correspondente.
+pub fn record(
+    conn: &Connection,
+    path: &str,
+    entry_index: u32,
+    entry: &MessageEntry,
+) -> Result<(), String> {
+    let timestamp = entry.meta.timestamp.as_deref().unwrap_or("");
+
+    for block in entry.message.blocks() {
+        match block {
+            ContentBlock::ToolUse(tool) => {
+                let Some((_, field, modified)) =
+                    FILE_TOOLS.iter().find(|(name, ..)| *name == tool.name)
+                else {
+                    continue;
+                };
+                let Some(file_path) = tool.input.get(*field).and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                conn.prepare_cached(
+                    "INSERT INTO file_touches (path, file_path, modified, tool_use_id, entry_index, timestamp)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                )
+                .and_then(|mut stmt| {
+                    stmt.execute(params![path, file_path, modified, tool.id, entry_index, timestamp])
+                })
+                .map_err(|e| format!("Failed to index file touch: {}", e))?;
+            }
+            ContentBlock::ToolResult(result) if result.is_error == Some(true) => {
+                conn.prepare_cached(
+                    "DELETE FROM file_touches WHERE path = ?1 AND tool_use_id = ?2",
+                )
+                .and_then(|mut stmt| stmt.execute(params![path, result.tool_use_id]))
+                .map_err(|e| format!("Failed to index file touch: {}", e))?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Arquivos tocados, agrupados por (sessao, arquivo).
+/// `session_path` restringe a uma sessao; `file_path` a um arquivo (path exato ou sufixo, para paths relativos).
+pub async fn query(
+    data_dir: &Path,
+    session_path: Option<String>,
+    file_path: Option<String>,
+) -> Result<Vec<TouchRow>, String> {
+    let mut conditions = Vec::new();
+    let mut values = Vec::new();
+
+    if let Some(session_path) = session_path {
+        conditions.push("session_path = ?".to_string());
+        values.push(session_path);
+    }
+    if let Some(file_path) = file_path {
+        let normalized = file_path.replace('\\', "/");
+        if normalized.starts_with('/') || normalized.get(1..2) == Some(":") {
+            conditions.push("replace(file_path, '\\', '/') = ?".to_string());
+            values.push(normalized);
+        } else {
+            conditions.push("replace(file_path, '\\', '/') LIKE ? ESCAPE '!'".to_string());
+            values.push(format!("%/{}", like_escape(&normalized)));
+        }
+    }
+
+    let sql = format!(
+        "SELECT session_path, file_path, sum(modified = 0), sum(modified = 1), min(timestamp), max(timestamp)
+         FROM file_touches JOIN files USING (path) {}
+         GROUP BY session_path, file_path ORDER BY min(timestamp)",
+        where_clause(&conditions)
+    );
+    search_index::run_query(data_dir, "file", sql, values, |row| {
+        Ok(TouchRow {
+            session_path: row.get(0)?,
+            file_path: row.get(1)?,
+            reads: row.get(2)?,
+            modifications: row.get(3)?,
+            first_timestamp: row.get(4)?,
+            last_timestamp: row.get(5)?,
+        })
+    })
+    .await
+}
@@ -0,0 +1,139 @@
+use crate::models::transcript::{ContentBlock, MessageEntry};
+use crate::services::permissions;
+use crate::services::search_index::{self, push_scope, where_clause};
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+pub const TABLE: &str = "permission_events";
+
+pub const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS permission_events (
+         path TEXT NOT NULL,
+         tool_use_id TEXT,
+         entry_index INTEGER NOT NULL,
+         timestamp TEXT NOT NULL,
+         tool_name TEXT,
+         rule TEXT NOT NULL,
+         example TEXT NOT NULL,
+         outcome TEXT NOT NULL
+     );
+     CREATE INDEX IF NOT EXISTS permission_events_path ON permission_events (path, tool_use_id);";
+
+/// Caracteres guardados do exemplo de invocacao (comando, URL, arquivo)
+const PERMISSION_EXAMPLE_CHARS: usize = 200;
+
+/// tool_result gravado pelo Claude Code quando o usuario rejeita a chamada
+const REJECTED_TOOL_USE: &str = "doesn't want to proceed with this tool use";
+
+/// tool_result gravado quando o usuario cancela (Esc) durante o pedido de permissao ou a execucao
+const CANCELLED_TOOL_USE: &str = "doesn't want to take this action right now";
+
+/// Marcador de interrupcao de turno ("[Request interrupted by user for tool use]" vem com o tool_result cancelado)
+const INTERRUPT_MARKER: &str = "[Request interrupted by user]";
+
+/// Chamada que pede permissao (com o desfecho) ou interrupcao de turno
+#[derive(Debug, Clone)]
+pub struct PermissionRow {
+    pub session_path: String,
+    pub timestamp: String,
+    /// None em interrupcoes de turno
+    pub tool_name: Option<String>,
+    /// Regra no formato de `permissions.allow` (vazia sem prefixo seguro)
+    pub rule: String,
+    pub example: String,
+    /// "pending" (sem resultado), "executed", "rejected" ou "interrupted".
+    /// "executed" nao distingue prompt aprovado de chamada liberada por modo ou regra
+    pub outcome: String,
+}
+
+/// Registra chamadas que pedem permissao; o tool_result define o desfecho (rejeitada, cancelada ou executada)
+pub fn record(
+    conn: &Connection,
+    path: &str,
+    entry_index: u32,
+    entry: &MessageEntry,
+) -> Result<(), String> {
+    let timestamp = entry.meta.timestamp.as_deref().unwrap_or("");
+
+    for block in entry.message.blocks() {
+        match block {
+            ContentBlock::ToolUse(tool) => {
+                let Some(rule) = permissions::rule_for(&tool.name, &tool.input) else {
+                    continue;
+                };
+                let example = ["command", "url", "file_path", "notebook_path", "query"]
+                    .iter()
+                    .find_map(|key| tool.input.get(*key).and_then(|v| v.as_str()))
+                    .unwrap_or("")
+                    .chars()
+                    .take(PERMISSION_EXAMPLE_CHARS)
+                    .collect::<String>();
+                conn.prepare_cached(
+                    "INSERT INTO permission_events (path, tool_use_id, entry_index, timestamp, tool_name, rule, example, outcome)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'pending')",
+                )
+                .and_then(|mut stmt| {
+                    stmt.execute(params![path, tool.id, entry_index, timestamp, tool.name, rule, example])
+                })
+                .map_err(|e| format!("Failed to index permission event: {}", e))?;
+            }
+            ContentBlock::ToolResult(result) => {
+                let text = result.text();
+                let outcome = if text.contains(REJECTED_TOOL_USE) {
+                    "rejected"
+                } else if text.contains(CANCELLED_TOOL_USE) {
+                    "interrupted"
+                } else {
+                    "executed"
+                };
+                conn.prepare_cached(
+                    "UPDATE permission_events SET outcome = ?3 WHERE path = ?1 AND tool_use_id = ?2",
+                )
+                .and_then(|mut stmt| stmt.execute(params![path, result.tool_use_id, outcome]))
+                .map_err(|e| format!("Failed to index permission event: {}", e))?;
+            }
+            ContentBlock::Text(text) if text.text.trim() == INTERRUPT_MARKER => {
+                conn.prepare_cached(
+                    "INSERT INTO permission_events (path, entry_index, timestamp, rule, example, outcome)
+                     VALUES (?1, ?2, ?3, '', '', 'interrupted')",
+                )
+                .and_then(|mut stmt| stmt.execute(params![path, entry_index, timestamp]))
+                .map_err(|e| format!("Failed to index permission event: {}", e))?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Chamadas que pedem permissao e interrupcoes (inclusive de subagentes), em ordem de arquivo
+pub async fn query(
+    data_dir: &Path,
+    session_paths: Option<Vec<String>>,
+    since: Option<String>,
+    until: Option<String>,
+) -> Result<Vec<PermissionRow>, String> {
+    let mut conditions = Vec::new();
+    let mut values: Vec<String> = Vec::new();
+    if !push_scope(&mut conditions, &mut values, session_paths, since, until) {
+        return Ok(Vec::new());
+    }
+
+    let sql = format!(
+        "SELECT session_path, timestamp, tool_name, rule, example, outcome
+         FROM permission_events JOIN files USING (path) {}
+         ORDER BY session_path, path, entry_index",
+        where_clause(&conditions)
+    );
+    search_index::run_query(data_dir, "permission", sql, values, |row| {
+        Ok(PermissionRow {
+            session_path: row.get(0)?,
+            timestamp: row.get(1)?,
+            tool_name: row.get(2)?,
+            rule: row.get(3)?,
+            example: row.get(4)?,
+            outcome: row.get(5)?,
+        })
+    })
+    .await
+}
@@ -0,0 +1,184 @@
+use crate::models::transcript::TranscriptEntry;
+use crate::services::search_index::{self, push_scope, where_clause};
+use regex::Regex;
+use rusqlite::{params, Connection};
+use std::path::Path;
+use std::sync::LazyLock;
+
+pub const TABLE: &str = "rate_limits";
+
+pub const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS rate_limits (
+         path TEXT NOT NULL,
+         entry_index INTEGER NOT NULL,
+         timestamp TEXT NOT NULL,
+         kind TEXT NOT NULL,
+         message TEXT NOT NULL,
+         reset_at TEXT,
+         reset_hint TEXT
+     );
+     CREATE INDEX IF NOT EXISTS rate_limits_path ON rate_limits (path);
+     CREATE INDEX IF NOT EXISTS rate_limits_timestamp ON rate_limits (timestamp);";
+
+/// Caracteres guardados da mensagem de erro de limite
+const RATE_LIMIT_MESSAGE_CHARS: usize = 300;
+
+/// Erro 429 da API (rate limit por minuto / overload de conta)
+static RATE_LIMIT_TEXT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b429\b|rate_limit_error|rate limit").expect("valid rate limit regex")
+});
+
+/// Limite de uso do plano ("Claude AI usage limit reached", "5-hour limit reached", "You've hit your limit")
+static USAGE_LIMIT_TEXT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)usage limit|limit reached|hit your limit").expect("valid usage limit regex")
+});
+
+/// Reset em epoch no formato antigo ("Claude AI usage limit reached|1735689600")
+static RESET_EPOCH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\|(\d{10})\b").expect("valid reset epoch regex"));
+
+/// Reset em texto ("resets 3pm", "will reset at 3pm (America/Sao_Paulo).")
+static RESET_HINT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\breset(?:s)?\s+(?:at\s+)?([^.\n]+)").expect("valid reset hint regex")
+});
+
+/// Erro de limite (rate limit ou limite de uso do plano) gravado no transcript
+#[derive(Debug, Clone)]
+pub struct RateLimitRow {
+    pub session_path: String,
+    /// Transcript do subagente onde o erro ocorreu (None = transcript da sessao)
+    pub subagent_path: Option<String>,
+    pub entry_index: u32,
+    pub timestamp: String,
+    /// "usage_limit" (limite do plano) ou "rate_limit" (429 da API)
+    pub kind: String,
+    pub message: String,
+    /// Ultimo modelo usado pela sessao antes do erro
+    pub model: Option<String>,
+    /// Horario do reset quando o transcript traz um valor absoluto (epoch ou retryInMs)
+    pub reset_at: Option<String>,
+    /// Reset como escrito na mensagem (ex: "3pm (America/Sao_Paulo)")
+    pub reset_hint: Option<String>,
+}
+
+/// Registra erros de limite: mensagem sintetica do assistente (`isApiErrorMessage`, ex:
+/// "Claude AI usage limit reached|1735689600", "5-hour limit reached ∙ resets 3pm", "API Error: 429 ...")
+/// ou entry system `api_error` com status 429 (retentativa automatica, reset em `retryInMs`)
+pub fn record(
+    conn: &Connection,
+    path: &str,
+    entry_index: u32,
+    entry: &TranscriptEntry,
+) -> Result<(), String> {
+    let Some(timestamp) = entry.timestamp().filter(|t| !t.is_empty()) else {
+        return Ok(());
+    };
+
+    let (kind, message, reset_at, reset_hint) = match entry {
+        TranscriptEntry::Assistant(assistant) => {
+            let api_error = assistant
+                .extra
+                .get("isApiErrorMessage")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+                || assistant.message.model.as_deref().is_some_and(|m| m.starts_with('<'));
+            if !api_error {
+                return Ok(());
+            }
+            let text = assistant.message.text();
+            let kind = if RATE_LIMIT_TEXT.is_match(&text) {
+                "rate_limit"
+            } else if USAGE_LIMIT_TEXT.is_match(&text) {
+                "usage_limit"
+            } else {
+                return Ok(());
+            };
+            let reset_at = RESET_EPOCH
+                .captures(&text)
+                .and_then(|c| c[1].parse::<i64>().ok())
+                .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+                .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+            let reset_hint = RESET_HINT
+                .captures(&text)
+                .map(|c| c[1].trim().to_string())
+                .filter(|h| !h.is_empty());
+            let message = RESET_EPOCH.replace(&text, "").trim().to_string();
+            (kind, message, reset_at, reset_hint)
+        }
+        TranscriptEntry::System(system) if system.subtype.as_deref() == Some("api_error") => {
+            let error = system.extra.get("error");
+            let status = error
+                .and_then(|e| e.get("status"))
+                .and_then(|v| v.as_u64());
+            let error_text = error.map(|e| e.to_string()).unwrap_or_default();
+            if status != Some(429) && !RATE_LIMIT_TEXT.is_match(&error_text) {
+                return Ok(());
+            }
+            let message = error
+                .and_then(|e| e.pointer("/error/error/message").or_else(|| e.get("message")))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+                .or_else(|| system.content.clone())
+                .unwrap_or(error_text);
+            let reset_at = system
+                .extra
+                .get("retryInMs")
+                .and_then(|v| v.as_f64())
+                .zip(chrono::DateTime::parse_from_rfc3339(timestamp).ok())
+                .map(|(ms, t)| t + chrono::Duration::milliseconds(ms as i64))
+                .map(|t| t.to_utc().to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+            ("rate_limit", message, reset_at, None)
+        }
+        _ => return Ok(()),
+    };
+
+    let message: String = message.chars().take(RATE_LIMIT_MESSAGE_CHARS).collect();
+    conn.prepare_cached(
+        "INSERT INTO rate_limits (path, entry_index, timestamp, kind, message, reset_at, reset_hint)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )
+    .and_then(|mut stmt| {
+        stmt.execute(params![path, entry_index, timestamp, kind, message, reset_at, reset_hint])
+    })
+    .map_err(|e| format!("Failed to index rate limit: {}", e))?;
+    Ok(())
+}
+
+/// Erros de limite, mais recentes primeiro, com o ultimo modelo real da sessao antes de cada um
+pub async fn query(
+    data_dir: &Path,
+    session_paths: Option<Vec<String>>,
+    since: Option<String>,
+    until: Option<String>,
+) -> Result<Vec<RateLimitRow>, String> {
+    let mut conditions = Vec::new();
+    let mut values: Vec<String> = Vec::new();
+    if !push_scope(&mut conditions, &mut values, session_paths, since, until) {
+        return Ok(Vec::new());
+    }
+
+    let sql = format!(
+        "SELECT session_path, nullif(path, session_path), entry_index, timestamp, kind, message,
+                reset_at, reset_hint,
+                (SELECT t.model FROM timings t
+                 WHERE t.path = rate_limits.path AND t.entry_index < rate_limits.entry_index
+                   AND t.model IS NOT NULL
+                 ORDER BY t.entry_index DESC LIMIT 1)
+         FROM rate_limits JOIN files USING (path) {}
+         ORDER BY timestamp DESC, rate_limits.rowid DESC",
+        where_clause(&conditions)
+    );
+    search_index::run_query(data_dir, "rate limit", sql, values, |row| {
+        Ok(RateLimitRow {
+            session_path: row.get(0)?,
+            subagent_path: row.get(1)?,
+            entry_index: row.get(2)?,
+            timestamp: row.get(3)?,
+            kind: row.get(4)?,
+            message: row.get(5)?,
+            reset_at: row.get(6)?,
+            reset_hint: row.get(7)?,
+            model: row.get(8)?,
+        })
+    })
+    .await
+}
@@ -0,0 +1,90 @@
+use crate::models::transcript::TranscriptEntry;
+use crate::services::search_index::{self, push_scope, where_clause};
+use crate::services::session_parser;
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+pub const TABLE: &str = "timings";
+
+pub const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS timings (
+         path TEXT NOT NULL,
+         entry_index INTEGER NOT NULL,
+         timestamp TEXT NOT NULL,
+         kind TEXT NOT NULL,
+         model TEXT
+     );
+     CREATE INDEX IF NOT EXISTS timings_path ON timings (path, entry_index);";
+
+/// Horario de um entry user/assistant, para metricas de latencia e tempo ativo
+#[derive(Debug, Clone)]
+pub struct TimingRow {
+    pub session_path: String,
+    pub entry_index: u32,
+    pub timestamp: String,
+    /// "prompt" (prompt real do usuario), "assistant" ou "other" (tool_result, sidechain, isMeta)
+    pub kind: String,
+}
+
+/// Registra o horario e o tipo do entry, com o modelo real das respostas
+pub fn record(
+    conn: &Connection,
+    path: &str,
+    entry_index: u32,
+    entry: &TranscriptEntry,
+) -> Result<(), String> {
+    let Some(timestamp) = entry.timestamp().filter(|t| !t.is_empty()) else {
+        return Ok(());
+    };
+    let sidechain = entry.meta().is_some_and(|m| m.is_sidechain);
+    let kind = match entry {
+        _ if session_parser::prompt_text(entry).is_some() => "prompt",
+        TranscriptEntry::Assistant(_) if !sidechain => "assistant",
+        _ => "other",
+    };
+    // Modelo real da resposta; "<synthetic>" (erros locais) fica de fora
+    let model = match entry {
+        TranscriptEntry::Assistant(assistant) => assistant
+            .message
+            .model
+            .as_deref()
+            .filter(|m| !m.is_empty() && !m.starts_with('<')),
+        _ => None,
+    };
+    conn.prepare_cached(
+        "INSERT INTO timings (path, entry_index, timestamp, kind, model) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )
+    .and_then(|mut stmt| stmt.execute(params![path, entry_index, timestamp, kind, model]))
+    .map_err(|e| format!("Failed to index timing: {}", e))?;
+    Ok(())
+}
+
+/// Horarios dos entries em ordem de sessao e de arquivo
+pub async fn query(
+    data_dir: &Path,
+    session_paths: Option<Vec<String>>,
+    since: Option<String>,
+    until: Option<String>,
+) -> Result<Vec<TimingRow>, String> {
+    let mut conditions = Vec::new();
+    let mut values: Vec<String> = Vec::new();
+    if !push_scope(&mut conditions, &mut values, session_paths, since, until) {
+        return Ok(Vec::new());
+    }
+    // Subagentes rodam dentro do turno da sessao; so o transcript da sessao mede o tempo
+    conditions.push("path = session_path".to_string());
+
+    let sql = format!(
+        "SELECT path, entry_index, timestamp, kind FROM timings JOIN files USING (path) {}
+         ORDER BY path, entry_index",
+        where_clause(&conditions)
+    );
+    search_index::run_query(data_dir, "timing", sql, values, |row| {
+        Ok(TimingRow {
+            session_path: row.get(0)?,
+            entry_index: row.get(1)?,
+            timestamp: row.get(2)?,
+            kind: row.get(3)?,
+        })
+    })
+    .await
+}
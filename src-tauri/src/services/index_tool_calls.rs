@@ -0,0 +1,162 @@
+use crate::models::transcript::{ContentBlock, MessageEntry};
+use crate::services::search_index::{self, push_scope, where_clause};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+
+pub const TABLE: &str = "tool_calls";
+
+pub const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS tool_calls (
+         path TEXT NOT NULL,
+         tool_use_id TEXT NOT NULL,
+         tool_name TEXT NOT NULL,
+         server INTEGER NOT NULL,
+         entry_index INTEGER NOT NULL,
+         timestamp TEXT NOT NULL,
+         result_timestamp TEXT,
+         result_chars INTEGER,
+         is_error INTEGER NOT NULL DEFAULT 0
+     );
+     CREATE INDEX IF NOT EXISTS tool_calls_path ON tool_calls (path, tool_use_id);
+     CREATE INDEX IF NOT EXISTS tool_calls_timestamp ON tool_calls (timestamp);";
+
+/// Estatisticas agregadas de uma ferramenta
+#[derive(Debug, Clone)]
+pub struct ToolStatsRow {
+    pub tool_name: String,
+    /// Ferramenta executada pela API (server_tool_use)
+    pub server: bool,
+    pub calls: u32,
+    pub errors: u32,
+    pub results: u32,
+    pub avg_result_chars: f64,
+    pub total_result_chars: u64,
+    pub avg_latency_ms: Option<f64>,
+    pub max_latency_ms: Option<f64>,
+}
+
+/// Registra todo tool_use/server_tool_use; o resultado completa a linha com tamanho, erro e horario.
+/// Resultados de server tools (ex: `web_search_tool_result`) vem na propria mensagem do assistente.
+pub fn record(
+    conn: &Connection,
+    path: &str,
+    entry_index: u32,
+    entry: &MessageEntry,
+) -> Result<(), String> {
+    let timestamp = entry.meta.timestamp.as_deref().unwrap_or("");
+
+    for block in entry.message.blocks() {
+        let (tool, server) = match block {
+            ContentBlock::ToolUse(tool) => (tool, false),
+            ContentBlock::ServerToolUse(tool) => (tool, true),
+            ContentBlock::ToolResult(result) => {
+                let chars = result.text().chars().count();
+                let is_error = result.is_error == Some(true);
+                record_result(conn, path, &result.tool_use_id, timestamp, chars, is_error)?;
+                continue;
+            }
+            ContentBlock::Unknown(value) => {
+                let is_server_result = value
+                    .get("type")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|t| t.ends_with("_tool_result"));
+                let Some(tool_use_id) = value.get("tool_use_id").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                if !is_server_result {
+                    continue;
+                }
+                let content = value.get("content");
+                let chars = content.map_or(0, |c| c.to_string().chars().count());
+                // Falha de server tool: content e um objeto `*_tool_result_error`
+                let is_error = content
+                    .and_then(|c| c.get("type"))
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|t| t.ends_with("_error"));
+                record_result(conn, path, tool_use_id, timestamp, chars, is_error)?;
+                continue;
+            }
+            _ => continue,
+        };
+        conn.prepare_cached(
+            "INSERT INTO tool_calls (path, tool_use_id, tool_name, server, entry_index, timestamp)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .and_then(|mut stmt| {
+            stmt.execute(params![path, tool.id, tool.name, server, entry_index, timestamp])
+        })
+        .map_err(|e| format!("Failed to index tool call: {}", e))?;
+    }
+
+    Ok(())
+}
+
+fn record_result(
+    conn: &Connection,
+    path: &str,
+    tool_use_id: &str,
+    timestamp: &str,
+    chars: usize,
+    is_error: bool,
+) -> Result<(), String> {
+    conn.prepare_cached(
+        "UPDATE tool_calls SET result_timestamp = ?3, result_chars = ?4, is_error = ?5
+         WHERE path = ?1 AND tool_use_id = ?2",
+    )
+    .and_then(|mut stmt| {
+        stmt.execute(params![path, tool_use_id, timestamp, chars as i64, is_error])
+    })
+    .map_err(|e| format!("Failed to index tool result: {}", e))?;
+    Ok(())
+}
+
+/// Nome da ferramenta de um tool_use ja registrado no arquivo
+pub fn tool_name(conn: &Connection, path: &str, tool_use_id: &str) -> Result<Option<String>, String> {
+    conn.prepare_cached("SELECT tool_name FROM tool_calls WHERE path = ?1 AND tool_use_id = ?2")
+        .and_then(|mut stmt| {
+            stmt.query_row(params![path, tool_use_id], |row| row.get(0))
+                .optional()
+        })
+        .map_err(|e| format!("Failed to read tool name: {}", e))
+}
+
+/// Estatisticas por ferramenta, mais usadas primeiro (datas sobre o horario do tool_use).
+/// Chamadas de subagentes contam para a sessao pai.
+pub async fn query(
+    data_dir: &Path,
+    session_paths: Option<Vec<String>>,
+    since: Option<String>,
+    until: Option<String>,
+) -> Result<Vec<ToolStatsRow>, String> {
+    let mut conditions = Vec::new();
+    let mut values: Vec<String> = Vec::new();
+    if !push_scope(&mut conditions, &mut values, session_paths, since, until) {
+        return Ok(Vec::new());
+    }
+
+    // Latencia so quando os dois horarios existem (julianday em dias -> ms)
+    let sql = format!(
+        "SELECT tool_name, max(server), count(*), sum(is_error), count(result_chars),
+                coalesce(avg(result_chars), 0), coalesce(sum(result_chars), 0), avg(latency), max(latency)
+         FROM (
+             SELECT *, CASE WHEN timestamp != '' AND result_timestamp != ''
+                       THEN round((julianday(result_timestamp) - julianday(timestamp)) * 86400000.0) END AS latency
+             FROM tool_calls JOIN files USING (path) {}
+         )
+         GROUP BY tool_name ORDER BY count(*) DESC",
+        where_clause(&conditions)
+    );
+    search_index::run_query(data_dir, "tool stats", sql, values, |row| {
+        Ok(ToolStatsRow {
+            tool_name: row.get(0)?,
+            server: row.get(1)?,
+            calls: row.get(2)?,
+            errors: row.get(3)?,
+            results: row.get(4)?,
+            avg_result_chars: row.get(5)?,
+            total_result_chars: row.get::<_, i64>(6)? as u64,
+            avg_latency_ms: row.get(7)?,
+            max_latency_ms: row.get(8)?,
+        })
+    })
+    .await
+}
@@ -1,8 +1,36 @@
 pub mod account_reader;
 pub mod anthropic_client;
+pub mod bash_history;
+pub mod context_timeline;
+pub mod conversation_tree;
 pub mod credentials;
+pub mod disk_usage;
+pub mod file_history;
+pub mod index_bash;
+pub mod index_file_touches;
+pub mod index_permissions;
+pub mod index_rate_limits;
+pub mod index_timings;
+pub mod index_tool_calls;
 pub mod mcp_checker;
+pub mod permissions;
+pub mod pricing;
+pub mod project_migration;
+pub mod project_resolver;
+pub mod rate_limits;
+pub mod search_index;
+pub mod search_query;
+pub mod session_archive;
+pub mod session_cache;
+pub mod session_cleanup;
+pub mod session_diff;
+pub mod session_export;
+pub mod session_fork;
 pub mod session_parser;
+pub mod session_reader;
+pub mod session_timing;
 pub mod stats_reader;
+pub mod subagents;
 pub mod terminal_launcher;
+pub mod tool_analytics;
 pub mod wsl;
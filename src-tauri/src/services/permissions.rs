@@ -0,0 +1,527 @@
+use crate::models::session::{
+    PermissionOptions, PermissionPreview, PermissionReport, PermissionRuleStats,
+    ProjectPermissions, SessionMeta,
+};
+use crate::services::index_permissions;
+use crate::services::session_parser;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Execucoes (sem nenhuma rejeicao) para sugerir uma regra `allow`
+const MIN_EXECUTIONS: u32 = 3;
+
+/// Rejeicoes (sem nenhuma execucao) para sugerir uma regra `deny`
+const MIN_REJECTIONS: u32 = 2;
+
+/// Ferramentas que o Claude Code executa sem pedir permissao
+const NO_PROMPT_TOOLS: &[&str] = &[
+    "Read",
+    "Glob",
+    "Grep",
+    "LS",
+    "NotebookRead",
+    "TodoRead",
+    "TodoWrite",
+    "Task",
+    "Agent",
+    "ExitPlanMode",
+    "BashOutput",
+    "KillBash",
+    "KillShell",
+];
+
+/// Subcomandos de leitura/build liberados por prefixo (`Bash(cargo test:*)`).
+/// Qualquer outro comando simples vira regra exata (`Bash(<comando>)`).
+const PREFIX_SUBCOMMANDS: &[(&str, &[&str])] = &[
+    ("npm", &["test", "run", "ls", "outdated", "view"]),
+    ("pnpm", &["test", "run", "ls", "outdated"]),
+    ("yarn", &["test", "run", "list", "outdated"]),
+    ("bun", &["test", "run"]),
+    (
+        "cargo",
+        &[
+            "build", "check", "test", "clippy", "fmt", "doc", "tree", "metadata",
+        ],
+    ),
+    (
+        "git",
+        &[
+            "status",
+            "diff",
+            "log",
+            "show",
+            "blame",
+            "ls-files",
+            "rev-parse",
+        ],
+    ),
+    ("go", &["build", "test", "vet", "fmt", "list"]),
+    ("docker", &["ps", "images", "logs", "inspect"]),
+    ("kubectl", &["get", "describe", "logs"]),
+    ("pip", &["list", "show", "freeze"]),
+    ("dotnet", &["build", "test"]),
+    ("mvn", &["compile", "test", "verify"]),
+    ("gradle", &["build", "test"]),
+    ("deno", &["test", "lint", "fmt", "check"]),
+    ("terraform", &["plan", "validate", "fmt"]),
+    ("helm", &["list", "lint", "template"]),
+    ("brew", &["list", "info"]),
+    ("swift", &["build", "test"]),
+    ("flutter", &["test", "analyze"]),
+    ("dart", &["test", "analyze"]),
+    ("mix", &["test", "compile"]),
+    ("stack", &["build", "test"]),
+];
+
+/// Programas que executam codigo arbitrario ou destroem dados: nunca sugeridos em `allow`,
+/// nem como comando exato (interpretadores, shells, elevacao, wrappers, rede, remocao)
+const NEVER_ALLOW_PROGRAMS: &[&str] = &[
+    "python",
+    "node",
+    "ruby",
+    "perl",
+    "php",
+    "lua",
+    "deno",
+    "bun",
+    "npx",
+    "bunx",
+    "pnpx",
+    "uvx",
+    "sh",
+    "bash",
+    "zsh",
+    "fish",
+    "dash",
+    "ksh",
+    "pwsh",
+    "powershell",
+    "cmd",
+    "eval",
+    "exec",
+    "source",
+    ".",
+    "sudo",
+    "su",
+    "doas",
+    "env",
+    "xargs",
+    "nohup",
+    "timeout",
+    "watch",
+    "nice",
+    "time",
+    "ssh",
+    "scp",
+    "rsync",
+    "curl",
+    "wget",
+    "rm",
+    "rmdir",
+    "dd",
+    "mkfs",
+    "shred",
+    "chmod",
+    "chown",
+    "kill",
+    "killall",
+    "pkill",
+    "shutdown",
+    "reboot",
+];
+
+/// Subcomandos destrutivos ou que publicam: nunca sugeridos em `allow`
+const DESTRUCTIVE_SUBCOMMANDS: &[&str] = &[
+    "git push",
+    "git reset",
+    "git clean",
+    "git checkout",
+    "git restore",
+    "git rebase",
+    "git branch",
+    "git tag",
+    "git stash",
+    "git rm",
+    "git filter-branch",
+    "kubectl delete",
+    "kubectl apply",
+    "kubectl exec",
+    "kubectl scale",
+    "docker rm",
+    "docker rmi",
+    "docker system",
+    "docker volume",
+    "docker run",
+    "docker exec",
+    "terraform apply",
+    "terraform destroy",
+    "helm install",
+    "helm upgrade",
+    "helm uninstall",
+    "npm publish",
+    "npm unpublish",
+    "cargo publish",
+    "gh release",
+    "gh repo",
+];
+
+/// Operadores que tornam um comando Bash composto (sem regra de prefixo segura)
+const COMPOUND_MARKERS: &[&str] = &["&&", "||", ";", "|", "`", "$(", ">", "<", "\n"];
+
+/// Regra de permissao (formato de `permissions.allow`) que libera a chamada, ou None
+/// quando a ferramenta nao pede permissao.
+/// A regra fica vazia quando nao ha prefixo seguro (ex: comando Bash composto).
+pub fn rule_for(tool_name: &str, input: &Value) -> Option<String> {
+    if NO_PROMPT_TOOLS.contains(&tool_name) {
+        return None;
+    }
+    let rule = match tool_name {
+        "Bash" => input
+            .get("command")
+            .and_then(|v| v.as_str())
+            .and_then(bash_rule),
+        "WebFetch" => input
+            .get("url")
+            .and_then(|v| v.as_str())
+            .and_then(url_host)
+            .map(|host| format!("WebFetch(domain:{})", host)),
+        _ => Some(tool_name.to_string()),
+    };
+    Some(rule.unwrap_or_default())
+}
+
+/// Regra de um comando simples: prefixo `programa subcomando:*` para subcomandos de
+/// `PREFIX_SUBCOMMANDS`, senao o comando exato
+fn bash_rule(command: &str) -> Option<String> {
+    let command = command.trim();
+    if COMPOUND_MARKERS.iter().any(|m| command.contains(m)) {
+        return None;
+    }
+    let mut words = command.split_whitespace();
+    let program = words.next()?;
+    // Atribuicao de variavel (FOO=1 cmd) muda o comando casado pelo Claude Code
+    if program.contains('=') {
+        return None;
+    }
+    let prefixed = words.next().filter(|sub| {
+        PREFIX_SUBCOMMANDS
+            .iter()
+            .any(|(p, subs)| *p == program && subs.contains(sub))
+    });
+    Some(match prefixed {
+        Some(sub) => format!("Bash({} {}:*)", program, sub),
+        None => format!(
+            "Bash({})",
+            command.split_whitespace().collect::<Vec<_>>().join(" ")
+        ),
+    })
+}
+
+/// Regra que pode ser sugerida em `allow`: prefixos Bash so de `PREFIX_SUBCOMMANDS`; comandos
+/// exatos de `NEVER_ALLOW_PROGRAMS` (inclusive versoes e paths, ex: `python3.12`, `/bin/rm`)
+/// e `DESTRUCTIVE_SUBCOMMANDS` ficam de fora
+fn allow_suggestable(rule: &str) -> bool {
+    let (tool, spec) = split_rule(rule);
+    let Some(spec) = spec.filter(|_| tool == "Bash") else {
+        return true;
+    };
+    // Prefixos so saem de `PREFIX_SUBCOMMANDS`, ja revisados
+    if spec.ends_with(":*") {
+        return PREFIX_SUBCOMMANDS.iter().any(|(program, subs)| {
+            subs.iter()
+                .any(|sub| spec == format!("{} {}:*", program, sub))
+        });
+    }
+    let mut words = spec.split_whitespace();
+    let Some(program) = words.next() else {
+        return false;
+    };
+    let program = program.rsplit(['/', '\\']).next().unwrap_or(program);
+    let base = program
+        .trim_end_matches(".exe")
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    if NEVER_ALLOW_PROGRAMS.contains(&base) || NEVER_ALLOW_PROGRAMS.contains(&program) {
+        return false;
+    }
+    let subcommand = words.next().map(|sub| format!("{} {}", base, sub));
+    !subcommand.is_some_and(|s| DESTRUCTIVE_SUBCOMMANDS.contains(&s.as_str()))
+}
+
+fn url_host(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host = rest.split(['/', '?', '#']).next()?;
+    let host = host.rsplit_once('@').map_or(host, |(_, h)| h);
+    let host = host.split(':').next()?;
+    (!host.is_empty()).then(|| host.to_lowercase())
+}
+
+/// Regra existente (`Bash`, `Bash(npm:*)`, `mcp__server`) que ja cobre `rule`
+fn covers(existing: &str, rule: &str) -> bool {
+    if existing == rule {
+        return true;
+    }
+    let (rule_tool, rule_spec) = split_rule(rule);
+    let (tool, spec) = split_rule(existing);
+    match spec {
+        None if tool == rule_tool => true,
+        // Servidor MCP inteiro: `mcp__server` ou `mcp__server__*`
+        None => {
+            let server = tool.trim_end_matches("__*");
+            server.starts_with("mcp__") && rule_tool.starts_with(&format!("{}__", server))
+        }
+        Some(spec) if tool == "Bash" && rule_tool == "Bash" => {
+            let (Some(prefix), Some(rule_spec)) = (spec.strip_suffix(":*"), rule_spec) else {
+                return false;
+            };
+            // Prefixo cobre outro prefixo mais longo ou um comando exato
+            let rule_command = rule_spec.strip_suffix(":*").unwrap_or(rule_spec);
+            rule_command == prefix || rule_command.starts_with(&format!("{} ", prefix))
+        }
+        Some(_) => false,
+    }
+}
+
+fn split_rule(rule: &str) -> (&str, Option<&str>) {
+    match rule.split_once('(') {
+        Some((tool, spec)) => (tool, Some(spec.strip_suffix(')').unwrap_or(spec))),
+        None => (rule, None),
+    }
+}
+
+/// Rejeicoes, execucoes e interrupcoes por projeto, com regras allow/deny sugeridas.
+/// `project_dir` converte o path do projeto para leitura local (ex: UNC do WSL).
+pub async fn permission_analysis(
+    claude_dir: &str,
+    options: &PermissionOptions,
+    cache_dir: &Path,
+    data_dir: &Path,
+    project_dir: impl Fn(&str) -> PathBuf,
+) -> Result<PermissionReport, String> {
+    let sessions = session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+
+    let session_paths =
+        session_parser::session_paths_for_project(&sessions, options.project.as_deref());
+    let rows = index_permissions::query(
+        data_dir,
+        session_paths,
+        options.since.clone(),
+        options.until.clone(),
+    )
+    .await?;
+
+    let by_path: HashMap<&str, &SessionMeta> =
+        sessions.iter().map(|s| (s.file_path.as_str(), s)).collect();
+
+    let mut projects: HashMap<String, ProjectPermissions> = HashMap::new();
+    let mut rules: HashMap<(String, String, String), PermissionRuleStats> = HashMap::new();
+    for row in rows {
+        let Some(session) = by_path.get(row.session_path.as_str()) else {
+            continue;
+        };
+        let project = projects
+            .entry(session.project_path.clone())
+            .or_insert_with(|| ProjectPermissions {
+                project_path: session.project_path.clone(),
+                project_display: session.project_display.clone(),
+                ..Default::default()
+            });
+        match row.outcome.as_str() {
+            "executed" => project.executed += 1,
+            "rejected" => project.rejected += 1,
+            "interrupted" => project.interruptions += 1,
+            _ => {}
+        }
+        let Some(tool_name) = row.tool_name else {
+            continue;
+        };
+        let stats = rules
+            .entry((
+                session.project_path.clone(),
+                tool_name.clone(),
+                row.rule.clone(),
+            ))
+            .or_insert_with(|| PermissionRuleStats {
+                rule: row.rule.clone(),
+                tool_name,
+                example: row.example.clone(),
+                ..Default::default()
+            });
+        match row.outcome.as_str() {
+            "executed" => stats.executed += 1,
+            "rejected" => stats.rejected += 1,
+            "interrupted" => stats.interrupted += 1,
+            _ => {}
+        }
+        if row.timestamp > stats.last_seen {
+            stats.last_seen = row.timestamp;
+        }
+    }
+
+    let user_settings = read_rules(&Path::new(claude_dir).join("settings.json")).await;
+    for ((project_path, ..), stats) in rules {
+        if let Some(project) = projects.get_mut(&project_path) {
+            project.rules.push(stats);
+        }
+    }
+
+    let mut report = PermissionReport::default();
+    for mut project in projects.into_values() {
+        let dir = project_dir(&project.project_path).join(".claude");
+        let mut allow = user_settings.0.clone();
+        let mut deny = user_settings.1.clone();
+        for file in ["settings.json", "settings.local.json"] {
+            let (a, d) = read_rules(&dir.join(file)).await;
+            allow.extend(a);
+            deny.extend(d);
+        }
+
+        for stats in &mut project.rules {
+            if stats.rule.is_empty() {
+                continue;
+            }
+            stats.existing = if deny.iter().any(|r| covers(r, &stats.rule)) {
+                Some("deny".to_string())
+            } else if allow.iter().any(|r| covers(r, &stats.rule)) {
+                Some("allow".to_string())
+            } else {
+                None
+            };
+            if stats.existing.is_some() {
+                continue;
+            }
+            if stats.executed >= MIN_EXECUTIONS
+                && stats.rejected == 0
+                && allow_suggestable(&stats.rule)
+            {
+                project.suggested_allow.push(stats.rule.clone());
+            } else if stats.rejected >= MIN_REJECTIONS && stats.executed == 0 {
+                project.suggested_deny.push(stats.rule.clone());
+            }
+        }
+
+        project.rules.sort_by(|a, b| {
+            (b.rejected + b.interrupted, b.executed).cmp(&(a.rejected + a.interrupted, a.executed))
+        });
+        project.suggested_allow.sort();
+        project.suggested_deny.sort();
+        report.projects.push(project);
+    }
+    report
+        .projects
+        .sort_by_key(|p| std::cmp::Reverse(p.rejected + p.interruptions));
+    Ok(report)
+}
+
+/// Regras `permissions.allow` e `permissions.deny` de um settings.json (vazias se ausente)
+async fn read_rules(path: &Path) -> (Vec<String>, Vec<String>) {
+    let Ok(content) = tokio::fs::read_to_string(path).await else {
+        return (Vec::new(), Vec::new());
+    };
+    let Ok(root) = serde_json::from_str::<Value>(&content) else {
+        return (Vec::new(), Vec::new());
+    };
+    let list = |key: &str| -> Vec<String> {
+        root.pointer(&format!("/permissions/{}", key))
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    (list("allow"), list("deny"))
+}
+
+/// Conteudo de `.claude/settings.json` do projeto com as regras adicionadas, sem gravar
+pub async fn preview_rules(
+    project_dir: &Path,
+    allow: &[String],
+    deny: &[String],
+) -> Result<PermissionPreview, String> {
+    let settings_path = project_dir.join(".claude").join("settings.json");
+    let current = match tokio::fs::read_to_string(&settings_path).await {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(format!("Failed to read settings.json: {}", e)),
+    };
+
+    let mut root: Value = match current.as_deref().filter(|c| !c.trim().is_empty()) {
+        Some(content) => serde_json::from_str(content)
+            .map_err(|e| format!("Failed to parse settings.json: {}", e))?,
+        None => serde_json::json!({}),
+    };
+    let Some(object) = root.as_object_mut() else {
+        return Err("settings.json is not a JSON object".to_string());
+    };
+    let permissions = object
+        .entry("permissions")
+        .or_insert_with(|| serde_json::json!({}));
+    let Some(permissions) = permissions.as_object_mut() else {
+        return Err("settings.json permissions is not a JSON object".to_string());
+    };
+
+    let mut added = 0;
+    for (key, rules) in [("allow", allow), ("deny", deny)] {
+        if rules.is_empty() {
+            continue;
+        }
+        let list = permissions
+            .entry(key)
+            .or_insert_with(|| Value::Array(Vec::new()));
+        let Some(list) = list.as_array_mut() else {
+            return Err(format!("settings.json permissions.{} is not an array", key));
+        };
+        for rule in rules {
+            if !list.iter().any(|v| v.as_str() == Some(rule)) {
+                list.push(Value::String(rule.clone()));
+                added += 1;
+            }
+        }
+    }
+
+    let proposed = serde_json::to_string_pretty(&root)
+        .map_err(|e| format!("Failed to serialize settings.json: {}", e))?
+        + "\n";
+    let diff = similar::TextDiff::from_lines(current.as_deref().unwrap_or(""), &proposed)
+        .unified_diff()
+        .context_radius(3)
+        .header(".claude/settings.json", ".claude/settings.json")
+        .to_string();
+
+    Ok(PermissionPreview {
+        settings_path: settings_path.to_string_lossy().to_string(),
+        current,
+        proposed,
+        diff,
+        added,
+    })
+}
+
+/// Grava as regras em `.claude/settings.json` do projeto (mesmo conteudo do preview)
+pub async fn apply_rules(
+    project_dir: &Path,
+    allow: &[String],
+    deny: &[String],
+) -> Result<PermissionPreview, String> {
+    let preview = preview_rules(project_dir, allow, deny).await?;
+    if preview.added == 0 {
+        return Ok(preview);
+    }
+    let settings_path = Path::new(&preview.settings_path);
+    if let Some(parent) = settings_path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create .claude dir: {}", e))?;
+    }
+    let tmp_path = settings_path.with_extension("json.tmp");
+    tokio::fs::write(&tmp_path, &preview.proposed)
+        .await
+        .map_err(|e| format!("Failed to write settings.json: {}", e))?;
+    tokio::fs::rename(&tmp_path, settings_path)
+        .await
+        .map_err(|e| format!("Failed to replace settings.json: {}", e))?;
+    Ok(preview)
+}
@@ -0,0 +1,116 @@
+use crate::models::session::TokenTotals;
+use crate::models::transcript::{Message, Usage};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Precos em USD por milhao de tokens:
+/// (modelo, input, output, cache write 5m, cache write 1h, cache read).
+/// Ids listados explicitamente; modelo fora da lista fica sem preco em vez de herdar o de outro.
+const PRICES: &[(&str, f64, f64, f64, f64, f64)] = &[
+    ("claude-opus-4-6", 5.0, 25.0, 6.25, 10.0, 0.5),
+    ("claude-opus-4-5", 5.0, 25.0, 6.25, 10.0, 0.5),
+    ("claude-opus-4-1", 15.0, 75.0, 18.75, 30.0, 1.5),
+    ("claude-opus-4", 15.0, 75.0, 18.75, 30.0, 1.5),
+    ("claude-3-opus", 15.0, 75.0, 18.75, 30.0, 1.5),
+    ("claude-sonnet-4-6", 3.0, 15.0, 3.75, 6.0, 0.3),
+    ("claude-sonnet-4-5", 3.0, 15.0, 3.75, 6.0, 0.3),
+    ("claude-sonnet-4", 3.0, 15.0, 3.75, 6.0, 0.3),
+    ("claude-3-7-sonnet", 3.0, 15.0, 3.75, 6.0, 0.3),
+    ("claude-3-5-sonnet", 3.0, 15.0, 3.75, 6.0, 0.3),
+    ("claude-haiku-4-5", 1.0, 5.0, 1.25, 2.0, 0.1),
+    ("claude-3-5-haiku", 0.8, 4.0, 1.0, 1.6, 0.08),
+    ("claude-3-haiku", 0.25, 1.25, 0.3, 0.5, 0.03),
+];
+
+/// Janela de contexto padrao dos modelos Claude
+const DEFAULT_CONTEXT_WINDOW: u64 = 200_000;
+
+/// Janela estendida (beta de 1M tokens, sufixo `[1m]` no id do modelo)
+const EXTENDED_CONTEXT_WINDOW: u64 = 1_000_000;
+
+/// Janela de contexto do modelo. O id gravado no transcript nao indica o beta de 1M,
+/// entao um contexto observado acima da janela padrao tambem conta como estendido.
+pub fn context_window(model: &str, observed_tokens: u64) -> u64 {
+    if model.ends_with("[1m]") || observed_tokens > DEFAULT_CONTEXT_WINDOW {
+        EXTENDED_CONTEXT_WINDOW
+    } else {
+        DEFAULT_CONTEXT_WINDOW
+    }
+}
+
+/// Custo em USD de um uso de tokens; None quando o modelo nao tem preco conhecido
+pub fn cost_usd(model: &str, usage: &Usage) -> Option<f64> {
+    let id = base_model(model);
+    let (_, input, output, write_5m, write_1h, cache_read) =
+        PRICES.iter().find(|(name, ..)| *name == id)?;
+
+    // `cache_creation` separa as escritas com TTL de 1h (preco maior) das de 5m
+    let written_1h = usage
+        .extra
+        .get("cache_creation")
+        .and_then(|c| c.get("ephemeral_1h_input_tokens"))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0)
+        .min(usage.cache_creation_input_tokens);
+    let written_5m = usage.cache_creation_input_tokens - written_1h;
+
+    Some(
+        (usage.input_tokens as f64 * input
+            + usage.output_tokens as f64 * output
+            + written_5m as f64 * write_5m
+            + written_1h as f64 * write_1h
+            + usage.cache_read_input_tokens as f64 * cache_read)
+            / 1_000_000.0,
+    )
+}
+
+/// Id do modelo sem o sufixo `[1m]` e sem a data (`claude-opus-4-1-20250805` -> `claude-opus-4-1`)
+fn base_model(model: &str) -> &str {
+    let model = model.strip_suffix("[1m]").unwrap_or(model);
+    match model.rsplit_once('-') {
+        Some((base, date)) if date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit()) => base,
+        _ => model,
+    }
+}
+
+/// Soma tokens e custo das mensagens do assistente.
+/// O Claude Code grava um entry por bloco de conteudo repetindo o mesmo `usage`,
+/// entao cada `message.id` so e contado uma vez (inclusive historico copiado ao retomar a sessao).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageAccumulator {
+    seen: HashSet<String>,
+    pub totals: TokenTotals,
+}
+
+impl UsageAccumulator {
+    pub fn add(&mut self, message: &Message) {
+        let Some(usage) = &message.usage else {
+            return;
+        };
+        if let Some(id) = &message.id {
+            if !self.seen.insert(id.clone()) {
+                return;
+            }
+        }
+        add_usage(&mut self.totals, message.model.as_deref().unwrap_or(""), usage);
+    }
+}
+
+/// Soma um `usage` (sem deduplicacao) aos totais
+pub fn add_usage(totals: &mut TokenTotals, model: &str, usage: &Usage) {
+    totals.input_tokens += usage.input_tokens;
+    totals.output_tokens += usage.output_tokens;
+    totals.cache_creation_input_tokens += usage.cache_creation_input_tokens;
+    totals.cache_read_input_tokens += usage.cache_read_input_tokens;
+
+    match cost_usd(model, usage) {
+        Some(cost) => totals.cost_usd += cost,
+        // Modelo sem preco conhecido (ex: "<synthetic>"): tokens contam, custo nao
+        None => {
+            totals.unpriced_tokens += usage.input_tokens
+                + usage.output_tokens
+                + usage.cache_creation_input_tokens
+                + usage.cache_read_input_tokens
+        }
+    }
+}
@@ -0,0 +1,477 @@
+use crate::models::session::{ProjectFolderMove, ProjectMigrationOptions, ProjectMigrationReport};
+use crate::services::project_resolver::{self, path_to_slug};
+use crate::services::{session_archive, session_parser, stats_reader};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Pasta de projects/ com o path que ela passa a ter
+struct Planned {
+    folder_move: ProjectFolderMove,
+    source: PathBuf,
+    target: PathBuf,
+}
+
+/// Resultado de `migrate_folders`
+struct Migrated {
+    moves: Vec<ProjectFolderMove>,
+    backup_dir: Option<String>,
+    errors: Vec<String>,
+}
+
+/// Migra as sessoes de um projeto que mudou de pasta: renomeia `projects/<slug>` para o slug
+/// novo, reescreve o `cwd` dos entries e a chave em `projects` do .claude.json.
+/// Subpastas do projeto (sessoes iniciadas num subdiretorio) vao junto.
+/// Antes de alterar qualquer coisa copia as pastas e o .claude.json para `<data_dir>/backups`.
+pub async fn migrate_project(
+    claude_dir: &str,
+    options: &ProjectMigrationOptions,
+    cache_dir: &Path,
+    data_dir: &Path,
+    project_dir: impl Fn(&str) -> PathBuf,
+) -> Result<ProjectMigrationReport, String> {
+    let old_path = trim_separator(&options.old_path).to_string();
+    let new_path = trim_separator(&options.new_path).to_string();
+    if old_path.is_empty() || new_path.is_empty() {
+        return Err("Enter the old and the new project path".to_string());
+    }
+    if old_path == new_path {
+        return Err("Old and new project paths are the same".to_string());
+    }
+
+    let sessions = session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+    let projects_dir = Path::new(claude_dir).join("projects");
+
+    // Pastas afetadas: a do path antigo e as de sessoes cujo projeto fica dentro dele
+    let mut folders: BTreeMap<String, (String, u32)> = BTreeMap::new();
+    let old_slug = path_to_slug(&old_path);
+    if projects_dir.join(&old_slug).is_dir() {
+        folders.insert(old_slug, (old_path.clone(), 0));
+    }
+    for session in &sessions {
+        if moved(&session.project_path, &old_path, &new_path).is_none() {
+            continue;
+        }
+        let Some(slug) = Path::new(&session.file_path)
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        let entry = folders
+            .entry(slug)
+            .or_insert_with(|| (session.project_path.clone(), 0));
+        entry.1 += 1;
+    }
+
+    let mut planned = Vec::new();
+    let mut new_slugs = HashSet::new();
+    for (slug, (path, sessions)) in folders {
+        let project_new_path =
+            moved(&path, &old_path, &new_path).unwrap_or_else(|| new_path.clone());
+        let new_slug = path_to_slug(&project_new_path);
+        if !new_slugs.insert(new_slug.clone()) {
+            return Err(format!(
+                "More than one project folder would move to {}",
+                new_slug
+            ));
+        }
+        let source = projects_dir.join(&slug);
+        let target = projects_dir.join(&new_slug);
+        planned.push(Planned {
+            folder_move: ProjectFolderMove {
+                old_path: path,
+                new_path: project_new_path,
+                old_slug: slug,
+                merge: target != source && target.is_dir(),
+                new_slug,
+                sessions,
+                files: 0,
+                rewritten_entries: 0,
+            },
+            source,
+            target,
+        });
+    }
+
+    let config_path = project_resolver::config_candidates(Path::new(claude_dir))
+        .into_iter()
+        .find(|p| p.is_file());
+    let config_keys = match &config_path {
+        Some(path) => read_config(path)
+            .await?
+            .get("projects")
+            .and_then(|v| v.as_object())
+            .map(|projects| {
+                projects
+                    .keys()
+                    .filter(|k| moved(k, &old_path, &new_path).is_some())
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+    if planned.is_empty() && config_keys.is_empty() {
+        return Err(format!("No sessions found for {}", old_path));
+    }
+
+    let dry_run = options.dry_run;
+    let migrate_old = old_path.clone();
+    let migrate_new = new_path.clone();
+    let backup_root = data_dir.join("backups");
+    let backup_config = config_path.clone();
+    let mut migrated = tokio::task::spawn_blocking(move || {
+        migrate_folders(
+            planned,
+            &migrate_old,
+            &migrate_new,
+            dry_run,
+            &backup_root,
+            backup_config.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Migration task failed: {}", e))??;
+
+    if !dry_run {
+        if let Some(path) = &config_path {
+            if let Err(e) = update_config(path, &old_path, &new_path).await {
+                migrated.errors.push(format!("{}: {}", path.display(), e));
+            }
+        }
+        // Paths das sessoes mudaram: atualiza cache e indice
+        session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+    }
+
+    Ok(ProjectMigrationReport {
+        dry_run,
+        moves: migrated.moves,
+        config_path: config_path.map(|p| p.to_string_lossy().to_string()),
+        config_keys,
+        old_path_exists: project_dir(&old_path).is_dir(),
+        new_path_exists: project_dir(&new_path).is_dir(),
+        backup_dir: migrated.backup_dir,
+        errors: migrated.errors,
+    })
+}
+
+/// Conta (dry run) ou aplica a migracao de cada pasta. Conflitos de nomes ao juntar com uma
+/// pasta existente abortam tudo antes do backup.
+fn migrate_folders(
+    mut planned: Vec<Planned>,
+    old_path: &str,
+    new_path: &str,
+    dry_run: bool,
+    backup_root: &Path,
+    config_path: Option<&Path>,
+) -> Result<Migrated, String> {
+    for p in &mut planned {
+        let files = stats_reader::collect_jsonl_files(&p.source);
+        p.folder_move.files = files.len() as u32;
+        for file in &files {
+            let content = read_transcript(file)?;
+            p.folder_move.rewritten_entries += rewrite_cwd(&content, old_path, new_path).1;
+        }
+        if p.folder_move.merge {
+            let conflicts = conflicts(&p.source, &p.target);
+            if !conflicts.is_empty() {
+                return Err(format!(
+                    "{} already has {}",
+                    p.target.display(),
+                    conflicts.join(", ")
+                ));
+            }
+        }
+    }
+    if dry_run {
+        return Ok(Migrated {
+            moves: planned.into_iter().map(|p| p.folder_move).collect(),
+            backup_dir: None,
+            errors: Vec::new(),
+        });
+    }
+
+    let backup_dir = backup(&planned, backup_root, config_path)
+        .map_err(|e| format!("Failed to back up project: {}", e))?;
+
+    let mut errors = Vec::new();
+    let mut moves = Vec::with_capacity(planned.len());
+    for p in planned {
+        if let Err(e) = migrate_folder(&p, old_path, new_path) {
+            errors.push(format!("{}: {}", p.source.display(), e));
+        }
+        moves.push(p.folder_move);
+    }
+    Ok(Migrated {
+        moves,
+        backup_dir: Some(backup_dir.to_string_lossy().to_string()),
+        errors,
+    })
+}
+
+/// Reescreve o `cwd` dos transcripts e move a pasta para o slug novo
+fn migrate_folder(p: &Planned, old_path: &str, new_path: &str) -> Result<(), String> {
+    for file in stats_reader::collect_jsonl_files(&p.source) {
+        let content = read_transcript(&file)?;
+        let (rewritten, count) = rewrite_cwd(&content, old_path, new_path);
+        if count > 0 {
+            session_archive::write(&file, rewritten.as_bytes())
+                .map_err(|e| format!("Failed to rewrite {}: {}", file.display(), e))?;
+        }
+    }
+
+    if p.source == p.target {
+        return Ok(());
+    }
+    if !p.folder_move.merge {
+        return std::fs::rename(&p.source, &p.target)
+            .map_err(|e| format!("Failed to rename project folder: {}", e));
+    }
+    let entries = std::fs::read_dir(&p.source)
+        .map_err(|e| format!("Failed to read project folder: {}", e))?;
+    for entry in entries.flatten() {
+        std::fs::rename(entry.path(), p.target.join(entry.file_name()))
+            .map_err(|e| format!("Failed to move {}: {}", entry.path().display(), e))?;
+    }
+    std::fs::remove_dir(&p.source).map_err(|e| format!("Failed to remove old folder: {}", e))
+}
+
+/// Troca o `cwd` de cada entry que aponta para o projeto antigo. Linhas sem mudanca ficam intactas.
+fn rewrite_cwd(content: &str, old_path: &str, new_path: &str) -> (String, u32) {
+    let mut count = 0;
+    let mut output = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let body = line.trim_end_matches(['\r', '\n']);
+        // Check barato antes de parsear a linha inteira
+        let rewritten = if body.contains("\"cwd\"") {
+            serde_json::from_str::<Value>(body)
+                .ok()
+                .and_then(|mut entry| {
+                    let cwd = moved(entry.get("cwd")?.as_str()?, old_path, new_path)?;
+                    entry["cwd"] = Value::String(cwd);
+                    serde_json::to_string(&entry).ok()
+                })
+        } else {
+            None
+        };
+        match rewritten {
+            Some(rewritten) => {
+                count += 1;
+                output.push_str(&rewritten);
+                output.push_str(&line[body.len()..]);
+            }
+            None => output.push_str(line),
+        }
+    }
+    (output, count)
+}
+
+/// Path equivalente depois da mudanca, se `path` for o projeto antigo ou uma subpasta dele
+fn moved(path: &str, old_path: &str, new_path: &str) -> Option<String> {
+    let rest = path.strip_prefix(old_path)?;
+    (rest.is_empty() || rest.starts_with(['/', '\\'])).then(|| format!("{}{}", new_path, rest))
+}
+
+fn trim_separator(path: &str) -> &str {
+    let trimmed = path.trim().trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() {
+        path.trim()
+    } else {
+        trimmed
+    }
+}
+
+fn read_transcript(path: &Path) -> Result<String, String> {
+    session_archive::read(path)
+        .and_then(|bytes| {
+            String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// Nomes da pasta antiga que ja existem na pasta de destino
+fn conflicts(source: &Path, target: &Path) -> Vec<String> {
+    std::fs::read_dir(source)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| target.join(e.file_name()).exists())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Copia as pastas envolvidas (origem e destino) e o .claude.json para
+/// `<backup_root>/project-migration-<timestamp>/`
+fn backup(
+    planned: &[Planned],
+    backup_root: &Path,
+    config_path: Option<&Path>,
+) -> io::Result<PathBuf> {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let backup_dir = backup_root.join(format!("project-migration-{}", stamp));
+    let projects = backup_dir.join("projects");
+    std::fs::create_dir_all(&projects)?;
+    for p in planned {
+        copy_dir(&p.source, &projects.join(&p.folder_move.old_slug))?;
+        if p.folder_move.merge {
+            copy_dir(&p.target, &projects.join(&p.folder_move.new_slug))?;
+        }
+    }
+    if let Some(config) = config_path {
+        std::fs::copy(config, backup_dir.join(".claude.json"))?;
+    }
+    Ok(backup_dir)
+}
+
+fn copy_dir(source: &Path, target: &Path) -> io::Result<()> {
+    std::fs::create_dir_all(target)?;
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            copy_dir(&path, &target.join(entry.file_name()))?;
+        } else {
+            std::fs::copy(&path, target.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+async fn read_config(path: &Path) -> Result<Value, String> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Failed to read .claude.json: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse .claude.json: {}", e))
+}
+
+/// Renomeia as chaves do projeto em `projects`. Se o path novo ja tem entrada
+/// (o Claude ja rodou la), ela prevalece e a antiga so completa os campos que faltam.
+async fn update_config(path: &Path, old_path: &str, new_path: &str) -> Result<(), String> {
+    let mut root = read_config(path).await?;
+    let Some(projects) = root.get_mut("projects").and_then(|v| v.as_object_mut()) else {
+        return Ok(());
+    };
+
+    let mut kept = serde_json::Map::new();
+    let mut renamed = Vec::new();
+    for (key, value) in std::mem::take(projects) {
+        match moved(&key, old_path, new_path) {
+            Some(new_key) => renamed.push((new_key, value)),
+            None => {
+                kept.insert(key, value);
+            }
+        }
+    }
+    if renamed.is_empty() {
+        return Ok(());
+    }
+    for (key, value) in renamed {
+        match (kept.get_mut(&key), value) {
+            (Some(Value::Object(existing)), Value::Object(old)) => {
+                for (field, v) in old {
+                    existing.entry(field).or_insert(v);
+                }
+            }
+            (Some(_), _) => {}
+            (None, value) => {
+                kept.insert(key, value);
+            }
+        }
+    }
+    *projects = kept;
+
+    let content = serde_json::to_string_pretty(&root)
+        .map_err(|e| format!("Failed to serialize .claude.json: {}", e))?;
+    let tmp = path.with_extension("json.tmp");
+    if let Err(e) = tokio::fs::write(&tmp, content).await {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(format!("Failed to write .claude.json: {}", e));
+    }
+    tokio::fs::rename(&tmp, path)
+        .await
+        .map_err(|e| format!("Failed to write .claude.json: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moved_respects_path_boundaries() {
+        assert_eq!(
+            moved("/a/foo", "/a/foo", "/b/bar").as_deref(),
+            Some("/b/bar")
+        );
+        assert_eq!(
+            moved("/a/foo/src", "/a/foo", "/b/bar").as_deref(),
+            Some("/b/bar/src")
+        );
+        assert_eq!(moved("/a/foobar", "/a/foo", "/b/bar"), None);
+        assert_eq!(moved("/a", "/a/foo", "/b/bar"), None);
+    }
+
+    #[test]
+    fn moved_handles_windows_separators() {
+        assert_eq!(
+            moved(r"C:\a\foo\src", r"C:\a\foo", r"D:\b").as_deref(),
+            Some(r"D:\b\src")
+        );
+        assert_eq!(moved(r"C:\a\foobar", r"C:\a\foo", r"D:\b"), None);
+    }
+
+    #[test]
+    fn rewrite_cwd_skips_other_projects_and_lines_without_cwd() {
+        let content = concat!(
+            r#"{"type":"summary","summary":"x"}"#,
+            "\n",
+            r#"{"type":"user","cwd":"/a/foobar"}"#,
+            "\n",
+            r#"{"type":"user","cwd":"/a/foo/src"}"#,
+            "\n",
+        );
+        let (rewritten, count) = rewrite_cwd(content, "/a/foo", "/b/bar");
+        assert_eq!(count, 1);
+        assert_eq!(
+            rewritten,
+            concat!(
+                r#"{"type":"summary","summary":"x"}"#,
+                "\n",
+                r#"{"type":"user","cwd":"/a/foobar"}"#,
+                "\n",
+                r#"{"type":"user","cwd":"/b/bar/src"}"#,
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    fn rewrite_cwd_keeps_crlf_and_missing_final_newline() {
+        let content = "{\"cwd\":\"/a/foo\"}\r\n{\"cwd\":\"/a/foo\"}";
+        let (rewritten, count) = rewrite_cwd(content, "/a/foo", "/b");
+        assert_eq!(count, 2);
+        assert_eq!(rewritten, "{\"cwd\":\"/b\"}\r\n{\"cwd\":\"/b\"}");
+    }
+
+    #[test]
+    fn rewrite_cwd_handles_escaped_windows_paths() {
+        let content = r#"{"cwd":"C:\\a\\foo\\src","type":"user"}"#;
+        let (rewritten, count) = rewrite_cwd(content, r"C:\a\foo", r"D:\b");
+        assert_eq!(count, 1);
+        assert_eq!(rewritten, r#"{"cwd":"D:\\b\\src","type":"user"}"#);
+    }
+
+    #[test]
+    fn rewrite_cwd_preserves_key_order() {
+        let content = r#"{"parentUuid":null,"isSidechain":false,"cwd":"/a/foo","sessionId":"s","type":"user","message":{"role":"user","content":"hi"},"uuid":"u1"}"#;
+        let (rewritten, _) = rewrite_cwd(content, "/a/foo", "/b");
+        assert_eq!(
+            rewritten,
+            r#"{"parentUuid":null,"isSidechain":false,"cwd":"/b","sessionId":"s","type":"user","message":{"role":"user","content":"hi"},"uuid":"u1"}"#
+        );
+    }
+}
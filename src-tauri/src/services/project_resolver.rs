@@ -0,0 +1,106 @@
+use crate::models::transcript::TranscriptEntry;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Converte path real para slug de diretorio (replica logica do Claude CLI:
+/// todo caractere nao alfanumerico vira '-')
+pub fn path_to_slug(path: &str) -> String {
+    path.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Versao curta do path para exibicao (ultimos dois componentes)
+pub fn display_name(path: &str) -> String {
+    let parts: Vec<&str> = path
+        .split(['/', '\\'])
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    if parts.len() <= 1 {
+        return path.to_string();
+    }
+
+    parts[parts.len() - 2..].join("/")
+}
+
+/// Extrai o campo `cwd` da primeira linha do JSONL que o possua
+pub fn first_cwd<'a>(lines: impl Iterator<Item = &'a str>) -> Option<String> {
+    for line in lines {
+        // Check barato antes de parsear a linha inteira
+        if !line.contains("\"cwd\"") {
+            continue;
+        }
+        let Ok(entry) = serde_json::from_str::<TranscriptEntry>(line) else {
+            continue;
+        };
+        if let Some(cwd) = entry.cwd() {
+            return Some(cwd.to_string());
+        }
+    }
+    None
+}
+
+/// Locais possiveis do .claude.json, em ordem de preferencia
+/// (dentro do diretorio .claude quando CLAUDE_CONFIG_DIR e usado, senao no home)
+pub fn config_candidates(claude_dir: &Path) -> Vec<PathBuf> {
+    let mut candidates = vec![claude_dir.join(".claude.json")];
+    if let Some(parent) = claude_dir.parent() {
+        candidates.push(parent.join(".claude.json"));
+    }
+    candidates
+}
+
+/// Le os paths de projetos conhecidos em .claude.json
+pub async fn read_known_projects(claude_dir: &Path) -> Vec<String> {
+    for path in config_candidates(claude_dir) {
+        let Ok(content) = tokio::fs::read_to_string(&path).await else {
+            continue;
+        };
+        let Ok(root) = serde_json::from_str::<Value>(&content) else {
+            continue;
+        };
+        return root
+            .get("projects")
+            .and_then(|v| v.as_object())
+            .map(|p| p.keys().cloned().collect())
+            .unwrap_or_default();
+    }
+
+    Vec::new()
+}
+
+/// Resolve o path canonico de cada pasta em projects/
+pub struct ProjectResolver {
+    known_by_slug: HashMap<String, String>,
+}
+
+impl ProjectResolver {
+    pub fn new(known_projects: &[String]) -> Self {
+        let known_by_slug = known_projects
+            .iter()
+            .map(|p| (path_to_slug(p), p.clone()))
+            .collect();
+        Self { known_by_slug }
+    }
+
+    pub async fn load(claude_dir: &Path) -> Self {
+        Self::new(&read_known_projects(claude_dir).await)
+    }
+
+    /// Ordem de preferencia: cwd cujo slug bate com a pasta, projeto conhecido
+    /// do .claude.json, primeiro cwd encontrado e, por fim, o proprio slug
+    pub fn resolve(&self, slug: &str, cwds: &[String]) -> String {
+        if let Some(cwd) = cwds.iter().find(|c| path_to_slug(c) == slug) {
+            return cwd.clone();
+        }
+        if let Some(known) = self.known_by_slug.get(slug) {
+            return known.clone();
+        }
+        if let Some(cwd) = cwds.first() {
+            return cwd.clone();
+        }
+        slug.to_string()
+    }
+}
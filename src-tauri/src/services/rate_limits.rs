@@ -0,0 +1,74 @@
+use crate::models::session::{
+    RateLimitDay, RateLimitHit, RateLimitOptions, RateLimitReport, SessionMeta,
+};
+use crate::services::index_rate_limits;
+use crate::services::session_parser;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Limites de uso e rate limits registrados nos transcripts, com contagem por dia
+pub async fn list_rate_limits(
+    claude_dir: &str,
+    options: &RateLimitOptions,
+    cache_dir: &Path,
+    data_dir: &Path,
+) -> Result<RateLimitReport, String> {
+    let sessions = session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+
+    let session_paths =
+        session_parser::session_paths_for_project(&sessions, options.project.as_deref());
+    let rows = index_rate_limits::query(
+        data_dir,
+        session_paths,
+        options.since.clone(),
+        options.until.clone(),
+    )
+    .await?;
+
+    let by_path: HashMap<&str, &SessionMeta> =
+        sessions.iter().map(|s| (s.file_path.as_str(), s)).collect();
+
+    let mut report = RateLimitReport::default();
+    let mut days: BTreeMap<String, RateLimitDay> = BTreeMap::new();
+    for row in rows {
+        let Some(session) = by_path.get(row.session_path.as_str()) else {
+            continue;
+        };
+        let date = row
+            .timestamp
+            .get(..10)
+            .unwrap_or(&row.timestamp)
+            .to_string();
+        let day = days.entry(date.clone()).or_insert_with(|| RateLimitDay {
+            date,
+            usage_limits: 0,
+            rate_limits: 0,
+        });
+        if row.kind == "usage_limit" {
+            report.usage_limits += 1;
+            day.usage_limits += 1;
+        } else {
+            report.rate_limits += 1;
+            day.rate_limits += 1;
+        }
+
+        report.hits.push(RateLimitHit {
+            session_id: session.id.clone(),
+            file_path: row.session_path,
+            project_path: session.project_path.clone(),
+            project_display: session.project_display.clone(),
+            subagent_path: row.subagent_path,
+            entry_index: row.entry_index,
+            timestamp: row.timestamp,
+            kind: row.kind,
+            // Sessao que comecou ja bloqueada: usa o primeiro modelo conhecido
+            model: row.model.or_else(|| session.models.first().cloned()),
+            message: row.message,
+            reset_at: row.reset_at,
+            reset_hint: row.reset_hint,
+        });
+    }
+
+    report.days = days.into_values().collect();
+    Ok(report)
+}
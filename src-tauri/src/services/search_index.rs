@@ -0,0 +1,635 @@
+use crate::models::transcript::{ContentBlock, MessageEntry, TranscriptEntry};
+use crate::services::search_query::{FilterKind, SearchQuery, SearchScope};
+use crate::services::{
+    index_bash, index_file_touches, index_permissions, index_rate_limits, index_timings,
+    index_tool_calls, session_archive,
+};
+use rusqlite::{params, params_from_iter, Connection, Row};
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+const INDEX_FILE: &str = "search-index.db";
+
+/// Incrementar quando o schema ou o conteudo indexado mudar (forca rebuild)
+const SCHEMA_VERSION: i64 = 13;
+
+/// Limite de entries retornados pelo indice por busca (os mais relevantes ou mais recentes),
+/// contado depois do filtro de projeto, das negacoes e dos regex
+pub const MAX_INDEX_HITS: usize = 5000;
+
+/// Tamanho dos lotes de rowids ao carregar conteudo dos hits
+const CONTENT_BATCH: usize = 500;
+
+/// Tabelas de cada analise (nome, schema), mantidas pelos modulos `index_*`
+const FEATURE_TABLES: &[(&str, &str)] = &[
+    (index_file_touches::TABLE, index_file_touches::SCHEMA),
+    (index_bash::TABLE, index_bash::SCHEMA),
+    (index_tool_calls::TABLE, index_tool_calls::SCHEMA),
+    (index_timings::TABLE, index_timings::SCHEMA),
+    (index_rate_limits::TABLE, index_rate_limits::SCHEMA),
+    (index_permissions::TABLE, index_permissions::SCHEMA),
+];
+
+/// Entry encontrado no indice
+#[derive(Debug, Clone)]
+pub struct IndexHit {
+    pub rowid: i64,
+    pub path: String,
+    /// Sessao dona do arquivo (o proprio `path`, ou a sessao pai em transcripts de subagente)
+    pub session_path: String,
+    pub entry_index: u32,
+    pub uuid: Option<String>,
+    pub entry_type: String,
+    pub timestamp: String,
+    /// Score bm25 do FTS5 (menor = mais relevante; 0 quando a consulta so tem filtros)
+    pub rank: f64,
+    /// Conteudo do entry; carregado so quando necessario (ver `load_contents`)
+    pub content: Option<HitContent>,
+}
+
+/// Colunas de texto de um entry indexado
+#[derive(Debug, Clone, Default)]
+pub struct HitContent {
+    pub text: String,
+    pub tool_input: String,
+    pub tool_result: String,
+}
+
+impl HitContent {
+    /// Colunas de texto na ordem de SearchScope
+    pub fn columns(&self) -> [&str; 3] {
+        [&self.text, &self.tool_input, &self.tool_result]
+    }
+
+    pub fn scoped(&self, scope: SearchScope) -> &str {
+        match scope {
+            SearchScope::Text => &self.text,
+            SearchScope::ToolUse => &self.tool_input,
+            SearchScope::ToolResult => &self.tool_result,
+        }
+    }
+}
+
+/// Colunas indexadas de um entry user/assistant
+struct EntryColumns {
+    text: String,
+    tool_input: String,
+    tool_result: String,
+    tools: String,
+    model: Option<String>,
+    branch: Option<String>,
+}
+
+/// Estado de indexacao de um arquivo
+struct IndexedFile {
+    session_path: String,
+    size: u64,
+    mtime_ms: u64,
+    parsed_bytes: u64,
+    entry_count: u32,
+}
+
+fn open_index(data_dir: &Path) -> Result<Connection, String> {
+    std::fs::create_dir_all(data_dir).map_err(|e| format!("Failed to create data dir: {}", e))?;
+
+    let conn = Connection::open(data_dir.join(INDEX_FILE))
+        .map_err(|e| format!("Failed to open search index: {}", e))?;
+    conn.busy_timeout(std::time::Duration::from_secs(5))
+        .map_err(|e| format!("Failed to configure search index: {}", e))?;
+    conn.pragma_update(None, "journal_mode", "WAL")
+        .map_err(|e| format!("Failed to configure search index: {}", e))?;
+
+    let version: i64 = conn
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(|e| format!("Failed to read index version: {}", e))?;
+
+    if version != SCHEMA_VERSION {
+        // Schema antigo: descarta e reconstroi do zero
+        let mut drop = String::from(
+            "DROP TABLE IF EXISTS files;
+             DROP TABLE IF EXISTS entries;",
+        );
+        for (table, _) in FEATURE_TABLES {
+            drop.push_str(&format!("DROP TABLE IF EXISTS {};", table));
+        }
+        conn.execute_batch(&drop)
+            .map_err(|e| format!("Failed to reset search index: {}", e))?;
+    }
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS files (
+             path TEXT PRIMARY KEY,
+             session_path TEXT NOT NULL,
+             size INTEGER NOT NULL,
+             mtime_ms INTEGER NOT NULL,
+             parsed_bytes INTEGER NOT NULL,
+             entry_count INTEGER NOT NULL
+         );
+         CREATE VIRTUAL TABLE IF NOT EXISTS entries USING fts5(
+             text,
+             tool_input,
+             tool_result,
+             path UNINDEXED,
+             entry_index UNINDEXED,
+             uuid UNINDEXED,
+             entry_type UNINDEXED,
+             timestamp UNINDEXED,
+             tools UNINDEXED,
+             model UNINDEXED,
+             branch UNINDEXED,
+             tokenize = 'unicode61 remove_diacritics 2'
+         );",
+    )
+    .map_err(|e| format!("Failed to create search index: {}", e))?;
+    for (_, schema) in FEATURE_TABLES {
+        conn.execute_batch(schema)
+            .map_err(|e| format!("Failed to create search index: {}", e))?;
+    }
+
+    conn.pragma_update(None, "user_version", SCHEMA_VERSION)
+        .map_err(|e| format!("Failed to write index version: {}", e))?;
+
+    Ok(conn)
+}
+
+/// Sincroniza o indice com a lista atual de arquivos, como (arquivo, sessao dona).
+/// Transcripts de subagente entram com a sessao pai como dona.
+/// Arquivos inalterados sao ignorados, arquivos que cresceram sao indexados a partir
+/// do ultimo offset e arquivos removidos saem do indice.
+pub async fn sync_index(data_dir: &Path, files: Vec<(String, String)>) -> Result<(), String> {
+    let data_dir = data_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || sync_index_blocking(&data_dir, &files))
+        .await
+        .map_err(|e| format!("Search index task failed: {}", e))?
+}
+
+fn sync_index_blocking(data_dir: &Path, files: &[(String, String)]) -> Result<(), String> {
+    let mut conn = open_index(data_dir)?;
+
+    let known = read_indexed_files(&conn)?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start index transaction: {}", e))?;
+
+    for (path, session_path) in files {
+        let Ok(metadata) = std::fs::metadata(path) else {
+            continue;
+        };
+        let size = metadata.len();
+        let mtime_ms = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        let (start, first_index) = match known.get(path) {
+            Some(k) if k.size == size && k.mtime_ms == mtime_ms => {
+                // Sessao pai mudou de arquivo (ex: arquivada) sem o subagente mudar
+                if k.session_path != *session_path {
+                    tx.execute(
+                        "UPDATE files SET session_path = ?2 WHERE path = ?1",
+                        params![path, session_path],
+                    )
+                    .map_err(|e| format!("Failed to update index file state: {}", e))?;
+                }
+                continue;
+            }
+            // Append-only: continua de onde parou
+            Some(k) if !session_archive::is_compressed(path) && k.parsed_bytes <= size && k.size <= size => {
+                (k.parsed_bytes, k.entry_count)
+            }
+            Some(_) => {
+                delete_file(&tx, path)?;
+                (0, 0)
+            }
+            None => (0, 0),
+        };
+
+        let (parsed_bytes, entry_count) = index_file(&tx, Path::new(path), start, first_index)?;
+
+        tx.execute(
+            "INSERT OR REPLACE INTO files (path, session_path, size, mtime_ms, parsed_bytes, entry_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                path,
+                session_path,
+                size as i64,
+                mtime_ms as i64,
+                parsed_bytes as i64,
+                entry_count
+            ],
+        )
+        .map_err(|e| format!("Failed to update index file state: {}", e))?;
+    }
+
+    // Remove do indice arquivos que nao existem mais
+    let present: HashSet<&String> = files.iter().map(|(path, _)| path).collect();
+    for path in known.keys().filter(|p| !present.contains(p)) {
+        delete_file(&tx, path)?;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit search index: {}", e))
+}
+
+fn read_indexed_files(conn: &Connection) -> Result<HashMap<String, IndexedFile>, String> {
+    let mut stmt = conn
+        .prepare("SELECT path, session_path, size, mtime_ms, parsed_bytes, entry_count FROM files")
+        .map_err(|e| format!("Failed to query index files: {}", e))?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                IndexedFile {
+                    session_path: row.get(1)?,
+                    size: row.get::<_, i64>(2)? as u64,
+                    mtime_ms: row.get::<_, i64>(3)? as u64,
+                    parsed_bytes: row.get::<_, i64>(4)? as u64,
+                    entry_count: row.get(5)?,
+                },
+            ))
+        })
+        .map_err(|e| format!("Failed to query index files: {}", e))?;
+
+    Ok(rows.flatten().collect())
+}
+
+fn delete_file(conn: &Connection, path: &str) -> Result<(), String> {
+    conn.execute("DELETE FROM entries WHERE path = ?1", params![path])
+        .map_err(|e| format!("Failed to remove indexed entries: {}", e))?;
+    for (table, _) in FEATURE_TABLES {
+        conn.execute(&format!("DELETE FROM {} WHERE path = ?1", table), params![path])
+            .map_err(|e| format!("Failed to remove indexed {}: {}", table, e))?;
+    }
+    conn.execute("DELETE FROM files WHERE path = ?1", params![path])
+        .map_err(|e| format!("Failed to remove indexed file: {}", e))?;
+    Ok(())
+}
+
+/// Indexa as linhas completas a partir de `start`; retorna (offset final, total de entries).
+/// Tabelas de ferramentas completam a linha do tool_use pelo tool_use_id, entao resultados
+/// gravados depois de uma indexacao incremental tambem entram.
+fn index_file(
+    conn: &Connection,
+    path: &Path,
+    start: u64,
+    first_index: u32,
+) -> Result<(u64, u32), String> {
+    let Some(buf) = read_from(path, start) else {
+        return Ok((start, first_index));
+    };
+
+    // Ignora linha final incompleta (arquivo ainda sendo escrito)
+    let complete_len = buf.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+    let text = String::from_utf8_lossy(&buf[..complete_len]);
+    let path_str = path.to_string_lossy();
+
+    let mut stmt = conn
+        .prepare_cached(
+            "INSERT INTO entries (text, tool_input, tool_result, path, entry_index, uuid,
+                                  entry_type, timestamp, tools, model, branch)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        )
+        .map_err(|e| format!("Failed to prepare index insert: {}", e))?;
+
+    // entry_index segue a mesma numeracao de session_reader
+    let mut entry_index = first_index;
+    for line in text.lines().filter(|l| !l.trim().is_empty()) {
+        // Mesmo criterio de session_reader: toda linha com objeto JSON conta
+        let Ok(entry) = serde_json::from_str::<TranscriptEntry>(line) else {
+            continue;
+        };
+
+        index_rate_limits::record(conn, &path_str, entry_index, &entry)?;
+
+        if let Some(message_entry) = entry.message_entry() {
+            index_file_touches::record(conn, &path_str, entry_index, message_entry)?;
+            index_bash::record(conn, &path_str, entry_index, message_entry)?;
+            index_tool_calls::record(conn, &path_str, entry_index, message_entry)?;
+            index_timings::record(conn, &path_str, entry_index, &entry)?;
+            index_permissions::record(conn, &path_str, entry_index, message_entry)?;
+
+            let columns = entry_columns(conn, &path_str, message_entry)?;
+            let has_content = !columns.text.trim().is_empty()
+                || !columns.tool_input.is_empty()
+                || !columns.tool_result.trim().is_empty();
+
+            if has_content {
+                let uuid = entry.uuid();
+                let timestamp = entry.timestamp().unwrap_or("");
+                let entry_type = entry.entry_type();
+                stmt.execute(params![
+                    columns.text,
+                    columns.tool_input,
+                    columns.tool_result,
+                    path_str,
+                    entry_index,
+                    uuid,
+                    entry_type,
+                    timestamp,
+                    columns.tools,
+                    columns.model,
+                    columns.branch,
+                ])
+                .map_err(|e| format!("Failed to index entry: {}", e))?;
+            }
+        }
+        entry_index += 1;
+    }
+
+    Ok((start + complete_len as u64, entry_index))
+}
+
+/// Condicoes comuns de escopo: sessoes (None = todas) e datas YYYY-MM-DD inclusivas.
+/// Sessoes casam pelo `session_path` de `files` (a consulta faz `JOIN files USING (path)`),
+/// entao linhas de transcripts de subagente contam para a sessao pai.
+/// Retorna false quando a lista de sessoes e vazia (nenhuma linha pode casar).
+pub fn push_scope(
+    conditions: &mut Vec<String>,
+    values: &mut Vec<String>,
+    session_paths: Option<Vec<String>>,
+    since: Option<String>,
+    until: Option<String>,
+) -> bool {
+    if let Some(paths) = session_paths {
+        if paths.is_empty() {
+            return false;
+        }
+        conditions.push(format!("session_path IN ({})", vec!["?"; paths.len()].join(", ")));
+        values.extend(paths);
+    }
+    if let Some(since) = since {
+        conditions.push("substr(timestamp, 1, 10) >= ?".to_string());
+        values.push(since);
+    }
+    if let Some(until) = until {
+        conditions.push("substr(timestamp, 1, 10) <= ?".to_string());
+        values.push(until);
+    }
+    true
+}
+
+/// Clausula WHERE com as condicoes unidas por AND (vazia sem condicoes)
+pub fn where_clause(conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    }
+}
+
+/// Executa uma consulta no indice fora do runtime async, mapeando cada linha com `map`.
+/// `what` nomeia a consulta nas mensagens de erro.
+pub async fn run_query<T, F>(
+    data_dir: &Path,
+    what: &'static str,
+    sql: String,
+    values: Vec<String>,
+    map: F,
+) -> Result<Vec<T>, String>
+where
+    T: Send + 'static,
+    F: Fn(&Row) -> rusqlite::Result<T> + Send + 'static,
+{
+    let db_dir: PathBuf = data_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let conn = open_index(&db_dir)?;
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to prepare {} query: {}", what, e))?;
+        let rows = stmt
+            .query_map(params_from_iter(values.iter()), map)
+            .map_err(|e| format!("Failed to run {} query: {}", what, e))?;
+
+        Ok(rows.flatten().collect())
+    })
+    .await
+    .map_err(|e| format!("Search index task failed: {}", e))?
+}
+
+fn read_from(path: &Path, start: u64) -> Option<Vec<u8>> {
+    let mut file = session_archive::open(path).ok()?;
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).ok()?;
+    Some(buf)
+}
+
+/// Separa o conteudo de um entry em texto, inputs de tool_use e conteudo de tool_result.
+/// tool_results recebem o nome da tool pelo tool_use_id gravado em `tool_calls`
+/// (registrado antes), inclusive quando o tool_use ficou antes do offset incremental.
+fn entry_columns(
+    conn: &Connection,
+    path: &str,
+    entry: &MessageEntry,
+) -> Result<EntryColumns, String> {
+    let mut tool_input = Vec::new();
+    let mut tool_result = Vec::new();
+    let mut tools = Vec::new();
+
+    for block in entry.message.blocks() {
+        match block {
+            ContentBlock::ToolUse(tool) | ContentBlock::ServerToolUse(tool) => {
+                tools.push(tool.name.clone());
+                if !tool.input.is_null() {
+                    tool_input.push(format!("{} {}", tool.name, tool.input));
+                }
+            }
+            ContentBlock::ToolResult(result) => {
+                let name = index_tool_calls::tool_name(conn, path, &result.tool_use_id)?;
+                tools.extend(name);
+                tool_result.push(result.text());
+            }
+            _ => {}
+        }
+    }
+
+    Ok(EntryColumns {
+        text: entry.message.text(),
+        tool_input: tool_input.join("\n"),
+        tool_result: tool_result.join("\n"),
+        // Espacos nas pontas permitem LIKE '% nome %' por nome exato
+        tools: if tools.is_empty() { String::new() } else { format!(" {} ", tools.join(" ")) },
+        model: entry.message.model.clone(),
+        branch: entry.meta.git_branch.clone(),
+    })
+}
+
+/// Escapa `%`, `_` e `!` para um padrao LIKE com `ESCAPE '!'`
+pub fn like_escape(text: &str) -> String {
+    text.replace('!', "!!").replace('%', "!%").replace('_', "!_")
+}
+
+/// Consulta o indice: termos positivos via FTS5 (ordenado por bm25) e filtros de entry
+/// via SQL. Sem termos de texto, lista os entries que passam nos filtros, mais recentes primeiro.
+/// `session_paths` aplica o filtro de projeto (None = todas as sessoes).
+/// Negacoes e regex sao avaliadas sobre o conteudo enquanto as linhas sao lidas, ate
+/// `MAX_INDEX_HITS + 1` hits (um a mais indica que o limite cortou resultados).
+pub async fn query_index(
+    data_dir: &Path,
+    query: &SearchQuery,
+    session_paths: Option<Vec<String>>,
+) -> Result<Vec<IndexHit>, String> {
+    let mut conditions: Vec<String> = Vec::new();
+    let mut values: Vec<String> = Vec::new();
+    if !push_scope(&mut conditions, &mut values, session_paths, None, None) {
+        return Ok(Vec::new());
+    }
+
+    let fts = query.fts_expression();
+    if let Some(expr) = &fts {
+        conditions.push("entries MATCH ?".to_string());
+        values.push(expr.clone());
+    }
+
+    for filter in &query.filters {
+        let (condition, value) = match &filter.kind {
+            FilterKind::Role(role) => ("entry_type = ?", role.clone()),
+            FilterKind::Tool(tool) => (
+                "coalesce(tools, '') LIKE ? ESCAPE '!'",
+                format!("% {} %", like_escape(tool)),
+            ),
+            FilterKind::Model(model) => (
+                "coalesce(model, '') LIKE ? ESCAPE '!'",
+                format!("%{}%", like_escape(model)),
+            ),
+            FilterKind::After(date) => ("timestamp >= ?", date.clone()),
+            FilterKind::Before(date) => ("timestamp < ?", date.clone()),
+            FilterKind::Branch(branch) => ("coalesce(branch, '') = ?", branch.clone()),
+            // Filtro de projeto chega como `session_paths`
+            FilterKind::Project(_) => continue,
+        };
+        conditions.push(if filter.negated {
+            format!("NOT ({})", condition)
+        } else {
+            condition.to_string()
+        });
+        values.push(value);
+    }
+
+    let (rank, order) = if fts.is_some() {
+        ("bm25(entries)", "rank")
+    } else {
+        ("0.0", "timestamp DESC")
+    };
+    // Conteudo lido junto so quando necessario (negacao/regex)
+    let with_content = query.needs_content();
+    let content_columns = if with_content {
+        ", text, tool_input, tool_result"
+    } else {
+        ""
+    };
+    let sql = format!(
+        "SELECT entries.rowid, path, session_path, entry_index, uuid, entry_type, timestamp, {} AS rank{}
+         FROM entries JOIN files USING (path) {} ORDER BY {}",
+        rank,
+        content_columns,
+        where_clause(&conditions),
+        order
+    );
+
+    let query = query.clone();
+    let db_dir: PathBuf = data_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let conn = open_index(&db_dir)?;
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to prepare search query: {}", e))?;
+        let rows = stmt
+            .query_map(params_from_iter(values.iter()), |row| {
+                let content = if with_content {
+                    Some(HitContent {
+                        text: row.get(8)?,
+                        tool_input: row.get(9)?,
+                        tool_result: row.get(10)?,
+                    })
+                } else {
+                    None
+                };
+                Ok(IndexHit {
+                    rowid: row.get(0)?,
+                    path: row.get(1)?,
+                    session_path: row.get(2)?,
+                    entry_index: row.get(3)?,
+                    uuid: row.get(4)?,
+                    entry_type: row.get(5)?,
+                    timestamp: row.get(6)?,
+                    rank: row.get(7)?,
+                    content,
+                })
+            })
+            .map_err(|e| format!("Failed to run search query: {}", e))?;
+
+        let mut hits = Vec::new();
+        for hit in rows.flatten() {
+            let matches = hit
+                .content
+                .as_ref()
+                .is_none_or(|c| query.evaluate(c.columns()).is_some());
+            if matches {
+                hits.push(hit);
+                if hits.len() > MAX_INDEX_HITS {
+                    break;
+                }
+            }
+        }
+        Ok(hits)
+    })
+    .await
+    .map_err(|e| format!("Search index task failed: {}", e))?
+}
+
+/// Carrega o conteudo dos hits que ainda nao o possuem
+pub async fn load_contents(data_dir: &Path, hits: &mut [IndexHit]) -> Result<(), String> {
+    let rowids: Vec<i64> = hits
+        .iter()
+        .filter(|h| h.content.is_none())
+        .map(|h| h.rowid)
+        .collect();
+    if rowids.is_empty() {
+        return Ok(());
+    }
+
+    let db_dir: PathBuf = data_dir.to_path_buf();
+    let mut contents: HashMap<i64, HitContent> = tokio::task::spawn_blocking(move || {
+        let conn = open_index(&db_dir)?;
+        let mut contents = HashMap::new();
+
+        for batch in rowids.chunks(CONTENT_BATCH) {
+            let placeholders = vec!["?"; batch.len()].join(", ");
+            let sql = format!(
+                "SELECT rowid, text, tool_input, tool_result FROM entries WHERE rowid IN ({})",
+                placeholders
+            );
+            let mut stmt = conn
+                .prepare(&sql)
+                .map_err(|e| format!("Failed to prepare content query: {}", e))?;
+            let rows = stmt
+                .query_map(params_from_iter(batch.iter()), |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        HitContent {
+                            text: row.get(1)?,
+                            tool_input: row.get(2)?,
+                            tool_result: row.get(3)?,
+                        },
+                    ))
+                })
+                .map_err(|e| format!("Content query failed: {}", e))?;
+            contents.extend(rows.flatten());
+        }
+
+        Ok::<_, String>(contents)
+    })
+    .await
+    .map_err(|e| format!("Search index task failed: {}", e))??;
+
+    for hit in hits.iter_mut().filter(|h| h.content.is_none()) {
+        hit.content = Some(contents.remove(&hit.rowid).unwrap_or_default());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,370 @@
+use regex::{Regex, RegexBuilder};
+
+/// Parte do entry onde os termos de texto sao procurados
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchScope {
+    Text,
+    ToolUse,
+    ToolResult,
+}
+
+impl SearchScope {
+    pub const ALL: [SearchScope; 3] = [SearchScope::Text, SearchScope::ToolUse, SearchScope::ToolResult];
+
+    /// Coluna correspondente no indice FTS
+    pub fn column(self) -> &'static str {
+        match self {
+            SearchScope::Text => "text",
+            SearchScope::ToolUse => "tool_input",
+            SearchScope::ToolResult => "tool_result",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SearchScope::Text => "text",
+            SearchScope::ToolUse => "tool_use",
+            SearchScope::ToolResult => "tool_result",
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            SearchScope::Text => 0,
+            SearchScope::ToolUse => 1,
+            SearchScope::ToolResult => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum TextTerm {
+    /// Palavra simples (busca por prefixo no indice)
+    Word(String),
+    /// Frase entre aspas
+    Phrase(String),
+    /// Expressao regular entre barras: /pattern/
+    Pattern(Regex),
+}
+
+#[derive(Debug, Clone)]
+pub struct Term {
+    pub value: TextTerm,
+    pub negated: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum FilterKind {
+    Project(String),
+    Role(String),
+    Tool(String),
+    Model(String),
+    /// Data YYYY-MM-DD (inclusiva)
+    After(String),
+    /// Data YYYY-MM-DD (exclusiva)
+    Before(String),
+    Branch(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Filter {
+    pub kind: FilterKind,
+    pub negated: bool,
+    /// Texto original do filtro, devolvido em SearchMatch
+    pub raw: String,
+}
+
+/// Consulta estruturada: termos de texto, filtros `chave:valor` e escopos `in:`
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    pub terms: Vec<Term>,
+    pub filters: Vec<Filter>,
+    pub scopes: Vec<SearchScope>,
+}
+
+impl SearchQuery {
+    /// Interpreta a sintaxe de busca:
+    /// `project:rex role:user tool:Bash model:opus after:2026-09-01 before:2026-10-01
+    /// branch:main "frase exata" -excluir /regex/ in:tool_use in:tool_result in:all`
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut query = SearchQuery::default();
+        let chars: Vec<char> = input.chars().collect();
+        let mut i = 0;
+
+        while i < chars.len() {
+            if chars[i].is_whitespace() {
+                i += 1;
+                continue;
+            }
+
+            let negated = chars[i] == '-' && chars.get(i + 1).is_some_and(|c| !c.is_whitespace());
+            if negated {
+                i += 1;
+            }
+
+            // Frase entre aspas
+            if chars[i] == '"' {
+                let (value, next) = read_quoted(&chars, i);
+                i = next;
+                if !value.trim().is_empty() {
+                    query.terms.push(Term {
+                        value: TextTerm::Phrase(value.to_lowercase()),
+                        negated,
+                    });
+                }
+                continue;
+            }
+
+            // Regex entre barras
+            if chars[i] == '/' {
+                if let Some((pattern, next)) = read_regex(&chars, i) {
+                    i = next;
+                    let regex = RegexBuilder::new(&pattern)
+                        .case_insensitive(true)
+                        .build()
+                        .map_err(|e| format!("Invalid regex /{}/: {}", pattern, e))?;
+                    query.terms.push(Term {
+                        value: TextTerm::Pattern(regex),
+                        negated,
+                    });
+                    continue;
+                }
+            }
+
+            // Token simples, possivelmente chave:valor (valor pode ser entre aspas)
+            let start = i;
+            let mut quoted = None;
+            while i < chars.len() && !chars[i].is_whitespace() {
+                if chars[i] == ':' && chars.get(i + 1) == Some(&'"') {
+                    let key: String = chars[start..i].iter().collect();
+                    let (value, next) = read_quoted(&chars, i + 1);
+                    quoted = Some((key, value));
+                    i = next;
+                    break;
+                }
+                i += 1;
+            }
+
+            if let Some((key, value)) = quoted {
+                query.push_token(&key, Some(value), negated)?;
+                continue;
+            }
+
+            let token: String = chars[start..i].iter().collect();
+            match token.split_once(':') {
+                Some((key, value)) if !key.is_empty() && !value.is_empty() => {
+                    query.push_token(key, Some(value.to_string()), negated)?
+                }
+                _ => query.push_token(&token, None, negated)?,
+            }
+        }
+
+        if query.scopes.is_empty() {
+            query.scopes.push(SearchScope::Text);
+        }
+
+        Ok(query)
+    }
+
+    fn push_token(&mut self, key: &str, value: Option<String>, negated: bool) -> Result<(), String> {
+        let Some(value) = value else {
+            self.push_word(key, negated);
+            return Ok(());
+        };
+
+        let prefix = if negated { "-" } else { "" };
+        let raw = format!("{}{}:{}", prefix, key, value);
+        let kind = match key.to_lowercase().as_str() {
+            "project" => FilterKind::Project(value.to_lowercase()),
+            "role" => FilterKind::Role(value.to_lowercase()),
+            "tool" => FilterKind::Tool(value),
+            "model" => FilterKind::Model(value.to_lowercase()),
+            "after" => FilterKind::After(parse_date(&value)?),
+            "before" => FilterKind::Before(parse_date(&value)?),
+            "branch" => FilterKind::Branch(value),
+            "in" | "scope" => {
+                let scopes: &[SearchScope] = match value.to_lowercase().as_str() {
+                    "text" => &[SearchScope::Text],
+                    "tool_use" | "tool-use" | "input" => &[SearchScope::ToolUse],
+                    "tool_result" | "tool-result" | "output" => &[SearchScope::ToolResult],
+                    "tools" => &[SearchScope::ToolUse, SearchScope::ToolResult],
+                    "all" => &SearchScope::ALL,
+                    other => return Err(format!("Unknown search scope: {}", other)),
+                };
+                for scope in scopes {
+                    if !self.scopes.contains(scope) {
+                        self.scopes.push(*scope);
+                    }
+                }
+                return Ok(());
+            }
+            // Chave desconhecida (ex: URL, "foo:bar"): trata como texto
+            _ => {
+                self.push_word(&format!("{}:{}", key, value), negated);
+                return Ok(());
+            }
+        };
+
+        self.filters.push(Filter { kind, negated, raw });
+        Ok(())
+    }
+
+    fn push_word(&mut self, word: &str, negated: bool) {
+        if word.chars().any(|c| c.is_alphanumeric()) {
+            self.terms.push(Term {
+                value: TextTerm::Word(word.to_lowercase()),
+                negated,
+            });
+        }
+    }
+
+    /// Consulta sem termos nem filtros nao retorna nada
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.filters.is_empty()
+    }
+
+    /// Negacoes e regex so podem ser avaliadas com o conteudo do entry em maos
+    pub fn needs_content(&self) -> bool {
+        self.terms
+            .iter()
+            .any(|t| t.negated || matches!(t.value, TextTerm::Pattern(_)))
+    }
+
+    /// Expressao FTS5 para os termos positivos (palavras e frases) restrita aos escopos.
+    /// Negacoes e regex sao avaliadas depois, em `evaluate`.
+    pub fn fts_expression(&self) -> Option<String> {
+        let parts: Vec<String> = self
+            .terms
+            .iter()
+            .filter(|t| !t.negated)
+            .filter_map(|t| match &t.value {
+                TextTerm::Word(w) => Some(format!("\"{}\"*", w.replace('"', "\"\""))),
+                TextTerm::Phrase(p) => Some(format!("\"{}\"", p.replace('"', "\"\""))),
+                TextTerm::Pattern(_) => None,
+            })
+            .collect();
+
+        if parts.is_empty() {
+            return None;
+        }
+
+        let columns: Vec<&str> = self.scopes.iter().map(|s| s.column()).collect();
+        Some(format!("{{{}}} : ({})", columns.join(" "), parts.join(" ")))
+    }
+
+    /// Avalia negacoes e regex sobre as colunas [text, tool_input, tool_result] de um entry.
+    /// Retorna o escopo onde o match ocorreu, ou None se o entry for rejeitado.
+    pub fn evaluate(&self, columns: [&str; 3]) -> Option<SearchScope> {
+        let lowered: Vec<String> = columns.iter().map(|c| c.to_lowercase()).collect();
+        let mut matched_scope = None;
+
+        for term in &self.terms {
+            let found = self
+                .scopes
+                .iter()
+                .copied()
+                .find(|s| term_matches(&term.value, &lowered[s.index()]));
+
+            if term.negated {
+                if found.is_some() {
+                    return None;
+                }
+                continue;
+            }
+
+            // Regex precisa casar aqui; palavras/frases ja foram filtradas pelo FTS
+            if found.is_none() && matches!(term.value, TextTerm::Pattern(_)) {
+                return None;
+            }
+            if matched_scope.is_none() {
+                matched_scope = found;
+            }
+        }
+
+        // Sem termo localizavel (so filtros, ou tokenizacao do FTS divergente):
+        // usa o primeiro escopo com conteudo, considerando todos quando a consulta so tem filtros
+        let fallback: &[SearchScope] = if self.terms.is_empty() { &SearchScope::ALL } else { &self.scopes };
+        matched_scope.or_else(|| {
+            fallback
+                .iter()
+                .copied()
+                .find(|s| !lowered[s.index()].trim().is_empty())
+        })
+    }
+
+    /// Primeira posicao de um termo positivo no texto (ancora do snippet)
+    pub fn anchor(&self, text: &str) -> Option<(usize, usize)> {
+        let lowered = text.to_lowercase();
+        self.terms.iter().filter(|t| !t.negated).find_map(|t| match &t.value {
+            TextTerm::Word(w) | TextTerm::Phrase(w) => lowered.find(w.as_str()).map(|pos| (pos, w.len())),
+            TextTerm::Pattern(re) => re.find(&lowered).map(|m| (m.start(), m.len())),
+        })
+    }
+
+    /// Filtros por projeto sao aplicados no nivel da sessao
+    pub fn matches_project(&self, project_path: &str) -> bool {
+        let project_lower = project_path.to_lowercase();
+        self.filters.iter().all(|f| match &f.kind {
+            FilterKind::Project(p) => project_lower.contains(p.as_str()) != f.negated,
+            _ => true,
+        })
+    }
+
+    /// Consulta tem filtro `project:` (positivo ou negado)
+    pub fn has_project_filter(&self) -> bool {
+        self.filters
+            .iter()
+            .any(|f| matches!(f.kind, FilterKind::Project(_)))
+    }
+
+    /// Textos dos filtros ativos, para exibicao no resultado
+    pub fn filter_labels(&self) -> Vec<String> {
+        self.filters.iter().map(|f| f.raw.clone()).collect()
+    }
+}
+
+fn term_matches(term: &TextTerm, text: &str) -> bool {
+    match term {
+        TextTerm::Word(w) | TextTerm::Phrase(w) => text.contains(w.as_str()),
+        TextTerm::Pattern(re) => re.is_match(text),
+    }
+}
+
+/// Le um trecho entre aspas a partir de `start` (que aponta para a aspa de abertura)
+fn read_quoted(chars: &[char], start: usize) -> (String, usize) {
+    let mut i = start + 1;
+    let mut value = String::new();
+    while i < chars.len() && chars[i] != '"' {
+        value.push(chars[i]);
+        i += 1;
+    }
+    (value, (i + 1).min(chars.len()))
+}
+
+/// Le /pattern/ a partir de `start`; a barra final precisa ser seguida de espaco ou fim
+fn read_regex(chars: &[char], start: usize) -> Option<(String, usize)> {
+    let mut i = start + 1;
+    let mut pattern = String::new();
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if chars.get(i + 1) == Some(&'/') => {
+                pattern.push('/');
+                i += 2;
+            }
+            '/' if chars.get(i + 1).is_none_or(|c| c.is_whitespace()) => {
+                return if pattern.is_empty() { None } else { Some((pattern, i + 1)) };
+            }
+            c => {
+                pattern.push(c);
+                i += 1;
+            }
+        }
+    }
+    None
+}
+
+fn parse_date(value: &str) -> Result<String, String> {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", value))
+}
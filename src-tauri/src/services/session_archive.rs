@@ -0,0 +1,332 @@
+use crate::models::session::{
+    ArchiveFormat, ArchiveOptions, ArchiveReport, ArchivedSession, RestoredSession,
+};
+use crate::services::session_parser;
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+const PLAIN_EXT: &str = ".jsonl";
+const ZSTD_EXT: &str = ".jsonl.zst";
+const GZIP_EXT: &str = ".jsonl.gz";
+
+/// Nivel do zstd: bem mais compacto que o padrao (3) e ainda rapido para JSONL
+const ZSTD_LEVEL: i32 = 10;
+
+/// Sessoes modificadas ha menos tempo que isso podem estar abertas no Claude Code
+const ACTIVE_WINDOW: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// Leitor com seek sobre o JSONL (descomprimido quando necessario)
+pub trait ReadSeek: Read + Seek + Send {}
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// Nome de arquivo de transcript: `.jsonl`, `.jsonl.zst` ou `.jsonl.gz`
+pub fn is_transcript(filename: &str) -> bool {
+    [PLAIN_EXT, ZSTD_EXT, GZIP_EXT]
+        .iter()
+        .any(|ext| filename.ends_with(ext))
+}
+
+/// Nome do arquivo sem a extensao de transcript (id da sessao)
+pub fn session_id(filename: &str) -> &str {
+    [ZSTD_EXT, GZIP_EXT, PLAIN_EXT]
+        .iter()
+        .find_map(|ext| filename.strip_suffix(ext))
+        .unwrap_or(filename)
+}
+
+/// Id da sessao a partir do path do transcript (`<id>.jsonl`, `.jsonl.zst` ou `.jsonl.gz`)
+pub fn session_id_from_path(path: &Path) -> String {
+    path.file_name()
+        .map(|n| session_id(&n.to_string_lossy()).to_string())
+        .unwrap_or_default()
+}
+
+/// Transcript arquivado (comprimido)
+pub fn is_compressed(path: impl AsRef<Path>) -> bool {
+    let name = path.as_ref().to_string_lossy();
+    name.ends_with(ZSTD_EXT) || name.ends_with(GZIP_EXT)
+}
+
+/// Abre um transcript para leitura. Arquivos comprimidos sao descomprimidos em memoria,
+/// entao offsets sempre se referem ao JSONL original.
+pub fn open(path: &Path) -> io::Result<Box<dyn ReadSeek>> {
+    if is_compressed(path) {
+        Ok(Box::new(Cursor::new(read(path)?)))
+    } else {
+        Ok(Box::new(File::open(path)?))
+    }
+}
+
+/// Conteudo completo do transcript (descomprimido)
+pub fn read(path: &Path) -> io::Result<Vec<u8>> {
+    let name = path.to_string_lossy();
+    let file = BufReader::new(File::open(path)?);
+    let mut buf = Vec::new();
+    if name.ends_with(ZSTD_EXT) {
+        zstd::stream::Decoder::with_buffer(file)?.read_to_end(&mut buf)?;
+    } else if name.ends_with(GZIP_EXT) {
+        flate2::bufread::GzDecoder::new(file).read_to_end(&mut buf)?;
+    } else {
+        let mut file = file;
+        file.read_to_end(&mut buf)?;
+    }
+    Ok(buf)
+}
+
+/// Equivalente a `tokio::fs::read_to_string` que tambem le transcripts comprimidos
+pub async fn read_to_string(path: &Path) -> io::Result<String> {
+    if !is_compressed(path) {
+        return tokio::fs::read_to_string(path).await;
+    }
+    let path = path.to_path_buf();
+    let bytes = tokio::task::spawn_blocking(move || read(&path))
+        .await
+        .map_err(io::Error::other)??;
+    String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Reescreve um transcript no mesmo formato (comprimido ou nao), mantendo o mtime
+pub fn write(path: &Path, content: &[u8]) -> io::Result<()> {
+    let name = path.to_string_lossy();
+    let metadata = std::fs::metadata(path).ok();
+    let tmp = path.with_extension("tmp");
+    let result = (|| -> io::Result<()> {
+        let output = File::create(&tmp)?;
+        let output = if name.ends_with(ZSTD_EXT) {
+            let mut encoder = zstd::stream::Encoder::new(output, ZSTD_LEVEL)?;
+            encoder.write_all(content)?;
+            encoder.finish()?
+        } else if name.ends_with(GZIP_EXT) {
+            let mut encoder = flate2::write::GzEncoder::new(output, flate2::Compression::best());
+            encoder.write_all(content)?;
+            encoder.finish()?
+        } else {
+            let mut output = output;
+            output.write_all(content)?;
+            output
+        };
+        output.sync_all()
+    })();
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e);
+    }
+    std::fs::rename(&tmp, path)?;
+    if let Some(metadata) = metadata {
+        keep_mtime(path, &metadata);
+    }
+    Ok(())
+}
+
+/// Comprime sessoes selecionadas ou sem atividade ha `older_than_days` dias.
+/// O arquivo fica na mesma pasta (`<id>.jsonl.zst`/`.gz`) e continua listado e pesquisavel;
+/// o `.jsonl` original so e removido depois de conferir o arquivo gerado.
+pub async fn archive_sessions(
+    claude_dir: &str,
+    options: &ArchiveOptions,
+    cache_dir: &Path,
+    data_dir: &Path,
+) -> Result<ArchiveReport, String> {
+    if options.session_paths.is_none() && options.older_than_days.is_none() {
+        return Err("Select sessions or an age to archive".to_string());
+    }
+
+    let sessions = session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+    let cutoff = options
+        .older_than_days
+        .map(|days| chrono::Utc::now() - chrono::Duration::days(days as i64));
+    let project = options.project.as_deref().map(str::to_lowercase);
+
+    let selected: Vec<(String, PathBuf)> = sessions
+        .iter()
+        .filter(|s| !s.archived)
+        .filter(|s| {
+            options
+                .session_paths
+                .as_ref()
+                .is_none_or(|paths| paths.contains(&s.file_path))
+        })
+        .filter(|s| {
+            project
+                .as_ref()
+                .is_none_or(|p| s.project_path.to_lowercase().contains(p))
+        })
+        .filter(|s| {
+            cutoff.is_none_or(|cutoff| {
+                chrono::DateTime::parse_from_rfc3339(&s.last_timestamp)
+                    .is_ok_and(|last| last < cutoff)
+            })
+        })
+        .map(|s| (s.id.clone(), PathBuf::from(&s.file_path)))
+        .collect();
+
+    let format = options.format;
+    tokio::task::spawn_blocking(move || {
+        let mut report = ArchiveReport::default();
+        for (session_id, path) in selected {
+            match archive_file(&path, format) {
+                Ok((archive, original_bytes, archived_bytes)) => {
+                    report.original_bytes += original_bytes;
+                    report.archived_bytes += archived_bytes;
+                    report.archived.push(ArchivedSession {
+                        session_id,
+                        source: path.to_string_lossy().to_string(),
+                        archive: archive.to_string_lossy().to_string(),
+                        original_bytes,
+                        archived_bytes,
+                    });
+                }
+                Err(e) => report.errors.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+        report
+    })
+    .await
+    .map_err(|e| format!("Archive task failed: {}", e))
+}
+
+fn archive_file(path: &Path, format: ArchiveFormat) -> Result<(PathBuf, u64, u64), String> {
+    let name = path.to_string_lossy();
+    let stem = name
+        .strip_suffix(PLAIN_EXT)
+        .ok_or_else(|| "Not a .jsonl transcript".to_string())?;
+    let archive = PathBuf::from(match format {
+        ArchiveFormat::Zstd => format!("{}{}", stem, ZSTD_EXT),
+        ArchiveFormat::Gzip => format!("{}{}", stem, GZIP_EXT),
+    });
+    if archive.exists() {
+        return Err(format!("{} already exists", archive.display()));
+    }
+
+    let metadata = std::fs::metadata(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let recent = metadata
+        .modified()
+        .ok()
+        .and_then(|mtime| mtime.elapsed().ok())
+        .is_none_or(|age| age < ACTIVE_WINDOW);
+    if recent {
+        return Err("Session is still active".to_string());
+    }
+    let tmp = archive.with_extension("tmp");
+    let result = (|| -> io::Result<u64> {
+        let mut input = BufReader::new(File::open(path)?);
+        let output = File::create(&tmp)?;
+        let output = match format {
+            ArchiveFormat::Zstd => {
+                let mut encoder = zstd::stream::Encoder::new(output, ZSTD_LEVEL)?;
+                io::copy(&mut input, &mut encoder)?;
+                encoder.finish()?
+            }
+            ArchiveFormat::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(output, flate2::Compression::best());
+                io::copy(&mut input, &mut encoder)?;
+                encoder.finish()?
+            }
+        };
+        output.sync_all()?;
+        Ok(output.metadata()?.len())
+    })();
+    let archived_bytes = match result {
+        Ok(len) => len,
+        Err(e) => {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(format!("Failed to compress session: {}", e));
+        }
+    };
+
+    // Confere o arquivo antes de apagar o original
+    let restored = match verify_len(&tmp, format) {
+        Ok(len) => len,
+        Err(e) => {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(format!("Failed to verify archive: {}", e));
+        }
+    };
+    if restored != metadata.len() {
+        let _ = std::fs::remove_file(&tmp);
+        return Err("Archive verification failed: size mismatch".to_string());
+    }
+
+    // Linhas gravadas durante a compressao ficariam so no original apagado
+    if changed_since(path, &metadata) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err("Session changed while archiving".to_string());
+    }
+
+    std::fs::rename(&tmp, &archive).map_err(|e| format!("Failed to write archive: {}", e))?;
+    keep_mtime(&archive, &metadata);
+    std::fs::remove_file(path).map_err(|e| format!("Failed to remove original session: {}", e))?;
+    Ok((archive, metadata.len(), archived_bytes))
+}
+
+/// Arquivo com tamanho ou mtime diferente do lido antes (ou que sumiu)
+fn changed_since(path: &Path, before: &std::fs::Metadata) -> bool {
+    std::fs::metadata(path).map_or(true, |now| {
+        now.len() != before.len() || now.modified().ok() != before.modified().ok()
+    })
+}
+
+/// Tamanho descomprimido de um arquivo ainda com nome temporario
+fn verify_len(tmp: &Path, format: ArchiveFormat) -> io::Result<u64> {
+    let file = BufReader::new(File::open(tmp)?);
+    match format {
+        ArchiveFormat::Zstd => io::copy(
+            &mut zstd::stream::Decoder::with_buffer(file)?,
+            &mut io::sink(),
+        ),
+        ArchiveFormat::Gzip => {
+            io::copy(&mut flate2::bufread::GzDecoder::new(file), &mut io::sink())
+        }
+    }
+}
+
+/// Mantem o mtime original (listagem e "sessoes antigas" continuam corretas)
+fn keep_mtime(path: &Path, original: &std::fs::Metadata) {
+    if let (Ok(mtime), Ok(file)) = (original.modified(), File::options().write(true).open(path)) {
+        let _ = file.set_modified(mtime);
+    }
+}
+
+/// Descomprime um transcript arquivado de volta para `<id>.jsonl` (para `claude --resume`)
+pub async fn restore_session(archive_path: &str) -> Result<RestoredSession, String> {
+    let archive = PathBuf::from(archive_path);
+    if !is_compressed(&archive) {
+        return Err("Session is not archived".to_string());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let file_name = archive
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let session_id = session_id(&file_name).to_string();
+        let target = archive.with_file_name(format!("{}{}", session_id, PLAIN_EXT));
+        if target.exists() {
+            return Err(format!("{} already exists", target.display()));
+        }
+
+        let metadata =
+            std::fs::metadata(&archive).map_err(|e| format!("Failed to read archive: {}", e))?;
+        let bytes = read(&archive).map_err(|e| format!("Failed to decompress session: {}", e))?;
+        let tmp = target.with_extension("tmp");
+        let written =
+            File::create(&tmp).and_then(|mut f| f.write_all(&bytes).and_then(|_| f.sync_all()));
+        if let Err(e) = written {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(format!("Failed to restore session: {}", e));
+        }
+        std::fs::rename(&tmp, &target).map_err(|e| format!("Failed to restore session: {}", e))?;
+        keep_mtime(&target, &metadata);
+        std::fs::remove_file(&archive).map_err(|e| format!("Failed to remove archive: {}", e))?;
+
+        Ok(RestoredSession {
+            session_id,
+            file_path: target.to_string_lossy().to_string(),
+            bytes: bytes.len() as u64,
+        })
+    })
+    .await
+    .map_err(|e| format!("Restore task failed: {}", e))?
+}
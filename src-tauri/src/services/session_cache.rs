@@ -0,0 +1,241 @@
+use crate::models::transcript::{Message, TranscriptEntry};
+use crate::services::pricing::UsageAccumulator;
+use crate::services::{session_archive, session_parser};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+const CACHE_FILE: &str = "sessions-cache.json";
+const CACHE_VERSION: u32 = 5;
+
+/// Bloco lido do fim do arquivo ao procurar o ultimo timestamp
+const TAIL_CHUNK: u64 = 64 * 1024;
+
+/// Dados agregados de uma sessao, atualizaveis incrementalmente linha a linha
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionScan {
+    pub message_count: u32,
+    pub cwd: Option<String>,
+    /// Ultimo texto do assistente (fallback do titulo)
+    pub summary: Option<String>,
+    pub first_prompt: Option<String>,
+    /// Ultimo entry `summary` gravado pelo Claude Code
+    pub latest_summary: Option<String>,
+    pub first_timestamp: Option<String>,
+    /// Tokens e custo, com a mesma deduplicacao por `message.id` do painel de subagentes
+    pub usage: UsageAccumulator,
+    /// Modelos distintos na ordem do primeiro uso
+    pub models: Vec<String>,
+    /// Trocas de modelo no meio da sessao (ex: /model)
+    pub model_switches: u32,
+    pub last_model: Option<String>,
+}
+
+impl SessionScan {
+    pub fn ingest(&mut self, entry: &TranscriptEntry) {
+        if entry.message_entry().is_some() {
+            self.message_count += 1;
+        }
+        if self.cwd.is_none() {
+            self.cwd = entry.cwd().map(String::from);
+        }
+        if let Some(summary) = session_parser::entry_summary(entry) {
+            self.summary = Some(summary);
+        }
+        if self.first_prompt.is_none() {
+            self.first_prompt = session_parser::prompt_text(entry);
+        }
+        if self.first_timestamp.is_none() {
+            self.first_timestamp = entry.timestamp().map(String::from);
+        }
+        if let TranscriptEntry::Assistant(assistant) = entry {
+            self.ingest_model(&assistant.message);
+            self.usage.add(&assistant.message);
+        }
+        if let TranscriptEntry::Summary(summary) = entry {
+            if !summary.summary.trim().is_empty() {
+                self.latest_summary = Some(summary.summary.trim().to_string());
+            }
+        }
+    }
+
+    fn ingest_model(&mut self, message: &Message) {
+        let model = message.model.as_deref().unwrap_or("");
+        // "<synthetic>" marca mensagens geradas localmente (ex: erros de API)
+        if !model.is_empty() && !model.starts_with('<') {
+            if !self.models.iter().any(|m| m == model) {
+                self.models.push(model.to_string());
+            }
+            if self.last_model.as_deref().is_some_and(|last| last != model) {
+                self.model_switches += 1;
+            }
+            self.last_model = Some(model.to_string());
+        }
+    }
+}
+
+/// Entrada do cache: metadados do arquivo no momento do parse + agregados
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedSession {
+    pub size: u64,
+    pub mtime_ms: u64,
+    /// Offset logo apos a ultima linha completa ja processada
+    pub parsed_bytes: u64,
+    pub last_timestamp: String,
+    pub scan: SessionScan,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SessionCache {
+    version: u32,
+    sessions: HashMap<String, CachedSession>,
+}
+
+impl SessionCache {
+    /// Le o cache do disco; versao diferente ou arquivo corrompido resulta em cache vazio
+    pub async fn load(cache_dir: &Path) -> Self {
+        let Ok(content) = tokio::fs::read_to_string(cache_dir.join(CACHE_FILE)).await else {
+            return Self::empty();
+        };
+        match serde_json::from_str::<SessionCache>(&content) {
+            Ok(cache) if cache.version == CACHE_VERSION => cache,
+            _ => Self::empty(),
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            version: CACHE_VERSION,
+            sessions: HashMap::new(),
+        }
+    }
+
+    /// Grava via arquivo temporario + rename para nunca deixar o cache pela metade
+    pub async fn save(&self, cache_dir: &Path) -> Result<(), String> {
+        tokio::fs::create_dir_all(cache_dir)
+            .await
+            .map_err(|e| format!("Failed to create cache dir: {}", e))?;
+
+        let content = serde_json::to_string(self)
+            .map_err(|e| format!("Failed to serialize session cache: {}", e))?;
+
+        let tmp_path = cache_dir.join(format!("{}.tmp", CACHE_FILE));
+        tokio::fs::write(&tmp_path, content)
+            .await
+            .map_err(|e| format!("Failed to write session cache: {}", e))?;
+        tokio::fs::rename(&tmp_path, cache_dir.join(CACHE_FILE))
+            .await
+            .map_err(|e| format!("Failed to replace session cache: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, path: &str) -> Option<&CachedSession> {
+        self.sessions.get(path)
+    }
+
+    pub fn insert(&mut self, path: String, cached: CachedSession) {
+        self.sessions.insert(path, cached);
+    }
+
+    /// Remove entradas de arquivos que nao existem mais
+    pub fn retain_paths(&mut self, keep: &std::collections::HashSet<String>) {
+        self.sessions.retain(|path, _| keep.contains(path));
+    }
+}
+
+/// Atualiza (ou cria) a entrada de cache de um arquivo de sessao.
+/// Arquivos que so cresceram sao lidos a partir do ultimo offset processado.
+pub async fn refresh_entry(
+    path: PathBuf,
+    size: u64,
+    mtime_ms: u64,
+    previous: Option<CachedSession>,
+) -> Option<CachedSession> {
+    tokio::task::spawn_blocking(move || refresh_entry_blocking(&path, size, mtime_ms, previous))
+        .await
+        .ok()
+        .flatten()
+}
+
+fn refresh_entry_blocking(
+    path: &Path,
+    size: u64,
+    mtime_ms: u64,
+    previous: Option<CachedSession>,
+) -> Option<CachedSession> {
+    // So reaproveita o estado anterior quando o arquivo apenas cresceu (append-only)
+    let (mut scan, start) = match previous {
+        Some(prev) if !session_archive::is_compressed(path) && prev.parsed_bytes <= size && prev.size <= size => {
+            (prev.scan, prev.parsed_bytes)
+        }
+        _ => (SessionScan::default(), 0),
+    };
+
+    let mut file = session_archive::open(path).ok()?;
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut buf = Vec::with_capacity(size.saturating_sub(start) as usize);
+    file.read_to_end(&mut buf).ok()?;
+
+    // Ignora linha final incompleta (arquivo ainda sendo escrito)
+    let complete_len = buf.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+    let text = String::from_utf8_lossy(&buf[..complete_len]);
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Ok(entry) = serde_json::from_str::<TranscriptEntry>(line) {
+            scan.ingest(&entry);
+        }
+    }
+
+    let last_timestamp = read_last_timestamp(path).unwrap_or_default();
+
+    Some(CachedSession {
+        size,
+        mtime_ms,
+        parsed_bytes: start + complete_len as u64,
+        last_timestamp,
+        scan,
+    })
+}
+
+/// Le o ultimo `timestamp` do JSONL lendo apenas o final do arquivo
+pub fn read_last_timestamp(path: &Path) -> Option<String> {
+    let mut file = session_archive::open(path).ok()?;
+    let len = file.seek(SeekFrom::End(0)).ok()?;
+    let mut chunk = TAIL_CHUNK;
+
+    loop {
+        let start = len.saturating_sub(chunk);
+        file.seek(SeekFrom::Start(start)).ok()?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).ok()?;
+        let text = String::from_utf8_lossy(&buf);
+
+        // A primeira linha do bloco pode estar cortada; so e valida se o bloco comeca no inicio
+        let mut lines: Vec<&str> = text.lines().collect();
+        if start > 0 && !lines.is_empty() {
+            lines.remove(0);
+        }
+
+        for line in lines.iter().rev() {
+            if !line.contains("\"timestamp\"") {
+                continue;
+            }
+            let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else {
+                continue;
+            };
+            if let Some(ts) = value.get("timestamp").and_then(|v| v.as_str()) {
+                return Some(ts.to_string());
+            }
+        }
+
+        if start == 0 {
+            return None;
+        }
+        chunk *= 4;
+    }
+}
@@ -0,0 +1,282 @@
+use crate::models::session::{
+    ArchiveOptions, RetentionAction, RetentionCandidate, RetentionPolicy, RetentionReason,
+    RetentionReport, SessionMeta, TrashReport,
+};
+use crate::services::{session_archive, session_parser, subagents};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Padrao do Claude Code quando `cleanupPeriodDays` nao esta definido
+const DEFAULT_CLEANUP_PERIOD_DAYS: u32 = 30;
+
+/// Sessao com os arquivos que saem junto com ela
+struct Planned {
+    session: SessionMeta,
+    files: Vec<PathBuf>,
+    bytes: u64,
+}
+
+/// `cleanupPeriodDays` do ~/.claude/settings.json (sessoes mais antigas sao apagadas pelo Claude Code)
+pub async fn cleanup_period_days(claude_dir: &str) -> u32 {
+    let path = Path::new(claude_dir).join("settings.json");
+    tokio::fs::read_to_string(&path)
+        .await
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|root| root.get("cleanupPeriodDays").and_then(|v| v.as_u64()))
+        .map(|days| days.min(u32::MAX as u64) as u32)
+        .unwrap_or(DEFAULT_CLEANUP_PERIOD_DAYS)
+}
+
+/// Tamanho em disco de um arquivo ou pasta (sem seguir symlinks)
+pub fn disk_size(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| disk_size(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Move sessoes para a lixeira do sistema. So aceita paths que sao sessoes listadas.
+pub async fn trash_sessions(
+    claude_dir: &str,
+    session_paths: &[String],
+    cache_dir: &Path,
+    data_dir: &Path,
+) -> Result<TrashReport, String> {
+    let sessions = session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+    let mut unknown: Vec<String> = session_paths
+        .iter()
+        .filter(|p| !sessions.iter().any(|s| &s.file_path == *p))
+        .map(|p| format!("{}: not a session", p))
+        .collect();
+    let selected: Vec<SessionMeta> = sessions
+        .into_iter()
+        .filter(|s| session_paths.contains(&s.file_path))
+        .collect();
+
+    let claude = PathBuf::from(claude_dir);
+    let mut report = tokio::task::spawn_blocking(move || {
+        let planned = plan(&claude, selected, RetentionAction::Trash);
+        trash_planned(planned)
+    })
+    .await
+    .map_err(|e| format!("Trash task failed: {}", e))?;
+    report.errors.append(&mut unknown);
+
+    // Tira as sessoes removidas do cache e do indice
+    session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+    Ok(report)
+}
+
+/// Aplica (ou simula, com `dry_run`) uma politica de retencao
+pub async fn apply_retention(
+    claude_dir: &str,
+    policy: &RetentionPolicy,
+    cache_dir: &Path,
+    data_dir: &Path,
+    project_dir: impl Fn(&str) -> PathBuf,
+) -> Result<RetentionReport, String> {
+    let cleanup_period_days = cleanup_period_days(claude_dir).await;
+    let max_age_days = if policy.use_cleanup_period {
+        Some(cleanup_period_days)
+    } else {
+        policy.older_than_days
+    };
+    if max_age_days.is_none() && !policy.missing_projects {
+        return Err("Choose an age or missing projects rule".to_string());
+    }
+
+    let sessions = session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+    let cutoff = max_age_days.map(|days| chrono::Utc::now() - chrono::Duration::days(days as i64));
+    let project = policy.project.as_deref().map(str::to_lowercase);
+    let mut missing: HashMap<String, bool> = HashMap::new();
+
+    let mut selected = Vec::new();
+    let mut reasons_by_path: HashMap<String, Vec<RetentionReason>> = HashMap::new();
+    for session in sessions {
+        if project
+            .as_ref()
+            .is_some_and(|p| !session.project_path.to_lowercase().contains(p))
+        {
+            continue;
+        }
+        // Arquivar de novo nao libera nada
+        if policy.action == RetentionAction::Archive && session.archived {
+            continue;
+        }
+
+        let mut reasons = Vec::new();
+        if cutoff.is_some_and(|cutoff| {
+            chrono::DateTime::parse_from_rfc3339(&session.last_timestamp)
+                .is_ok_and(|last| last < cutoff)
+        }) {
+            reasons.push(RetentionReason::Expired);
+        }
+        if policy.missing_projects
+            && *missing
+                .entry(session.project_path.clone())
+                .or_insert_with(|| is_missing(&session.project_path, &project_dir))
+        {
+            reasons.push(RetentionReason::MissingProject);
+        }
+        if !reasons.is_empty() {
+            reasons_by_path.insert(session.file_path.clone(), reasons);
+            selected.push(session);
+        }
+    }
+
+    let claude = PathBuf::from(claude_dir);
+    let action = policy.action;
+    let planned = tokio::task::spawn_blocking(move || plan(&claude, selected, action))
+        .await
+        .map_err(|e| format!("Retention task failed: {}", e))?;
+
+    let candidates: Vec<RetentionCandidate> = planned
+        .iter()
+        .map(|p| RetentionCandidate {
+            session_id: p.session.id.clone(),
+            project_path: p.session.project_path.clone(),
+            project_display: p.session.project_display.clone(),
+            file_path: p.session.file_path.clone(),
+            last_timestamp: p.session.last_timestamp.clone(),
+            archived: p.session.archived,
+            reasons: reasons_by_path
+                .remove(&p.session.file_path)
+                .unwrap_or_default(),
+            files: p
+                .files
+                .iter()
+                .map(|f| f.to_string_lossy().to_string())
+                .collect(),
+            bytes: p.bytes,
+        })
+        .collect();
+
+    let mut report = RetentionReport {
+        dry_run: policy.dry_run,
+        action,
+        cleanup_period_days,
+        max_age_days,
+        total_bytes: candidates.iter().map(|c| c.bytes).sum(),
+        candidates,
+        processed: 0,
+        freed_bytes: 0,
+        errors: Vec::new(),
+    };
+    if policy.dry_run || planned.is_empty() {
+        return Ok(report);
+    }
+
+    match action {
+        RetentionAction::Trash => {
+            let trashed = tokio::task::spawn_blocking(move || trash_planned(planned))
+                .await
+                .map_err(|e| format!("Trash task failed: {}", e))?;
+            report.processed = trashed.session_ids.len() as u32;
+            report.freed_bytes = trashed.freed_bytes;
+            report.errors = trashed.errors;
+            session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+        }
+        RetentionAction::Archive => {
+            let options = ArchiveOptions {
+                session_paths: Some(planned.into_iter().map(|p| p.session.file_path).collect()),
+                format: policy.format,
+                ..Default::default()
+            };
+            let archived =
+                session_archive::archive_sessions(claude_dir, &options, cache_dir, data_dir)
+                    .await?;
+            report.processed = archived.archived.len() as u32;
+            report.freed_bytes = archived
+                .original_bytes
+                .saturating_sub(archived.archived_bytes);
+            report.errors = archived.errors;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Pasta do projeto que nao existe mais. Slugs sem path resolvido nao contam.
+pub fn is_missing(project_path: &str, project_dir: &impl Fn(&str) -> PathBuf) -> bool {
+    let looks_like_path = project_path.starts_with('/')
+        || project_path.starts_with("\\\\")
+        || matches!(project_path.get(1..3), Some(":\\") | Some(":/"));
+    looks_like_path && !project_dir(project_path).is_dir()
+}
+
+/// Arquivos de cada sessao. No archive so o transcript; no trash tambem a pasta `<id>/`
+/// (subagentes, tool results), subagentes no formato antigo, file-history, todos e session-env.
+fn plan(claude_dir: &Path, sessions: Vec<SessionMeta>, action: RetentionAction) -> Vec<Planned> {
+    let mut agents_by_dir: HashMap<PathBuf, HashMap<String, Vec<PathBuf>>> = HashMap::new();
+    let todos: Vec<PathBuf> = std::fs::read_dir(claude_dir.join("todos"))
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+
+    sessions
+        .into_iter()
+        .map(|session| {
+            let transcript = PathBuf::from(&session.file_path);
+            let mut files = vec![transcript.clone()];
+
+            if action == RetentionAction::Trash {
+                if let Some(project_dir) = transcript.parent() {
+                    files.push(project_dir.join(&session.id));
+                    let agents = agents_by_dir
+                        .entry(project_dir.to_path_buf())
+                        .or_insert_with(|| subagents::files_by_session(project_dir));
+                    files.extend(
+                        agents
+                            .get(&session.id)
+                            .into_iter()
+                            .flatten()
+                            .filter(|a| a.parent() == Some(project_dir))
+                            .cloned(),
+                    );
+                }
+                files.push(claude_dir.join("file-history").join(&session.id));
+                files.push(claude_dir.join("session-env").join(&session.id));
+                let prefix = format!("{}-", session.id);
+                files.extend(
+                    todos
+                        .iter()
+                        .filter(|t| {
+                            t.file_name()
+                                .is_some_and(|n| n.to_string_lossy().starts_with(&prefix))
+                        })
+                        .cloned(),
+                );
+                files.retain(|f| f.exists());
+            }
+
+            let bytes = files.iter().map(|f| disk_size(f)).sum();
+            Planned {
+                session,
+                files,
+                bytes,
+            }
+        })
+        .collect()
+}
+
+fn trash_planned(planned: Vec<Planned>) -> TrashReport {
+    let mut report = TrashReport::default();
+    for p in planned {
+        match trash::delete_all(&p.files) {
+            Ok(()) => {
+                report.files += p.files.len() as u32;
+                report.freed_bytes += p.bytes;
+                report.session_ids.push(p.session.id);
+            }
+            Err(e) => report
+                .errors
+                .push(format!("{}: {}", p.session.file_path, e)),
+        }
+    }
+    report
+}
@@ -0,0 +1,301 @@
+use crate::models::session::{FileDiff, SessionDiff, TurnRange};
+use crate::models::transcript::{ContentBlock, ToolUseBlock, TranscriptEntry};
+use crate::services::{conversation_tree, session_archive, session_parser};
+use serde_json::Value;
+use similar::{ChangeTag, TextDiff};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Linhas de contexto em volta de cada hunk
+const CONTEXT_LINES: usize = 3;
+
+/// Conteudo conhecido de um arquivo ao longo da sessao
+enum FileState {
+    /// Conteudo inteiro conhecido (Write ou originalFile do tool_result)
+    Full { original: String, current: String },
+    /// So os trechos old_string/new_string; cada par vira um hunk proprio, sem numeracao de linhas
+    Fragments(Vec<(String, String)>),
+}
+
+struct FileChanges {
+    file_path: String,
+    state: FileState,
+    edit_count: u32,
+    created: bool,
+    complete: bool,
+}
+
+/// Diff por arquivo das mudancas feitas pela sessao (ou por um intervalo de turnos)
+pub async fn session_diff(session_path: &str, range: TurnRange) -> Result<SessionDiff, String> {
+    let path = Path::new(session_path);
+    let entries = session_parser::read_transcript(path).await?;
+    let session_id = session_archive::session_id_from_path(path);
+    Ok(build_diff(session_id, &entries, range))
+}
+
+/// Grava o diff da sessao como um arquivo `.patch` aplicavel.
+/// Arquivos so com trechos (`complete == false`) ficam de fora; retorna o que foi gravado.
+pub async fn export_patch(
+    session_path: &str,
+    output: &Path,
+    range: TurnRange,
+) -> Result<SessionDiff, String> {
+    let mut diff = session_diff(session_path, range).await?;
+    let skipped = diff.files.iter().filter(|f| !f.complete).count();
+    diff.files.retain(|f| f.complete);
+    if diff.files.is_empty() && skipped > 0 {
+        return Err(format!(
+            "No applicable changes: original content unknown for {} file(s)",
+            skipped
+        ));
+    }
+    diff.additions = diff.files.iter().map(|f| f.additions).sum();
+    diff.deletions = diff.files.iter().map(|f| f.deletions).sum();
+    let patch: String = diff.files.iter().map(|f| f.diff.as_str()).collect();
+    tokio::fs::write(output, patch)
+        .await
+        .map_err(|e| format!("Failed to write patch: {}", e))?;
+    Ok(diff)
+}
+
+fn build_diff(session_id: String, entries: &[TranscriptEntry], range: TurnRange) -> SessionDiff {
+    let active: HashSet<String> = conversation_tree::build_tree(entries)
+        .active_path
+        .into_iter()
+        .collect();
+
+    // Ramos abandonados (rewind) nao fazem parte do resultado final
+    let kept: Vec<&TranscriptEntry> = entries
+        .iter()
+        .filter(|entry| {
+            entry.message_entry().is_some_and(|e| {
+                e.meta.is_sidechain
+                    || e.meta
+                        .uuid
+                        .as_ref()
+                        .is_none_or(|uuid| active.contains(uuid))
+            })
+        })
+        .collect();
+
+    // tool_results: erros sao ignorados; o toolUseResult traz o conteudo original quando existe
+    let mut failed: HashSet<&str> = HashSet::new();
+    let mut results: HashMap<&str, &Value> = HashMap::new();
+    for message_entry in kept.iter().filter_map(|e| e.message_entry()) {
+        for block in message_entry.message.blocks() {
+            let ContentBlock::ToolResult(result) = block else {
+                continue;
+            };
+            if result.is_error == Some(true) {
+                failed.insert(result.tool_use_id.as_str());
+            } else if let Some(value) = &message_entry.tool_use_result {
+                results.insert(result.tool_use_id.as_str(), value);
+            }
+        }
+    }
+
+    let from = range.from_turn.unwrap_or(0);
+    let to = range.to_turn.unwrap_or(u32::MAX);
+    let mut turn = 0;
+    let mut files: Vec<FileChanges> = Vec::new();
+
+    for entry in kept {
+        if session_parser::prompt_text(entry).is_some() {
+            turn += 1;
+        }
+        if turn < from || turn > to {
+            continue;
+        }
+        let TranscriptEntry::Assistant(assistant) = entry else {
+            continue;
+        };
+        for block in assistant.message.blocks() {
+            let ContentBlock::ToolUse(tool) = block else {
+                continue;
+            };
+            if failed.contains(tool.id.as_str()) {
+                continue;
+            }
+            apply_tool(&mut files, tool, results.get(tool.id.as_str()).copied());
+        }
+    }
+
+    let mut diff = SessionDiff {
+        session_id,
+        turn_count: turn,
+        ..Default::default()
+    };
+    for changes in files {
+        let file = render(changes);
+        if file.additions == 0 && file.deletions == 0 {
+            continue;
+        }
+        diff.additions += file.additions;
+        diff.deletions += file.deletions;
+        diff.files.push(file);
+    }
+    diff
+}
+
+fn apply_tool(files: &mut Vec<FileChanges>, tool: &ToolUseBlock, result: Option<&Value>) {
+    let input = |key: &str| tool.input.get(key).and_then(|v| v.as_str());
+    let edits: Vec<(&str, &str, bool)> = match tool.name.as_str() {
+        "Edit" => vec![edit_of(&tool.input)],
+        "MultiEdit" => tool
+            .input
+            .get("edits")
+            .and_then(|v| v.as_array())
+            .map(|edits| edits.iter().map(edit_of).collect())
+            .unwrap_or_default(),
+        "Write" => Vec::new(),
+        _ => return,
+    };
+    let Some(file_path) = input("file_path") else {
+        return;
+    };
+
+    let original = result.and_then(|r| {
+        r.get("originalFile")
+            .or_else(|| r.get("originalFileContents"))
+            .and_then(|v| v.as_str())
+    });
+    let idx = match files.iter().position(|f| f.file_path == file_path) {
+        Some(idx) => idx,
+        None => {
+            let state = match original {
+                Some(content) => FileState::Full {
+                    original: content.to_string(),
+                    current: content.to_string(),
+                },
+                None => FileState::Fragments(Vec::new()),
+            };
+            files.push(FileChanges {
+                file_path: file_path.to_string(),
+                state,
+                edit_count: 0,
+                created: false,
+                complete: true,
+            });
+            files.len() - 1
+        }
+    };
+    let changes = &mut files[idx];
+    changes.edit_count += 1;
+
+    if tool.name == "Write" {
+        let content = input("content").unwrap_or_default().to_string();
+        // Write de arquivo novo (ou sem o original no resultado) parte do vazio
+        let is_create =
+            result.and_then(|r| r.get("type")).and_then(|v| v.as_str()) == Some("create");
+        match &mut changes.state {
+            FileState::Full { current, .. } => *current = content,
+            FileState::Fragments(fragments) => {
+                if !fragments.is_empty() || (!is_create && original.is_none()) {
+                    changes.complete = false;
+                }
+                changes.created = is_create && fragments.is_empty();
+                changes.state = FileState::Full {
+                    original: String::new(),
+                    current: content,
+                };
+            }
+        }
+        return;
+    }
+
+    for (old, new, replace_all) in edits {
+        match &mut changes.state {
+            FileState::Full { current, .. } => {
+                if !current.contains(old) {
+                    // Arquivo mudou fora da sessao: o trecho nao e mais localizavel
+                    changes.complete = false;
+                } else if replace_all {
+                    *current = current.replace(old, new);
+                } else {
+                    *current = current.replacen(old, new, 1);
+                }
+            }
+            FileState::Fragments(fragments) => {
+                changes.complete = false;
+                match fragments
+                    .iter_mut()
+                    .find(|(_, after)| !old.is_empty() && after.contains(old))
+                {
+                    Some((_, after)) => *after = after.replacen(old, new, 1),
+                    None => fragments.push((old.to_string(), new.to_string())),
+                }
+            }
+        }
+    }
+}
+
+fn edit_of(value: &Value) -> (&str, &str, bool) {
+    let field = |key: &str| value.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+    let replace_all = value
+        .get("replace_all")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    (field("old_string"), field("new_string"), replace_all)
+}
+
+fn render(changes: FileChanges) -> FileDiff {
+    let path = changes.file_path.trim_start_matches('/');
+    let old_header = if changes.created {
+        "/dev/null".to_string()
+    } else {
+        format!("a/{}", path)
+    };
+    let mut diff = format!("--- {}\n+++ b/{}\n", old_header, path);
+    let mut additions = 0;
+    let mut deletions = 0;
+
+    let fragments = matches!(changes.state, FileState::Fragments(_));
+    let pairs: Vec<(String, String)> = match changes.state {
+        FileState::Full { original, current } => vec![(original, current)],
+        FileState::Fragments(fragments) => fragments
+            .into_iter()
+            .map(|(old, new)| (with_newline(old), with_newline(new)))
+            .collect(),
+    };
+    for (old, new) in &pairs {
+        let text_diff = TextDiff::from_lines(old, new);
+        for change in text_diff.iter_all_changes() {
+            match change.tag() {
+                ChangeTag::Insert => additions += 1,
+                ChangeTag::Delete => deletions += 1,
+                ChangeTag::Equal => {}
+            }
+        }
+        let mut unified = text_diff.unified_diff();
+        unified.context_radius(CONTEXT_LINES);
+        for hunk in unified.iter_hunks() {
+            let hunk = hunk.to_string();
+            if fragments {
+                // Posicao do trecho no arquivo e desconhecida: o hunk nao e aplicavel
+                let body = hunk.split_once('\n').map_or("", |(_, body)| body);
+                diff.push_str("@@ fragment, line numbers unknown @@\n");
+                diff.push_str(body);
+            } else {
+                diff.push_str(&hunk);
+            }
+        }
+    }
+
+    FileDiff {
+        file_path: changes.file_path,
+        diff,
+        additions,
+        deletions,
+        edit_count: changes.edit_count,
+        created: changes.created,
+        complete: changes.complete,
+    }
+}
+
+/// Trechos sem quebra final gerariam "\ No newline at end of file" em cada hunk
+fn with_newline(mut text: String) -> String {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
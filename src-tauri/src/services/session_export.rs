@@ -0,0 +1,561 @@
+use crate::models::session::{ExportFormat, ExportOptions};
+use crate::models::transcript::{ContentBlock, MessageContent, TranscriptEntry};
+use crate::services::{conversation_tree, session_archive, session_parser, subagents};
+use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+
+/// Sessao normalizada: base dos tres formatos e o proprio formato do JSON exportado
+#[derive(Serialize)]
+struct ExportedSession {
+    session_id: String,
+    project_path: Option<String>,
+    first_timestamp: Option<String>,
+    last_timestamp: Option<String>,
+    messages: Vec<ExportedMessage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    subagents: Vec<ExportedSubagent>,
+}
+
+#[derive(Serialize)]
+struct ExportedMessage {
+    uuid: Option<String>,
+    parent_uuid: Option<String>,
+    role: String,
+    timestamp: Option<String>,
+    model: Option<String>,
+    is_sidechain: bool,
+    blocks: Vec<ExportedBlock>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ExportedBlock {
+    Text {
+        text: String,
+    },
+    Thinking {
+        text: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        input: Value,
+    },
+    ToolResult {
+        tool_use_id: String,
+        is_error: bool,
+        content: String,
+    },
+    Image {
+        media_type: Option<String>,
+    },
+}
+
+#[derive(Serialize)]
+struct ExportedSubagent {
+    agent_id: String,
+    subagent_type: String,
+    description: Option<String>,
+    tool_use_id: Option<String>,
+    messages: Vec<ExportedMessage>,
+}
+
+/// Exporta uma sessao para `output` no formato das opcoes
+pub async fn export_session(
+    session_path: &str,
+    output: &Path,
+    options: &ExportOptions,
+) -> Result<(), String> {
+    let rendered = render_session(session_path, options).await?;
+    tokio::fs::write(output, rendered)
+        .await
+        .map_err(|e| format!("Failed to write export: {}", e))
+}
+
+/// Exporta varias sessoes para um zip, uma pasta por projeto. Retorna quantas foram exportadas.
+pub async fn export_sessions(
+    paths: &[String],
+    output: &Path,
+    options: &ExportOptions,
+) -> Result<u32, String> {
+    let mut files = Vec::with_capacity(paths.len());
+    let mut names = HashSet::new();
+
+    for path in paths {
+        let rendered = render_session(path, options).await?;
+        let path = Path::new(path);
+        let project = path
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let stem = session_archive::session_id_from_path(path);
+        let name = format!("{}/{}.{}", project, stem, options.format.extension());
+        if names.insert(name.clone()) {
+            files.push((name, rendered));
+        }
+    }
+
+    let output = output.to_path_buf();
+    tokio::task::spawn_blocking(move || write_zip(&output, &files))
+        .await
+        .map_err(|e| format!("Failed to write zip: {}", e))?
+}
+
+fn write_zip(output: &Path, files: &[(String, String)]) -> Result<u32, String> {
+    let file = std::fs::File::create(output).map_err(|e| format!("Failed to create zip: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    for (name, content) in files {
+        zip.start_file(name.as_str(), options)
+            .map_err(|e| format!("Failed to write zip: {}", e))?;
+        zip.write_all(content.as_bytes())
+            .map_err(|e| format!("Failed to write zip: {}", e))?;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to write zip: {}", e))?;
+    Ok(files.len() as u32)
+}
+
+async fn render_session(session_path: &str, options: &ExportOptions) -> Result<String, String> {
+    let path = Path::new(session_path);
+    let entries = session_parser::read_transcript(path).await?;
+
+    let mut session = ExportedSession {
+        session_id: session_archive::session_id_from_path(path),
+        project_path: entries.iter().find_map(|e| e.cwd()).map(String::from),
+        first_timestamp: entries.iter().find_map(|e| e.timestamp()).map(String::from),
+        last_timestamp: entries
+            .iter()
+            .rev()
+            .find_map(|e| e.timestamp())
+            .map(String::from),
+        messages: normalize(&entries, options, true),
+        subagents: Vec::new(),
+    };
+
+    if options.include_sidechains {
+        for run in subagents::read_subagents(session_path).await?.runs {
+            let agent_entries = session_parser::read_transcript(Path::new(&run.file_path)).await?;
+            session.subagents.push(ExportedSubagent {
+                agent_id: run.agent_id,
+                subagent_type: run.subagent_type,
+                description: run.description,
+                tool_use_id: run.tool_use_id,
+                messages: normalize(&agent_entries, options, false),
+            });
+        }
+    }
+
+    match options.format {
+        ExportFormat::Markdown => Ok(to_markdown(&session)),
+        ExportFormat::Html => Ok(to_html(&session)),
+        ExportFormat::Json => serde_json::to_string_pretty(&session)
+            .map_err(|e| format!("Failed to serialize export: {}", e)),
+    }
+}
+
+/// Mensagens do ramo ativo (e sidechains, se pedido), sem entries isMeta.
+/// `main` = false exporta o transcript inteiro (transcript de subagente).
+fn normalize(
+    entries: &[TranscriptEntry],
+    options: &ExportOptions,
+    main: bool,
+) -> Vec<ExportedMessage> {
+    let active: HashSet<String> = if main {
+        conversation_tree::build_tree(entries)
+            .active_path
+            .into_iter()
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let message_entry = entry.message_entry()?;
+            let meta = &message_entry.meta;
+            if meta.is_meta {
+                return None;
+            }
+            if main {
+                let keep = if meta.is_sidechain {
+                    options.include_sidechains
+                } else {
+                    meta.uuid.as_ref().is_none_or(|uuid| active.contains(uuid))
+                };
+                if !keep {
+                    return None;
+                }
+            }
+
+            let blocks = match &message_entry.message.content {
+                MessageContent::Text(text) if text.trim().is_empty() => Vec::new(),
+                MessageContent::Text(text) => vec![ExportedBlock::Text { text: text.clone() }],
+                MessageContent::Blocks(blocks) => blocks
+                    .iter()
+                    .filter_map(|b| normalize_block(b, options))
+                    .collect(),
+            };
+            if blocks.is_empty() {
+                return None;
+            }
+
+            Some(ExportedMessage {
+                uuid: meta.uuid.clone(),
+                parent_uuid: meta.parent_uuid.clone(),
+                role: entry.entry_type().to_string(),
+                timestamp: meta.timestamp.clone(),
+                model: message_entry.message.model.clone(),
+                is_sidechain: meta.is_sidechain,
+                blocks,
+            })
+        })
+        .collect()
+}
+
+fn normalize_block(block: &ContentBlock, options: &ExportOptions) -> Option<ExportedBlock> {
+    match block {
+        ContentBlock::Text(t) if !t.text.trim().is_empty() => Some(ExportedBlock::Text {
+            text: t.text.clone(),
+        }),
+        ContentBlock::Thinking(t) if options.include_thinking && !t.thinking.trim().is_empty() => {
+            Some(ExportedBlock::Thinking {
+                text: t.thinking.clone(),
+            })
+        }
+        ContentBlock::ToolUse(tool) | ContentBlock::ServerToolUse(tool) => {
+            Some(ExportedBlock::ToolUse {
+                id: tool.id.clone(),
+                name: tool.name.clone(),
+                input: tool.input.clone(),
+            })
+        }
+        ContentBlock::ToolResult(result) if options.include_tool_results => {
+            Some(ExportedBlock::ToolResult {
+                tool_use_id: result.tool_use_id.clone(),
+                is_error: result.is_error.unwrap_or(false),
+                content: result.text(),
+            })
+        }
+        ContentBlock::Image(image) => Some(ExportedBlock::Image {
+            media_type: image
+                .source
+                .get("media_type")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        }),
+        _ => None,
+    }
+}
+
+// --- Markdown ---
+
+fn to_markdown(session: &ExportedSession) -> String {
+    let mut out = format!("# Session {}\n\n", session.session_id);
+    if let Some(project) = &session.project_path {
+        out.push_str(&format!("- **Project:** `{}`\n", project));
+    }
+    if let (Some(first), Some(last)) = (&session.first_timestamp, &session.last_timestamp) {
+        out.push_str(&format!(
+            "- **Started:** {}\n- **Ended:** {}\n",
+            first, last
+        ));
+    }
+    out.push_str("\n---\n\n");
+
+    for message in &session.messages {
+        markdown_message(&mut out, message, "##");
+    }
+
+    if !session.subagents.is_empty() {
+        out.push_str("# Subagents\n\n");
+        for agent in &session.subagents {
+            out.push_str(&format!("## {}", agent.subagent_type));
+            if let Some(description) = &agent.description {
+                out.push_str(&format!(": {}", description));
+            }
+            out.push_str(&format!("\n\n_Agent `{}`_\n\n", agent.agent_id));
+            for message in &agent.messages {
+                markdown_message(&mut out, message, "###");
+            }
+        }
+    }
+
+    out
+}
+
+fn markdown_message(out: &mut String, message: &ExportedMessage, heading: &str) {
+    out.push_str(&format!("{} {}", heading, role_label(message)));
+    if let Some(timestamp) = &message.timestamp {
+        out.push_str(&format!(" · {}", timestamp));
+    }
+    out.push_str("\n\n");
+
+    for block in &message.blocks {
+        match block {
+            ExportedBlock::Text { text } => {
+                out.push_str(text.trim_end());
+                out.push_str("\n\n");
+            }
+            ExportedBlock::Thinking { text } => {
+                out.push_str("<details><summary>Thinking</summary>\n\n");
+                out.push_str(text.trim_end());
+                out.push_str("\n\n</details>\n\n");
+            }
+            ExportedBlock::ToolUse { name, input, .. } => {
+                let input = serde_json::to_string_pretty(input).unwrap_or_default();
+                out.push_str(&format!(
+                    "**Tool: {}**\n\n{}\n",
+                    name,
+                    fence(&input, "json")
+                ));
+            }
+            ExportedBlock::ToolResult {
+                is_error, content, ..
+            } => {
+                let label = if *is_error {
+                    "Result (error)"
+                } else {
+                    "Result"
+                };
+                out.push_str(&format!(
+                    "<details><summary>{}</summary>\n\n{}\n</details>\n\n",
+                    label,
+                    fence(content, "")
+                ));
+            }
+            ExportedBlock::Image { media_type } => {
+                out.push_str(&format!(
+                    "_[image{}]_\n\n",
+                    media_type
+                        .as_deref()
+                        .map(|m| format!(": {}", m))
+                        .unwrap_or_default()
+                ));
+            }
+        }
+    }
+}
+
+/// Bloco de codigo com cerca maior que qualquer sequencia de crases do conteudo
+fn fence(content: &str, lang: &str) -> String {
+    let longest = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let ticks = "`".repeat(longest.max(2) + 1);
+    format!("{}{}\n{}\n{}\n", ticks, lang, content.trim_end(), ticks)
+}
+
+fn role_label(message: &ExportedMessage) -> String {
+    let role = if message.role == "assistant" {
+        "Assistant"
+    } else {
+        "User"
+    };
+    match (&message.model, message.is_sidechain) {
+        (Some(model), _) if message.role == "assistant" => format!("{} ({})", role, model),
+        (_, true) => format!("{} (sidechain)", role),
+        _ => role.to_string(),
+    }
+}
+
+// --- HTML ---
+
+const HTML_STYLE: &str = r#"
+body { margin: 0 auto; max-width: 960px; padding: 32px 24px; background: #0f1115; color: #d6d9e0; font: 14px/1.6 -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; }
+h1 { font-size: 22px; margin: 0 0 8px; }
+.meta { color: #8a8f9c; font-size: 12px; margin-bottom: 24px; }
+.msg { border: 1px solid #262a33; border-radius: 8px; padding: 12px 16px; margin: 12px 0; background: #151820; }
+.msg.user { border-left: 3px solid #10b981; }
+.msg.assistant { border-left: 3px solid #6366f1; }
+.msg.sidechain { opacity: 0.75; }
+.role { font-size: 12px; font-weight: 600; color: #8a8f9c; margin-bottom: 6px; }
+details { margin: 8px 0; border: 1px solid #262a33; border-radius: 6px; background: #11141a; }
+summary { cursor: pointer; padding: 6px 10px; font-size: 12px; font-family: ui-monospace, monospace; color: #a5b4fc; }
+details.result summary { color: #8a8f9c; }
+details.error summary { color: #f87171; }
+details.thinking summary { color: #fbbf24; }
+details > :not(summary) { margin: 0 10px 10px; }
+pre { overflow-x: auto; padding: 10px; border-radius: 6px; background: #0b0d11; font-size: 12px; }
+code { font-family: ui-monospace, SFMono-Regular, Menlo, monospace; }
+.tk-s { color: #a5d6a7; } .tk-n { color: #f9a825; } .tk-k { color: #c792ea; } .tk-c { color: #6b7280; font-style: italic; }
+h2 { font-size: 18px; margin-top: 32px; }
+"#;
+
+/// Realce de sintaxe generico (strings, numeros, comentarios, palavras-chave), sem dependencias externas
+const HTML_SCRIPT: &str = r#"
+(function () {
+  var re = /(\/\/[^\n]*|\/\*[\s\S]*?\*\/)|("(?:\\.|[^"\\\n])*"|'(?:\\.|[^'\\\n])*')|\b(\d+(?:\.\d+)?)\b|\b(fn|let|const|var|function|return|if|else|for|while|match|impl|struct|enum|pub|use|import|from|export|class|def|async|await|true|false|null|None|self|type|interface|mut|new)\b/g;
+  var esc = function (s) { return s.replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;"); };
+  document.querySelectorAll("pre code").forEach(function (el) {
+    var src = el.textContent, out = "", last = 0, m;
+    if (src.length > 200000) return;
+    while ((m = re.exec(src))) {
+      out += esc(src.slice(last, m.index));
+      var cls = m[1] ? "c" : m[2] ? "s" : m[3] ? "n" : "k";
+      out += '<span class="tk-' + cls + '">' + esc(m[0]) + "</span>";
+      last = re.lastIndex;
+    }
+    el.innerHTML = out + esc(src.slice(last));
+  });
+})();
+"#;
+
+fn to_html(session: &ExportedSession) -> String {
+    let mut body = format!(
+        "<h1>Session {}</h1>\n<div class=\"meta\">",
+        escape(&session.session_id)
+    );
+    if let Some(project) = &session.project_path {
+        body.push_str(&format!("<code>{}</code>", escape(project)));
+    }
+    if let (Some(first), Some(last)) = (&session.first_timestamp, &session.last_timestamp) {
+        body.push_str(&format!(" · {} → {}", escape(first), escape(last)));
+    }
+    body.push_str("</div>\n");
+
+    for message in &session.messages {
+        html_message(&mut body, message);
+    }
+
+    if !session.subagents.is_empty() {
+        body.push_str("<h2>Subagents</h2>\n");
+        for agent in &session.subagents {
+            let title = match &agent.description {
+                Some(description) => format!("{}: {}", agent.subagent_type, description),
+                None => agent.subagent_type.clone(),
+            };
+            body.push_str(&format!("<details><summary>{}</summary>\n", escape(&title)));
+            for message in &agent.messages {
+                html_message(&mut body, message);
+            }
+            body.push_str("</details>\n");
+        }
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Session {}</title>\n<style>{}</style>\n</head>\n<body>\n{}<script>{}</script>\n</body>\n</html>\n",
+        escape(&session.session_id),
+        HTML_STYLE,
+        body,
+        HTML_SCRIPT
+    )
+}
+
+fn html_message(out: &mut String, message: &ExportedMessage) {
+    let sidechain = if message.is_sidechain {
+        " sidechain"
+    } else {
+        ""
+    };
+    out.push_str(&format!(
+        "<div class=\"msg {}{}\">\n<div class=\"role\">{}",
+        escape(&message.role),
+        sidechain,
+        escape(&role_label(message))
+    ));
+    if let Some(timestamp) = &message.timestamp {
+        out.push_str(&format!(" · {}", escape(timestamp)));
+    }
+    out.push_str("</div>\n");
+
+    for block in &message.blocks {
+        match block {
+            ExportedBlock::Text { text } => out.push_str(&markdown_to_html(text)),
+            ExportedBlock::Thinking { text } => {
+                out.push_str(&format!(
+                    "<details class=\"thinking\"><summary>Thinking</summary>{}</details>\n",
+                    markdown_to_html(text)
+                ));
+            }
+            ExportedBlock::ToolUse { name, input, .. } => {
+                let input = serde_json::to_string_pretty(input).unwrap_or_default();
+                out.push_str(&format!(
+                    "<details class=\"tool\"><summary>{}</summary><pre><code class=\"language-json\">{}</code></pre></details>\n",
+                    escape(name),
+                    escape(&input)
+                ));
+            }
+            ExportedBlock::ToolResult {
+                is_error, content, ..
+            } => {
+                let (class, label) = if *is_error {
+                    ("result error", "Result (error)")
+                } else {
+                    ("result", "Result")
+                };
+                out.push_str(&format!(
+                    "<details class=\"{}\"><summary>{}</summary><pre><code>{}</code></pre></details>\n",
+                    class,
+                    label,
+                    escape(content)
+                ));
+            }
+            ExportedBlock::Image { media_type } => {
+                out.push_str(&format!(
+                    "<p><em>[image{}]</em></p>\n",
+                    escape(
+                        &media_type
+                            .as_deref()
+                            .map(|m| format!(": {}", m))
+                            .unwrap_or_default()
+                    )
+                ));
+            }
+        }
+    }
+
+    out.push_str("</div>\n");
+}
+
+/// Markdown para HTML; HTML cru do transcript vira texto escapado.
+/// Links e imagens com URL fora de `safe_url` perdem a tag e ficam so com o texto.
+fn markdown_to_html(text: &str) -> String {
+    // Para cada link/imagem aberto: se a tag foi mantida
+    let mut kept: Vec<bool> = Vec::new();
+    let parser = Parser::new_ext(text, Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH)
+        .filter_map(|event| match event {
+            Event::Html(raw) | Event::InlineHtml(raw) => Some(Event::Text(raw)),
+            Event::Start(Tag::Link { ref dest_url, .. } | Tag::Image { ref dest_url, .. }) => {
+                let safe = safe_url(dest_url);
+                kept.push(safe);
+                safe.then_some(event)
+            }
+            Event::End(TagEnd::Link | TagEnd::Image) => {
+                kept.pop().unwrap_or(true).then_some(event)
+            }
+            other => Some(other),
+        });
+    let mut out = String::new();
+    html::push_html(&mut out, parser);
+    out
+}
+
+/// URL relativa ou com esquema http, https ou mailto (bloqueia javascript:, data:, vbscript:...)
+fn safe_url(url: &str) -> bool {
+    // Navegadores ignoram espacos e controles dentro do esquema ("java\tscript:")
+    let cleaned: String = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+        .collect();
+    match cleaned.split_once(':') {
+        Some((scheme, _)) if !scheme.contains(['/', '?', '#']) => {
+            matches!(scheme.to_ascii_lowercase().as_str(), "http" | "https" | "mailto")
+        }
+        _ => true,
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,134 @@
+use crate::models::session::ForkedSession;
+use crate::services::{session_archive, session_parser};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Cria uma sessao nova com a conversa ate o entry `uuid` (inclusive).
+/// So entra o caminho da raiz ate esse entry (ramos abandonados e sidechains ficam de fora);
+/// uuids sao regerados para a copia nao colidir com o original, que nao e alterado.
+pub async fn fork_session(
+    claude_dir: &str,
+    session_path: &str,
+    uuid: &str,
+    cache_dir: &Path,
+    data_dir: &Path,
+) -> Result<ForkedSession, String> {
+    let source = PathBuf::from(session_path);
+    let content = session_archive::read_to_string(&source)
+        .await
+        .map_err(|e| format!("Failed to read session: {}", e))?;
+
+    let entries: Vec<Value> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let keep = ancestors(&entries, uuid)?;
+
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let source_session_id = session_archive::session_id_from_path(&source);
+
+    // Novo uuid para cada entry mantido; referencias fora da copia viram null
+    let new_uuids: HashMap<&str, String> = keep
+        .iter()
+        .filter_map(|&idx| entries[idx]["uuid"].as_str())
+        .map(|old| (old, uuid::Uuid::new_v4().to_string()))
+        .collect();
+    let remap = |value: &Value| -> Value {
+        value
+            .as_str()
+            .and_then(|old| new_uuids.get(old))
+            .map_or(Value::Null, |new| Value::String(new.clone()))
+    };
+
+    let mut lines = Vec::with_capacity(keep.len());
+    for &idx in &keep {
+        let mut entry = entries[idx].clone();
+        let Some(object) = entry.as_object_mut() else {
+            continue;
+        };
+        for key in [
+            "uuid",
+            "parentUuid",
+            "logicalParentUuid",
+            "sourceToolAssistantUUID",
+        ] {
+            if let Some(value) = object.get_mut(key) {
+                *value = remap(value);
+            }
+        }
+        object.insert("sessionId".to_string(), Value::String(session_id.clone()));
+        lines.push(
+            serde_json::to_string(&entry)
+                .map_err(|e| format!("Failed to serialize entry: {}", e))?,
+        );
+    }
+
+    let target = source.with_file_name(format!("{}.jsonl", session_id));
+    let entry_count = lines.len() as u32;
+    let write_target = target.clone();
+    tokio::task::spawn_blocking(move || write_new(&write_target, &lines))
+        .await
+        .map_err(|e| format!("Fork task failed: {}", e))??;
+
+    // Listagem e indice ja incluem a sessao nova; o fork ja foi gravado, entao so registra a falha
+    if let Err(e) = session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await {
+        eprintln!("[Rex] Failed to refresh sessions after fork: {}", e);
+    }
+
+    Ok(ForkedSession {
+        session_id,
+        source_session_id,
+        file_path: target.to_string_lossy().to_string(),
+        entries: entry_count,
+    })
+}
+
+/// Indices (em ordem de arquivo) do entry escolhido e de todos os seus ancestrais.
+/// Segue `parentUuid` e, em compactacoes, `logicalParentUuid`, como a arvore da conversa.
+fn ancestors(entries: &[Value], uuid: &str) -> Result<Vec<usize>, String> {
+    let mut by_uuid: HashMap<&str, usize> = HashMap::new();
+    for (idx, entry) in entries.iter().enumerate() {
+        if let Some(id) = entry["uuid"].as_str() {
+            // uuid repetido (historico copiado num resume): vale a primeira ocorrencia
+            by_uuid.entry(id).or_insert(idx);
+        }
+    }
+
+    let mut current = by_uuid.get(uuid).copied();
+    if current.is_none() {
+        return Err(format!("Entry {} not found in session", uuid));
+    }
+
+    let mut keep: HashSet<usize> = HashSet::new();
+    while let Some(idx) = current {
+        if !keep.insert(idx) {
+            break;
+        }
+        let entry = &entries[idx];
+        current = entry["parentUuid"]
+            .as_str()
+            .or(entry["logicalParentUuid"].as_str())
+            .and_then(|parent| by_uuid.get(parent).copied());
+    }
+
+    let mut keep: Vec<usize> = keep.into_iter().collect();
+    keep.sort_unstable();
+    Ok(keep)
+}
+
+fn write_new(target: &Path, lines: &[String]) -> Result<(), String> {
+    let tmp = target.with_extension("tmp");
+    let written = std::fs::File::create(&tmp).and_then(|mut file| {
+        for line in lines {
+            writeln!(file, "{}", line)?;
+        }
+        file.sync_all()
+    });
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(format!("Failed to write forked session: {}", e));
+    }
+    std::fs::rename(&tmp, target).map_err(|e| format!("Failed to write forked session: {}", e))
+}
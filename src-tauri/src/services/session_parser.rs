@@ -1,51 +1,99 @@
-use crate::models::session::{SearchMatch, SessionEntry, SessionMeta};
-use std::path::Path;
-
-/// Decodifica o nome da pasta do projeto para o path original
-fn decode_project_path(encoded: &str) -> String {
-    encoded.replace("-", "/")
+use crate::models::session::{
+    SearchHit, SearchMatch, SearchPage, SessionListOptions, SessionMeta, SessionSortKey,
+};
+use crate::models::transcript::TranscriptEntry;
+use crate::services::project_resolver::{self, ProjectResolver};
+use crate::services::search_index::{self, IndexHit};
+use crate::services::search_query::{SearchQuery, SearchScope};
+use crate::services::session_archive;
+use crate::services::session_cache::{self, CachedSession, SessionCache};
+use crate::services::subagents;
+use futures::StreamExt;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Numero de arquivos de sessao processados em paralelo na listagem
+const LIST_PARALLELISM: usize = 8;
+
+/// Tamanho maximo de titulos e resumos na listagem
+const TITLE_CHARS: usize = 100;
+
+/// Marcacao injetada pelo Claude Code em mensagens do usuario (slash commands, hooks, shell `!`)
+static PROMPT_NOISE: LazyLock<Regex> = LazyLock::new(|| {
+    let tags = [
+        "system-reminder",
+        "command-name",
+        "command-message",
+        "command-args",
+        "command-contents",
+        "local-command-stdout",
+        "local-command-stderr",
+        "bash-input",
+        "bash-stdout",
+        "bash-stderr",
+        "user-prompt-submit-hook",
+    ]
+    .join("|");
+    Regex::new(&format!(r"(?s)<({0})>.*?</({0})>", tags)).expect("valid prompt noise regex")
+});
+
+/// Resumo de um unico entry (primeiro texto de uma mensagem do assistente)
+pub fn entry_summary(entry: &TranscriptEntry) -> Option<String> {
+    let TranscriptEntry::Assistant(assistant) = entry else {
+        return None;
+    };
+    assistant.message.first_text().map(truncate_title)
 }
 
-/// Extrai resumo da ultima mensagem do assistente
-fn extract_summary(entries: &[SessionEntry]) -> String {
-    for entry in entries.iter().rev() {
-        if entry.entry_type == "assistant" {
-            if let Some(content) = entry.message.get("content") {
-                if let Some(arr) = content.as_array() {
-                    for item in arr {
-                        if let Some(text) = item.get("text").and_then(|t| t.as_str()) {
-                            let trimmed = text.chars().take(100).collect::<String>();
-                            return if text.len() > 100 {
-                                format!("{}...", trimmed)
-                            } else {
-                                trimmed
-                            };
-                        }
-                    }
-                } else if let Some(text) = content.as_str() {
-                    let trimmed = text.chars().take(100).collect::<String>();
-                    return if text.len() > 100 {
-                        format!("{}...", trimmed)
-                    } else {
-                        trimmed
-                    };
-                }
-            }
-        }
+/// Texto de um prompt real do usuario, sem a marcacao injetada pelo Claude Code.
+/// None para entries isMeta, sidechains, tool_results, slash commands sem texto e avisos de caveat/interrupcao.
+pub fn prompt_text(entry: &TranscriptEntry) -> Option<String> {
+    let TranscriptEntry::User(user) = entry else {
+        return None;
+    };
+    if user.meta.is_meta || user.meta.is_sidechain {
+        return None;
     }
-    String::from("No summary available")
+
+    // Mensagens so com tool_result tem texto vazio
+    let text = user.message.text();
+    let cleaned = PROMPT_NOISE.replace_all(&text, " ");
+    let cleaned = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if cleaned.is_empty()
+        || cleaned.starts_with("Caveat:")
+        || cleaned.starts_with("[Request interrupted")
+    {
+        return None;
+    }
+    Some(truncate_title(&cleaned))
 }
 
-/// Lista todas as sessoes encontradas no diretorio .claude
-pub async fn list_all_sessions(claude_dir: &str) -> Result<Vec<SessionMeta>, String> {
-    let projects_dir = Path::new(claude_dir).join("projects");
-    if !projects_dir.exists() {
-        return Ok(Vec::new());
+fn truncate_title(text: &str) -> String {
+    let trimmed = text.chars().take(TITLE_CHARS).collect::<String>();
+    if text.chars().count() > TITLE_CHARS {
+        format!("{}...", trimmed)
+    } else {
+        trimmed
     }
+}
+
+/// Arquivo de sessao (.jsonl, .jsonl.zst ou .jsonl.gz) encontrado em projects/<slug>/
+struct SessionFile {
+    project_name: String,
+    id: String,
+    path: String,
+    size: u64,
+    mtime_ms: u64,
+}
 
-    let mut sessions = Vec::new();
+/// Lista os arquivos de sessao com tamanho e mtime (sem ler o conteudo)
+async fn list_session_files(projects_dir: &Path) -> Result<Vec<SessionFile>, String> {
+    let mut files = Vec::new();
     let mut project_entries =
-        tokio::fs::read_dir(&projects_dir)
+        tokio::fs::read_dir(projects_dir)
             .await
             .map_err(|e| format!("Failed to read projects dir: {}", e))?;
 
@@ -63,7 +111,6 @@ pub async fn list_all_sessions(claude_dir: &str) -> Result<Vec<SessionMeta>, Str
             .file_name()
             .to_string_lossy()
             .to_string();
-        let decoded_path = decode_project_path(&project_name);
 
         let mut session_entries =
             tokio::fs::read_dir(&project_path)
@@ -75,215 +122,370 @@ pub async fn list_all_sessions(claude_dir: &str) -> Result<Vec<SessionMeta>, Str
             .await
             .map_err(|e| format!("Failed to read session entry: {}", e))?
         {
-            let session_path = session_entry.path();
             let filename = session_entry.file_name().to_string_lossy().to_string();
-
-            if !filename.ends_with(".jsonl") {
+            // Transcripts de subagentes aparecem agrupados na sessao pai
+            if !session_archive::is_transcript(&filename) || subagents::is_agent_file(&filename) {
                 continue;
             }
 
-            let session_id = filename.trim_end_matches(".jsonl").to_string();
-
-            match parse_session_file(session_path.to_str().unwrap_or("")).await {
-                Ok(entries) => {
-                    let last_timestamp = entries
-                        .last()
-                        .map(|e| e.timestamp.clone())
-                        .unwrap_or_default();
-                    let summary = extract_summary(&entries);
-                    let message_count = entries.len() as u32;
-
-                    // Versao simplificada do display
-                    let project_display = if decoded_path.starts_with('/') {
-                        decoded_path
-                            .split('/')
-                            .rev()
-                            .take(2)
-                            .collect::<Vec<_>>()
-                            .into_iter()
-                            .rev()
-                            .collect::<Vec<_>>()
-                            .join("/")
-                    } else {
-                        decoded_path.clone()
-                    };
-
-                    sessions.push(SessionMeta {
-                        id: session_id,
-                        project_path: decoded_path.clone(),
-                        project_display,
-                        summary,
-                        last_timestamp,
-                        message_count,
-                    });
-                }
-                Err(_) => continue,
-            }
+            let Ok(metadata) = session_entry.metadata().await else {
+                continue;
+            };
+            let mtime_ms = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0);
+
+            files.push(SessionFile {
+                project_name: project_name.clone(),
+                id: session_archive::session_id(&filename).to_string(),
+                path: session_entry.path().to_string_lossy().to_string(),
+                size: metadata.len(),
+                mtime_ms,
+            });
         }
     }
 
-    // Ordena por timestamp mais recente
-    sessions.sort_by(|a, b| b.last_timestamp.cmp(&a.last_timestamp));
-    Ok(sessions)
+    Ok(files)
 }
 
-/// Busca texto dentro das conversas de todas as sessoes
-pub async fn search_in_sessions(
-    claude_dir: &str,
-    query: &str,
-) -> Result<Vec<SearchMatch>, String> {
+/// Lista todas as sessoes encontradas no diretorio .claude.
+/// Usa cache persistente em `cache_dir`: so arquivos novos ou alterados sao relidos.
+pub async fn list_all_sessions(claude_dir: &str, cache_dir: &Path) -> Result<Vec<SessionMeta>, String> {
     let projects_dir = Path::new(claude_dir).join("projects");
     if !projects_dir.exists() {
         return Ok(Vec::new());
     }
 
-    let query_lower = query.to_lowercase();
-    let mut results = Vec::new();
-
-    let mut project_entries = tokio::fs::read_dir(&projects_dir)
-        .await
-        .map_err(|e| format!("Failed to read projects dir: {}", e))?;
-
-    while let Some(project_entry) = project_entries
-        .next_entry()
-        .await
-        .map_err(|e| format!("Failed to read entry: {}", e))?
-    {
-        let project_path = project_entry.path();
-        if !project_path.is_dir() {
+    let resolver = ProjectResolver::load(Path::new(claude_dir)).await;
+    let files = list_session_files(&projects_dir).await?;
+    let mut cache = SessionCache::load(cache_dir).await;
+
+    // Subagentes por sessao, uma leitura por pasta de projeto
+    let project_names: HashSet<String> = files.iter().map(|f| f.project_name.clone()).collect();
+    let dir = projects_dir.clone();
+    let subagent_counts: HashMap<(String, String), u32> = tokio::task::spawn_blocking(move || {
+        project_names
+            .into_iter()
+            .flat_map(|project| {
+                subagents::files_by_session(&dir.join(&project))
+                    .into_iter()
+                    .map(move |(id, files)| ((project.clone(), id), files.len() as u32))
+            })
+            .collect()
+    })
+    .await
+    .unwrap_or_default();
+
+    // Reparse apenas do que mudou, com paralelismo limitado
+    let jobs = files.iter().enumerate().map(|(idx, file)| {
+        let previous = cache.get(&file.path).cloned();
+        async move {
+            if let Some(prev) = &previous {
+                if prev.size == file.size && prev.mtime_ms == file.mtime_ms {
+                    return (idx, previous);
+                }
+            }
+            let refreshed = session_cache::refresh_entry(
+                PathBuf::from(&file.path),
+                file.size,
+                file.mtime_ms,
+                previous,
+            )
+            .await;
+            (idx, refreshed)
+        }
+    });
+    let scanned: Vec<(usize, Option<CachedSession>)> = futures::stream::iter(jobs)
+        .buffer_unordered(LIST_PARALLELISM)
+        .collect()
+        .await;
+
+    // cwds por pasta para resolver o path canonico do projeto
+    let mut cwds_by_project: HashMap<&str, Vec<String>> = HashMap::new();
+    let mut present = HashSet::new();
+    let mut cached_files = Vec::new();
+
+    for (idx, cached) in scanned {
+        let Some(cached) = cached else {
             continue;
+        };
+        let file = &files[idx];
+        present.insert(file.path.clone());
+        if let Some(cwd) = &cached.scan.cwd {
+            cwds_by_project
+                .entry(file.project_name.as_str())
+                .or_default()
+                .push(cwd.clone());
         }
+        cache.insert(file.path.clone(), cached.clone());
+        cached_files.push((file, cached));
+    }
 
-        let project_name = project_entry.file_name().to_string_lossy().to_string();
-        let decoded_path = decode_project_path(&project_name);
+    cache.retain_paths(&present);
+    if let Err(e) = cache.save(cache_dir).await {
+        eprintln!("[Rex] {}", e);
+    }
 
-        let mut session_entries = tokio::fs::read_dir(&project_path)
-            .await
-            .map_err(|e| format!("Failed to read session dir: {}", e))?;
+    let mut resolved: HashMap<&str, (String, String)> = HashMap::new();
+    let mut sessions = Vec::with_capacity(cached_files.len());
+
+    for (file, cached) in cached_files {
+        let (project_path, project_display) = resolved
+            .entry(file.project_name.as_str())
+            .or_insert_with(|| {
+                let empty = Vec::new();
+                let cwds = cwds_by_project.get(file.project_name.as_str()).unwrap_or(&empty);
+                let path = resolver.resolve(&file.project_name, cwds);
+                let display = project_resolver::display_name(&path);
+                (path, display)
+            })
+            .clone();
+
+        // Titulo: resumo do Claude Code > primeiro prompt > ultima resposta do assistente
+        let scan = cached.scan;
+        let summary = scan
+            .latest_summary
+            .clone()
+            .or_else(|| scan.first_prompt.clone())
+            .or(scan.summary)
+            .unwrap_or_else(|| String::from("No summary available"));
+
+        sessions.push(SessionMeta {
+            id: file.id.clone(),
+            project_path,
+            project_display,
+            file_path: file.path.clone(),
+            summary,
+            first_prompt: scan.first_prompt,
+            latest_summary: scan.latest_summary,
+            duration_ms: duration_ms(scan.first_timestamp.as_deref(), &cached.last_timestamp),
+            first_timestamp: scan.first_timestamp.unwrap_or_default(),
+            last_timestamp: cached.last_timestamp,
+            message_count: scan.message_count,
+            subagent_count: subagent_counts
+                .get(&(file.project_name.clone(), file.id.clone()))
+                .copied()
+                .unwrap_or(0),
+            tokens: scan.usage.totals,
+            models: scan.models,
+            model_switches: scan.model_switches,
+            archived: session_archive::is_compressed(&file.path),
+        });
+    }
 
-        while let Some(session_entry) = session_entries
-            .next_entry()
-            .await
-            .map_err(|e| format!("Failed to read session entry: {}", e))?
-        {
-            let session_path = session_entry.path();
-            let filename = session_entry.file_name().to_string_lossy().to_string();
-            if !filename.ends_with(".jsonl") {
-                continue;
-            }
+    // Ordena por timestamp mais recente
+    sessions.sort_by(|a, b| b.last_timestamp.cmp(&a.last_timestamp));
+    Ok(sessions)
+}
 
-            // Leitura raw para check rapido antes de parsear
-            let raw = match tokio::fs::read_to_string(&session_path).await {
-                Ok(c) => c,
-                Err(_) => continue,
-            };
+/// Filtra e ordena a listagem conforme as opcoes de list_sessions
+pub fn apply_list_options(mut sessions: Vec<SessionMeta>, options: &SessionListOptions) -> Vec<SessionMeta> {
+    let model = options.model.as_ref().map(|m| m.to_lowercase());
+    let project = options.project.as_ref().map(|p| p.to_lowercase());
+    // Timestamps ISO 8601: o prefixo YYYY-MM-DD compara como data
+    let day = |s: &SessionMeta| s.last_timestamp.get(..10).unwrap_or("").to_string();
+
+    sessions.retain(|s| {
+        model
+            .as_ref()
+            .is_none_or(|m| s.models.iter().any(|sm| sm.to_lowercase().contains(m)))
+            && project
+                .as_ref()
+                .is_none_or(|p| s.project_path.to_lowercase().contains(p))
+            && options.min_cost_usd.is_none_or(|min| s.tokens.cost_usd >= min)
+            && options.min_tokens.is_none_or(|min| s.tokens.total() >= min)
+            && options.since.as_ref().is_none_or(|since| day(s) >= *since)
+            && options.until.as_ref().is_none_or(|until| day(s) <= *until)
+    });
+
+    sessions.sort_by(|a, b| {
+        let ordering = match options.sort_by {
+            SessionSortKey::LastActive => a.last_timestamp.cmp(&b.last_timestamp),
+            SessionSortKey::Started => a.first_timestamp.cmp(&b.first_timestamp),
+            SessionSortKey::Duration => a.duration_ms.cmp(&b.duration_ms),
+            SessionSortKey::Cost => a.tokens.cost_usd.total_cmp(&b.tokens.cost_usd),
+            SessionSortKey::Tokens => a.tokens.total().cmp(&b.tokens.total()),
+            SessionSortKey::Messages => a.message_count.cmp(&b.message_count),
+        };
+        if options.ascending {
+            ordering
+        } else {
+            ordering.reverse()
+        }
+    });
+    sessions
+}
 
-            if !raw.to_lowercase().contains(&query_lower) {
-                continue;
-            }
+fn duration_ms(first: Option<&str>, last: &str) -> u64 {
+    let parse = |ts: &str| chrono::DateTime::parse_from_rfc3339(ts).ok();
+    match (first.and_then(parse), parse(last)) {
+        (Some(start), Some(end)) => (end - start).num_milliseconds().max(0) as u64,
+        _ => 0,
+    }
+}
 
-            // Parseia e busca nos entries
-            let entries: Vec<SessionEntry> = raw
-                .lines()
-                .filter(|l| !l.trim().is_empty())
-                .filter_map(|l| serde_json::from_str(l).ok())
-                .collect();
+/// Lista as sessoes e sincroniza o indice persistente (busca e arquivos tocados) com os arquivos atuais.
+/// Transcripts de subagente ficam fora da listagem, mas entram no indice ligados a sessao pai.
+pub async fn sync_sessions(
+    claude_dir: &str,
+    cache_dir: &Path,
+    data_dir: &Path,
+) -> Result<Vec<SessionMeta>, String> {
+    // Listagem (via cache) fornece os arquivos atuais e os metadados de cada sessao
+    let sessions = list_all_sessions(claude_dir, cache_dir).await?;
+    let session_paths: Vec<String> = sessions.iter().map(|s| s.file_path.clone()).collect();
+    let mut files = subagents::files_with_parent(session_paths.clone()).await?;
+    files.extend(session_paths.into_iter().map(|p| (p.clone(), p)));
+    search_index::sync_index(data_dir, files).await?;
+    Ok(sessions)
+}
 
-            let mut match_count: u32 = 0;
-            let mut first_match_text = String::new();
-            let mut first_match_type = String::new();
+/// Arquivos das sessoes cujo projeto contem `project` (sem diferenciar maiusculas); None = todas
+pub fn session_paths_for_project(sessions: &[SessionMeta], project: Option<&str>) -> Option<Vec<String>> {
+    let project = project?.to_lowercase();
+    Some(
+        sessions
+            .iter()
+            .filter(|s| s.project_path.to_lowercase().contains(&project))
+            .map(|s| s.file_path.clone())
+            .collect(),
+    )
+}
 
-            for entry in &entries {
-                if entry.entry_type != "user" && entry.entry_type != "assistant" {
-                    continue;
-                }
-                let text = extract_entry_text(&entry.message);
-                let text_lower = text.to_lowercase();
-                let occurrences = text_lower.matches(&query_lower).count() as u32;
-                if occurrences > 0 {
-                    match_count += occurrences;
-                    if first_match_text.is_empty() {
-                        first_match_text = extract_context(&text, &query_lower, 100);
-                        first_match_type = entry.entry_type.clone();
-                    }
-                }
-            }
+/// Sessoes por pagina de busca quando o frontend nao informa
+const DEFAULT_SEARCH_PAGE_SIZE: usize = 20;
 
-            if match_count > 0 {
-                let session_id = filename.trim_end_matches(".jsonl").to_string();
-                let last_timestamp = entries
-                    .last()
-                    .map(|e| e.timestamp.clone())
-                    .unwrap_or_default();
-                let summary = extract_summary(&entries);
-                let message_count = entries.len() as u32;
-                let project_display = if decoded_path.starts_with('/') {
-                    decoded_path
-                        .split('/')
-                        .rev()
-                        .take(2)
-                        .collect::<Vec<_>>()
-                        .into_iter()
-                        .rev()
-                        .collect::<Vec<_>>()
-                        .join("/")
-                } else {
-                    decoded_path.clone()
-                };
-
-                results.push(SearchMatch {
-                    session: SessionMeta {
-                        id: session_id,
-                        project_path: decoded_path.clone(),
-                        project_display,
-                        summary,
-                        last_timestamp,
-                        message_count,
-                    },
-                    matched_text: first_match_text,
-                    entry_type: first_match_type,
-                    match_count,
-                });
-            }
+/// Busca nas conversas de todas as sessoes usando o indice FTS persistente.
+/// `query` aceita a sintaxe estruturada de `SearchQuery` (filtros, frases, negacao, regex, escopos).
+/// Sessoes sao ordenadas por relevancia e paginadas por `cursor` (opaco, vindo de `next_cursor`).
+pub async fn search_in_sessions(
+    claude_dir: &str,
+    query: &str,
+    cursor: Option<&str>,
+    limit: Option<usize>,
+    cache_dir: &Path,
+    data_dir: &Path,
+) -> Result<SearchPage, String> {
+    let parsed = SearchQuery::parse(query)?;
+    let offset = match cursor {
+        Some(c) => c.parse::<usize>().map_err(|_| format!("Invalid search cursor: {}", c))?,
+        None => 0,
+    };
+    let limit = limit.unwrap_or(DEFAULT_SEARCH_PAGE_SIZE).max(1);
+
+    if parsed.is_empty() {
+        return Ok(SearchPage::default());
+    }
 
-            if results.len() >= 50 {
-                break;
-            }
-        }
+    let sessions = sync_sessions(claude_dir, cache_dir, data_dir).await?;
 
-        if results.len() >= 50 {
-            break;
-        }
+    let sessions_by_path: HashMap<&str, &SessionMeta> = sessions
+        .iter()
+        .filter(|s| parsed.matches_project(&s.project_path))
+        .map(|s| (s.file_path.as_str(), s))
+        .collect();
+    // Filtro de projeto vai para o SQL, antes do limite de hits
+    let session_paths = parsed
+        .has_project_filter()
+        .then(|| sessions_by_path.keys().map(|p| p.to_string()).collect());
+
+    // Hits ja passaram por negacoes e regex; totais e `truncated` contam so sobreviventes
+    let mut hits = search_index::query_index(data_dir, &parsed, session_paths).await?;
+    let truncated = hits.len() > search_index::MAX_INDEX_HITS;
+    hits.truncate(search_index::MAX_INDEX_HITS);
+    hits.retain(|hit| sessions_by_path.contains_key(hit.session_path.as_str()));
+
+    // Agrupa por sessao; relevancia da sessao = soma dos scores bm25 (negados)
+    let mut groups: Vec<(f64, Vec<IndexHit>)> = Vec::new();
+    let mut position: HashMap<String, usize> = HashMap::new();
+    let total_hits = hits.len() as u32;
+
+    for hit in hits {
+        let idx = *position.entry(hit.session_path.clone()).or_insert_with(|| {
+            groups.push((0.0, Vec::new()));
+            groups.len() - 1
+        });
+        groups[idx].0 -= hit.rank;
+        groups[idx].1.push(hit);
+    }
+
+    let last_timestamp = |g: &(f64, Vec<IndexHit>)| {
+        sessions_by_path
+            .get(g.1[0].session_path.as_str())
+            .map(|s| s.last_timestamp.clone())
+            .unwrap_or_default()
+    };
+    // Mais relevantes primeiro; empate (ex: so filtros) desempata pela sessao mais recente
+    groups.sort_by(|a, b| {
+        b.0.total_cmp(&a.0)
+            .then_with(|| last_timestamp(b).cmp(&last_timestamp(a)))
+    });
+
+    let total_sessions = groups.len();
+    let next_cursor = (offset + limit < total_sessions).then(|| (offset + limit).to_string());
+    let matched_filters = parsed.filter_labels();
+    let mut results = Vec::new();
+
+    for (_, mut group) in groups.into_iter().skip(offset).take(limit) {
+        search_index::load_contents(data_dir, &mut group).await?;
+        let session = sessions_by_path[group[0].session_path.as_str()];
+
+        // Hits chegam ordenados por relevancia: o primeiro e o melhor trecho
+        let search_hits: Vec<SearchHit> = group.iter().map(|hit| to_search_hit(hit, &parsed)).collect();
+        let best = search_hits[0].clone();
+
+        let mut ordered = search_hits;
+        // Hits da sessao primeiro, depois os de cada subagente
+        ordered.sort_by(|a, b| {
+            a.subagent_path
+                .cmp(&b.subagent_path)
+                .then(a.entry_index.cmp(&b.entry_index))
+        });
+
+        results.push(SearchMatch {
+            session: session.clone(),
+            matched_text: best.snippet,
+            entry_type: best.entry_type,
+            match_count: ordered.len() as u32,
+            matched_scope: best.scope,
+            matched_filters: matched_filters.clone(),
+            hits: ordered,
+        });
     }
 
-    // Mais matches primeiro
-    results.sort_by(|a, b| b.match_count.cmp(&a.match_count));
-    Ok(results)
+    Ok(SearchPage {
+        results,
+        total_sessions: total_sessions as u32,
+        total_hits,
+        truncated,
+        next_cursor,
+    })
 }
 
-/// Extrai texto completo de um message entry
-fn extract_entry_text(message: &serde_json::Value) -> String {
-    if let Some(content) = message.get("content") {
-        if let Some(text) = content.as_str() {
-            return text.to_string();
-        }
-        if let Some(arr) = content.as_array() {
-            return arr
-                .iter()
-                .filter_map(|item| item.get("text").and_then(|t| t.as_str()))
-                .collect::<Vec<_>>()
-                .join("\n");
-        }
+fn to_search_hit(hit: &IndexHit, query: &SearchQuery) -> SearchHit {
+    let content = hit.content.clone().unwrap_or_default();
+    let scope = query.evaluate(content.columns()).unwrap_or(SearchScope::Text);
+    let text = content.scoped(scope);
+
+    SearchHit {
+        entry_index: hit.entry_index,
+        uuid: hit.uuid.clone(),
+        entry_type: hit.entry_type.clone(),
+        timestamp: hit.timestamp.clone(),
+        scope: scope.label().to_string(),
+        snippet: extract_context(text, query.anchor(text), 100),
+        subagent_path: (hit.path != hit.session_path).then(|| hit.path.clone()),
     }
-    String::new()
 }
 
-/// Extrai trecho de contexto ao redor do match
-fn extract_context(text: &str, query_lower: &str, radius: usize) -> String {
-    let text_lower = text.to_lowercase();
-    if let Some(pos) = text_lower.find(query_lower) {
-        let start = pos.saturating_sub(radius);
-        let end = (pos + query_lower.len() + radius).min(text.len());
+/// Extrai trecho de contexto ao redor do match (`anchor` = posicao e tamanho no texto)
+fn extract_context(text: &str, anchor: Option<(usize, usize)>, radius: usize) -> String {
+    if let Some((pos, len)) = anchor {
+        let start = pos.saturating_sub(radius).min(text.len());
+        let end = (pos + len + radius).min(text.len());
         // Ajusta para limites de char boundary (safe UTF-8 slicing)
         let start = safe_floor_boundary(text, start);
         let end = safe_ceil_boundary(text, end);
@@ -314,17 +516,16 @@ fn safe_ceil_boundary(s: &str, mut i: usize) -> usize {
     i
 }
 
-/// Parse de um arquivo .jsonl de sessao
-pub async fn parse_session_file(path: &str) -> Result<Vec<SessionEntry>, String> {
-    let content = tokio::fs::read_to_string(path)
+/// Parse de um arquivo .jsonl de sessao inteiro.
+/// A posicao no vetor e o entry_index (mesma numeracao de session_reader).
+pub async fn read_transcript(path: &Path) -> Result<Vec<TranscriptEntry>, String> {
+    let content = session_archive::read_to_string(path)
         .await
         .map_err(|e| format!("Failed to read file: {}", e))?;
 
-    let entries: Vec<SessionEntry> = content
+    Ok(content
         .lines()
         .filter(|line| !line.trim().is_empty())
         .filter_map(|line| serde_json::from_str(line).ok())
-        .collect();
-
-    Ok(entries)
+        .collect())
 }
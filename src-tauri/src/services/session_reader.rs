@@ -0,0 +1,290 @@
+use crate::models::session::{FullContentBlock, ReadDirection, SessionWindow};
+use crate::models::transcript::{ContentBlock, ToolResultContent, TranscriptEntry};
+use crate::services::session_archive;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Instant, UNIX_EPOCH};
+
+/// Tamanho padrao de uma janela de leitura
+pub const DEFAULT_WINDOW_SIZE: usize = 200;
+const MAX_WINDOW_SIZE: usize = 2000;
+
+/// Conteudo de tool_result (e toolUseResult serializado) acima deste tamanho (bytes) e truncado na janela
+const MAX_TOOL_RESULT_BYTES: usize = 16 * 1024;
+
+/// Quantidade de indices de linhas mantidos em memoria
+const MAX_CACHED_INDEXES: usize = 16;
+
+/// Posicao de um entry no arquivo
+#[derive(Debug, Clone)]
+struct IndexedLine {
+    offset: u64,
+    len: u64,
+    uuid: Option<String>,
+}
+
+/// Indice de offsets das linhas validas de um JSONL, estendido incrementalmente
+#[derive(Debug, Clone, Default)]
+struct LineIndex {
+    size: u64,
+    mtime_ms: u64,
+    /// Offset logo apos a ultima linha completa ja indexada
+    scanned_bytes: u64,
+    lines: Vec<IndexedLine>,
+}
+
+/// Leitura minima da linha: todo objeto JSON e um entry (mesmo criterio de TranscriptEntry)
+#[derive(Deserialize)]
+struct LineProbe {
+    #[serde(default)]
+    uuid: Option<String>,
+}
+
+static LINE_INDEXES: LazyLock<Mutex<HashMap<String, (LineIndex, Instant)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Le uma janela de ate `limit` entries da sessao.
+/// Com `cursor` (uuid), le os entries depois (forward) ou antes (backward) dele, sem inclui-lo.
+/// Com `offset`, le a partir dele (forward) ou ate ele, exclusivo (backward).
+/// Sem nenhum dos dois, le do inicio (forward) ou do fim (backward).
+pub async fn read_window(
+    path: &str,
+    offset: Option<usize>,
+    cursor: Option<&str>,
+    limit: Option<usize>,
+    direction: ReadDirection,
+) -> Result<SessionWindow, String> {
+    let path = path.to_string();
+    let cursor = cursor.map(|c| c.to_string());
+    let limit = limit.unwrap_or(DEFAULT_WINDOW_SIZE).clamp(1, MAX_WINDOW_SIZE);
+
+    tokio::task::spawn_blocking(move || {
+        let index = line_index(&path)?;
+        let total = index.lines.len();
+
+        let (start, end) = match (&cursor, direction) {
+            (Some(uuid), direction) => {
+                let pos = index
+                    .lines
+                    .iter()
+                    .position(|l| l.uuid.as_deref() == Some(uuid.as_str()))
+                    .ok_or_else(|| format!("Entry not found: {}", uuid))?;
+                match direction {
+                    ReadDirection::Forward => (pos + 1, (pos + 1 + limit).min(total)),
+                    ReadDirection::Backward => (pos.saturating_sub(limit), pos),
+                }
+            }
+            (None, ReadDirection::Forward) => {
+                let start = offset.unwrap_or(0).min(total);
+                (start, (start + limit).min(total))
+            }
+            (None, ReadDirection::Backward) => {
+                let end = offset.unwrap_or(total).min(total);
+                (end.saturating_sub(limit), end)
+            }
+        };
+
+        let entries = read_entries(Path::new(&path), &index.lines[start..end])?;
+
+        Ok(SessionWindow {
+            entries,
+            start_index: start as u32,
+            total_entries: total as u32,
+            has_before: start > 0,
+            has_after: end < total,
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to read session window: {}", e))?
+}
+
+/// Bloco de conteudo completo (sem truncamento) de um entry, junto com o toolUseResult completo
+pub async fn read_content_block(
+    path: &str,
+    entry_index: usize,
+    block_index: usize,
+) -> Result<FullContentBlock, String> {
+    let path = path.to_string();
+
+    tokio::task::spawn_blocking(move || {
+        let index = line_index(&path)?;
+        let line = index
+            .lines
+            .get(entry_index)
+            .ok_or_else(|| format!("Entry {} not found", entry_index))?;
+
+        let bytes = read_range(Path::new(&path), line.offset, line.len)?;
+        let entry: TranscriptEntry = serde_json::from_slice(&bytes)
+            .map_err(|e| format!("Failed to parse entry: {}", e))?;
+
+        let block = entry
+            .message()
+            .and_then(|m| m.blocks().get(block_index))
+            .cloned()
+            .ok_or_else(|| format!("Content block {} not found in entry {}", block_index, entry_index))?;
+        let tool_use_result = entry.message_entry().and_then(|e| e.tool_use_result.clone());
+
+        Ok(FullContentBlock {
+            block,
+            tool_use_result,
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to read content block: {}", e))?
+}
+
+/// Indice de linhas atualizado do arquivo, reaproveitando o cache quando possivel
+fn line_index(path: &str) -> Result<LineIndex, String> {
+    let metadata = std::fs::metadata(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let size = metadata.len();
+    let mtime_ms = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+
+    let previous = LINE_INDEXES
+        .lock()
+        .ok()
+        .and_then(|mut cache| cache.remove(path))
+        .map(|(index, _)| index);
+
+    let index = match previous {
+        Some(index) if index.size == size && index.mtime_ms == mtime_ms => index,
+        // Arquivo so cresceu: continua do ultimo offset indexado
+        Some(index) if !session_archive::is_compressed(path) && index.size <= size && index.scanned_bytes <= size => {
+            extend_index(path, index, size, mtime_ms)?
+        }
+        _ => extend_index(path, LineIndex::default(), size, mtime_ms)?,
+    };
+
+    if let Ok(mut cache) = LINE_INDEXES.lock() {
+        if cache.len() >= MAX_CACHED_INDEXES {
+            let oldest = cache
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = oldest {
+                cache.remove(&key);
+            }
+        }
+        cache.insert(path.to_string(), (index.clone(), Instant::now()));
+    }
+
+    Ok(index)
+}
+
+fn extend_index(path: &str, mut index: LineIndex, size: u64, mtime_ms: u64) -> Result<LineIndex, String> {
+    let mut file = session_archive::open(Path::new(path)).map_err(|e| format!("Failed to read file: {}", e))?;
+    file.seek(SeekFrom::Start(index.scanned_bytes))
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let mut reader = BufReader::new(file);
+    let mut offset = index.scanned_bytes;
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        let read = reader
+            .read_until(b'\n', &mut line)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        // Linha final sem '\n' ainda esta sendo escrita
+        if read == 0 || line.last() != Some(&b'\n') {
+            break;
+        }
+
+        if let Ok(probe) = serde_json::from_slice::<LineProbe>(&line) {
+            index.lines.push(IndexedLine {
+                offset,
+                len: read as u64,
+                uuid: probe.uuid,
+            });
+        }
+        offset += read as u64;
+    }
+
+    index.size = size;
+    index.mtime_ms = mtime_ms;
+    index.scanned_bytes = offset;
+    Ok(index)
+}
+
+/// Le e faz parse de um trecho continuo de linhas, truncando tool_results grandes
+fn read_entries(path: &Path, lines: &[IndexedLine]) -> Result<Vec<TranscriptEntry>, String> {
+    let (Some(first), Some(last)) = (lines.first(), lines.last()) else {
+        return Ok(Vec::new());
+    };
+
+    let bytes = read_range(path, first.offset, last.offset + last.len - first.offset)?;
+
+    let entries = lines
+        .iter()
+        .filter_map(|line| {
+            let start = (line.offset - first.offset) as usize;
+            let slice = bytes.get(start..start + line.len as usize)?;
+            let mut entry: TranscriptEntry = serde_json::from_slice(slice).ok()?;
+            truncate_tool_results(&mut entry);
+            Some(entry)
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+fn read_range(path: &Path, offset: u64, len: u64) -> Result<Vec<u8>, String> {
+    let mut file = session_archive::open(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let mut buf = vec![0; len as usize];
+    file.read_exact(&mut buf)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    Ok(buf)
+}
+
+/// Substitui o conteudo de tool_results grandes por um preview.
+/// O bloco recebe `truncated: true` e `original_size` (bytes) para o viewer buscar o conteudo completo.
+/// Um `toolUseResult` grande vira `{truncated, original_size, preview}` (preview do JSON serializado).
+fn truncate_tool_results(entry: &mut TranscriptEntry) {
+    let Some(message_entry) = entry.message_entry_mut() else {
+        return;
+    };
+
+    if let Some(value) = &message_entry.tool_use_result {
+        let raw = value.to_string();
+        if raw.len() > MAX_TOOL_RESULT_BYTES {
+            message_entry.tool_use_result = Some(serde_json::json!({
+                "truncated": true,
+                "original_size": raw.len(),
+                "preview": &raw[..char_floor(&raw, MAX_TOOL_RESULT_BYTES)],
+            }));
+        }
+    }
+
+    for block in message_entry.message.blocks_mut() {
+        let ContentBlock::ToolResult(result) = block else {
+            continue;
+        };
+        let text = result.text();
+        if text.len() <= MAX_TOOL_RESULT_BYTES {
+            continue;
+        }
+
+        let cut = char_floor(&text, MAX_TOOL_RESULT_BYTES);
+        result.content = Some(ToolResultContent::Text(text[..cut].to_string()));
+        result.extra.insert("truncated".to_string(), serde_json::Value::Bool(true));
+        result.extra.insert("original_size".to_string(), serde_json::Value::from(text.len()));
+    }
+}
+
+/// Maior limite de char <= `max`
+fn char_floor(text: &str, max: usize) -> usize {
+    let mut cut = max.min(text.len());
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    cut
+}
@@ -0,0 +1,184 @@
+use crate::models::session::{
+    DayTiming, ProjectTiming, SessionMeta, SessionTiming, TimingOptions, TimingReport,
+    TimingTotals, TurnTiming,
+};
+use crate::services::index_timings::{self, TimingRow};
+use crate::services::session_parser;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Pausa padrao acima da qual o tempo entre entries conta como ocioso
+const DEFAULT_IDLE_THRESHOLD_SECS: u64 = 300;
+
+/// Latencia por turno, tempo ativo x ocioso e espera do usuario, por sessao, projeto e dia
+pub async fn session_timing(
+    claude_dir: &str,
+    options: &TimingOptions,
+    cache_dir: &Path,
+    data_dir: &Path,
+) -> Result<TimingReport, String> {
+    let sessions = session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+
+    let session_paths = match &options.session_path {
+        Some(path) => Some(vec![path.clone()]),
+        None => session_parser::session_paths_for_project(&sessions, options.project.as_deref()),
+    };
+    let rows = index_timings::query(
+        data_dir,
+        session_paths,
+        options.since.clone(),
+        options.until.clone(),
+    )
+    .await?;
+
+    let idle_threshold_ms = options
+        .idle_threshold_secs
+        .unwrap_or(DEFAULT_IDLE_THRESHOLD_SECS)
+        * 1000;
+    let by_path: HashMap<&str, &SessionMeta> =
+        sessions.iter().map(|s| (s.file_path.as_str(), s)).collect();
+
+    let mut report = TimingReport {
+        idle_threshold_ms,
+        ..Default::default()
+    };
+    let mut projects: HashMap<String, ProjectTiming> = HashMap::new();
+    let mut days: BTreeMap<String, TimingTotals> = BTreeMap::new();
+
+    // Linhas vem ordenadas por sessao e entry_index
+    for chunk in rows.chunk_by(|a, b| a.session_path == b.session_path) {
+        let Some(meta) = by_path.get(chunk[0].session_path.as_str()) else {
+            continue;
+        };
+        let (totals, turns, session_days) = time_session(chunk, idle_threshold_ms);
+
+        report.totals.add(&totals);
+        projects
+            .entry(meta.project_path.clone())
+            .or_insert_with(|| ProjectTiming {
+                project_path: meta.project_path.clone(),
+                project_display: meta.project_display.clone(),
+                totals: TimingTotals::default(),
+            })
+            .totals
+            .add(&totals);
+        for (date, day_totals) in session_days {
+            days.entry(date).or_default().add(&day_totals);
+        }
+
+        report.sessions.push(SessionTiming {
+            session_id: meta.id.clone(),
+            file_path: meta.file_path.clone(),
+            project_display: meta.project_display.clone(),
+            totals,
+            turns: if options.session_path.is_some() {
+                turns
+            } else {
+                Vec::new()
+            },
+        });
+    }
+
+    report
+        .sessions
+        .sort_by_key(|s| std::cmp::Reverse(s.totals.active_ms));
+    report.projects = projects.into_values().collect();
+    report
+        .projects
+        .sort_by_key(|p| std::cmp::Reverse(p.totals.active_ms));
+    report.days = days
+        .into_iter()
+        .map(|(date, totals)| DayTiming { date, totals })
+        .collect();
+    Ok(report)
+}
+
+/// Metricas de uma sessao. Turnos contam no dia do prompt; intervalos no dia em que comecam.
+fn time_session(
+    rows: &[TimingRow],
+    idle_threshold_ms: u64,
+) -> (
+    TimingTotals,
+    Vec<TurnTiming>,
+    BTreeMap<String, TimingTotals>,
+) {
+    let mut totals = TimingTotals {
+        sessions: 1,
+        ..Default::default()
+    };
+    let mut turns = Vec::new();
+    let mut days: BTreeMap<String, TimingTotals> = BTreeMap::new();
+
+    let events: Vec<(&TimingRow, i64)> = rows
+        .iter()
+        .filter_map(|row| Some((row, parse_ms(&row.timestamp)?)))
+        .collect();
+
+    // Turno aberto: (prompt, horario do prompt, ultima resposta do assistente)
+    let mut open: Option<(&TimingRow, i64, Option<i64>)> = None;
+    let mut close = |open: Option<(&TimingRow, i64, Option<i64>)>,
+                     totals: &mut TimingTotals,
+                     days: &mut BTreeMap<String, TimingTotals>| {
+        let Some((prompt, start, Some(end))) = open else {
+            return;
+        };
+        let latency_ms = (end - start).max(0) as u64;
+        for t in [
+            &mut *totals,
+            days.entry(day(&prompt.timestamp)).or_default(),
+        ] {
+            t.turns += 1;
+            t.wait_ms += latency_ms;
+            t.max_latency_ms = t.max_latency_ms.max(latency_ms);
+            t.avg_latency_ms = t.wait_ms / t.turns as u64;
+        }
+        turns.push(TurnTiming {
+            entry_index: prompt.entry_index,
+            timestamp: prompt.timestamp.clone(),
+            latency_ms,
+        });
+    };
+
+    for (i, &(row, ms)) in events.iter().enumerate() {
+        if let Some(&(previous, previous_ms)) = i.checked_sub(1).and_then(|p| events.get(p)) {
+            let gap = (ms - previous_ms).max(0) as u64;
+            let day_totals = days.entry(day(&previous.timestamp)).or_default();
+            if gap <= idle_threshold_ms {
+                totals.active_ms += gap;
+                day_totals.active_ms += gap;
+            } else {
+                totals.idle_ms += gap;
+                day_totals.idle_ms += gap;
+            }
+        }
+
+        match row.kind.as_str() {
+            "prompt" => {
+                close(open.take(), &mut totals, &mut days);
+                open = Some((row, ms, None));
+            }
+            "assistant" => {
+                if let Some((_, _, end)) = &mut open {
+                    *end = Some(ms);
+                }
+            }
+            _ => {}
+        }
+    }
+    close(open.take(), &mut totals, &mut days);
+
+    for day_totals in days.values_mut() {
+        day_totals.sessions = 1;
+    }
+    (totals, turns, days)
+}
+
+fn parse_ms(timestamp: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.timestamp_millis())
+}
+
+fn day(timestamp: &str) -> String {
+    timestamp.get(..10).unwrap_or(timestamp).to_string()
+}
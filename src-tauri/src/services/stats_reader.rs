@@ -1,8 +1,12 @@
 use crate::models::stats::{DailyActivity, DailyModelTokens, GlobalStats, ModelUsageEntry, ProjectMetrics};
+use crate::models::transcript::{ContentBlock, TranscriptEntry};
+use crate::services::project_resolver::{self, ProjectResolver};
+use crate::services::session_archive;
 use serde_json::Value;
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Metricas acumuladas de um projeto calculadas dos JSONL
+#[derive(Default)]
 struct ProjectJsonlStats {
     total_input_tokens: u64,
     total_output_tokens: u64,
@@ -32,7 +36,7 @@ pub async fn read_project_stats() -> Result<Vec<ProjectMetrics>, String> {
 
     let mut result = Vec::new();
 
-    // Coletar paths conhecidos para mapeamento slug -> path
+    // Coletar paths conhecidos para fallback slug -> path
     let known_projects: Vec<String> = root
         .get("projects")
         .and_then(|v| v.as_object())
@@ -95,11 +99,7 @@ async fn compute_project_jsonl_stats(
     let projects_dir = home.join(".claude").join("projects");
     let mut result: HashMap<String, ProjectJsonlStats> = HashMap::new();
 
-    // Mapa slug -> path real usando projetos conhecidos do .claude.json
-    let slug_map: HashMap<String, String> = known_projects
-        .iter()
-        .map(|p| (path_to_slug(p), p.clone()))
-        .collect();
+    let resolver = ProjectResolver::new(known_projects);
 
     // Listar diretorios sem bloquear o executor Tokio
     let pd = projects_dir.clone();
@@ -122,12 +122,6 @@ async fn compute_project_jsonl_stats(
             continue;
         }
 
-        // Usar mapa de projetos conhecidos; fallback para conversao simples
-        let project_path = slug_map
-            .get(dir_name.as_ref())
-            .cloned()
-            .unwrap_or_else(|| slug_to_path(&dir_name));
-
         // Coletar JSONL sem bloquear o executor
         let dp = dir_path.clone();
         let jsonl_files = tokio::task::spawn_blocking(move || collect_jsonl_files(&dp))
@@ -137,76 +131,71 @@ async fn compute_project_jsonl_stats(
             continue;
         }
 
-        let stats = result.entry(project_path).or_insert_with(|| ProjectJsonlStats {
-            total_input_tokens: 0,
-            total_output_tokens: 0,
-            total_cache_read_tokens: 0,
-            total_cache_creation_tokens: 0,
-            sessions: HashSet::new(),
-            model_usage: HashMap::new(),
-        });
+        let mut stats = ProjectJsonlStats::default();
+        let mut cwds: Vec<String> = Vec::new();
 
         for file_path in &jsonl_files {
-            let Ok(content) = tokio::fs::read_to_string(file_path).await else {
+            let Ok(content) = session_archive::read_to_string(file_path).await else {
                 continue;
             };
 
+            // Um cwd por arquivo, igual a listagem de sessoes
+            if let Some(cwd) = project_resolver::first_cwd(content.lines()) {
+                cwds.push(cwd);
+            }
+
             for line in content.lines() {
-                let Ok(entry) = serde_json::from_str::<Value>(line) else {
+                let Ok(entry) = serde_json::from_str::<TranscriptEntry>(line) else {
                     continue;
                 };
 
-                let entry_type = entry.get("type").and_then(|v| v.as_str()).unwrap_or("");
-
-                if entry_type == "user" {
-                    if let Some(sid) = entry.get("sessionId").and_then(|v| v.as_str()) {
-                        stats.sessions.insert(sid.to_string());
+                let assistant = match &entry {
+                    TranscriptEntry::User(user) => {
+                        if let Some(sid) = &user.meta.session_id {
+                            stats.sessions.insert(sid.clone());
+                        }
+                        continue;
                     }
-                }
-
-                if entry_type != "assistant" {
-                    continue;
-                }
+                    TranscriptEntry::Assistant(assistant) => assistant,
+                    _ => continue,
+                };
 
-                let msg = entry.get("message").unwrap_or(&Value::Null);
-                let Some(usage) = msg.get("usage") else {
+                let msg = &assistant.message;
+                let Some(usage) = &msg.usage else {
                     continue;
                 };
 
-                let inp = usage.get("input_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
-                let out = usage.get("output_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
-                let cache_read = usage.get("cache_read_input_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
-                let cache_create = usage.get("cache_creation_input_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
+                stats.total_input_tokens += usage.input_tokens;
+                stats.total_output_tokens += usage.output_tokens;
+                stats.total_cache_read_tokens += usage.cache_read_input_tokens;
+                stats.total_cache_creation_tokens += usage.cache_creation_input_tokens;
 
-                stats.total_input_tokens += inp;
-                stats.total_output_tokens += out;
-                stats.total_cache_read_tokens += cache_read;
-                stats.total_cache_creation_tokens += cache_create;
-
-                if let Some(model) = msg.get("model").and_then(|v| v.as_str()) {
-                    let entry = stats.model_usage.entry(model.to_string()).or_insert((0, 0));
-                    entry.0 += inp;
-                    entry.1 += out;
+                if let Some(model) = &msg.model {
+                    let entry = stats.model_usage.entry(model.clone()).or_insert((0, 0));
+                    entry.0 += usage.input_tokens;
+                    entry.1 += usage.output_tokens;
                 }
             }
         }
+
+        // Path canonico vem do cwd registrado nos entries
+        let project_path = resolver.resolve(&dir_name, &cwds);
+        let acc = result.entry(project_path).or_default();
+        acc.total_input_tokens += stats.total_input_tokens;
+        acc.total_output_tokens += stats.total_output_tokens;
+        acc.total_cache_read_tokens += stats.total_cache_read_tokens;
+        acc.total_cache_creation_tokens += stats.total_cache_creation_tokens;
+        acc.sessions.extend(stats.sessions);
+        for (model, (inp, out)) in stats.model_usage {
+            let entry = acc.model_usage.entry(model).or_insert((0, 0));
+            entry.0 += inp;
+            entry.1 += out;
+        }
     }
 
     result
 }
 
-/// Converte slug de diretorio para path real (fallback)
-fn slug_to_path(slug: &str) -> String {
-    slug.replacen('-', "/", 1).replace('-', "/")
-}
-
-/// Converte path real para slug de diretorio (replica logica do Claude CLI)
-fn path_to_slug(path: &str) -> String {
-    path.chars()
-        .map(|c| if c == '/' || c == ' ' || c == ',' { '-' } else { c })
-        .collect()
-}
-
 /// Constroi mapa invertido: project_path -> "owner/repo"
 fn build_github_map(root: &Value) -> HashMap<String, String> {
     let mut map = HashMap::new();
@@ -297,16 +286,16 @@ async fn supplement_global_stats(stats: &mut GlobalStats, home: &std::path::Path
         .unwrap_or_default();
 
     for file_path in &jsonl_files {
-        let Ok(content) = tokio::fs::read_to_string(file_path).await else {
+        let Ok(content) = session_archive::read_to_string(file_path).await else {
             continue;
         };
 
         for line in content.lines() {
-            let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            let Ok(entry) = serde_json::from_str::<TranscriptEntry>(line) else {
                 continue;
             };
 
-            let Some(ts) = entry.get("timestamp").and_then(|v| v.as_str()) else {
+            let Some(ts) = entry.timestamp() else {
                 continue;
             };
             if ts.len() < 10 {
@@ -319,41 +308,32 @@ async fn supplement_global_stats(stats: &mut GlobalStats, home: &std::path::Path
                 continue;
             }
 
-            let Some(entry_type) = entry.get("type").and_then(|v| v.as_str()) else {
-                continue;
-            };
-
             let day = days.entry(date.to_string()).or_insert_with(DayAccumulator::new);
 
-            match entry_type {
-                "user" => {
+            match &entry {
+                TranscriptEntry::User(user) => {
                     day.message_count += 1;
                     extra_messages += 1;
-                    if let Some(sid) = entry.get("sessionId").and_then(|v| v.as_str()) {
-                        day.sessions.insert(sid.to_string());
-                        extra_sessions.insert(sid.to_string());
+                    if let Some(sid) = &user.meta.session_id {
+                        day.sessions.insert(sid.clone());
+                        extra_sessions.insert(sid.clone());
                     }
                 }
-                "assistant" => {
-                    let msg = entry.get("message").unwrap_or(&Value::Null);
+                TranscriptEntry::Assistant(assistant) => {
+                    let msg = &assistant.message;
 
                     // Contar tool_use
-                    if let Some(content) = msg.get("content").and_then(|v| v.as_array()) {
-                        for block in content {
-                            if block.get("type").and_then(|v| v.as_str()) == Some("tool_use") {
-                                day.tool_call_count += 1;
-                            }
-                        }
-                    }
+                    day.tool_call_count += msg
+                        .blocks()
+                        .iter()
+                        .filter(|b| matches!(b, ContentBlock::ToolUse(_)))
+                        .count() as u64;
 
                     // Tokens por modelo
-                    if let Some(model) = msg.get("model").and_then(|v| v.as_str()) {
-                        if let Some(usage) = msg.get("usage") {
-                            let inp = usage.get("input_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
-                            let out = usage.get("output_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
-                            if inp + out > 0 {
-                                *day.tokens_by_model.entry(model.to_string()).or_insert(0) += inp + out;
-                            }
+                    if let (Some(model), Some(usage)) = (&msg.model, &msg.usage) {
+                        let tokens = usage.input_tokens + usage.output_tokens;
+                        if tokens > 0 {
+                            *day.tokens_by_model.entry(model.clone()).or_insert(0) += tokens;
                         }
                     }
 
@@ -395,25 +375,41 @@ async fn supplement_global_stats(stats: &mut GlobalStats, home: &std::path::Path
     }
 }
 
-/// Coleta todos os .jsonl recursivamente em um diretorio
-fn collect_jsonl_files(dir: &std::path::Path) -> Vec<std::path::PathBuf> {
+/// Coleta todos os transcripts (.jsonl, .jsonl.zst, .jsonl.gz) recursivamente em um diretorio
+pub fn collect_jsonl_files(dir: &std::path::Path) -> Vec<std::path::PathBuf> {
     let mut files = Vec::new();
+    // Ignorar diretorio memory
+    let enter = |path: &std::path::Path| path.file_name().is_none_or(|n| n != "memory");
+    walk_dir(dir, &enter, &mut |path, _| {
+        if path
+            .file_name()
+            .is_some_and(|n| session_archive::is_transcript(&n.to_string_lossy()))
+        {
+            files.push(path.to_path_buf());
+        }
+    });
+    files
+}
+
+/// Percorre um diretorio recursivamente. `enter` decide se desce em cada subdiretorio
+/// e `visit` recebe cada arquivo com seus metadados.
+pub fn walk_dir(
+    dir: &std::path::Path,
+    enter: &impl Fn(&std::path::Path) -> bool,
+    visit: &mut impl FnMut(&std::path::Path, &std::fs::Metadata),
+) {
     let Ok(entries) = std::fs::read_dir(dir) else {
-        return files;
+        return;
     };
 
     for entry in entries.flatten() {
         let path = entry.path();
         if path.is_dir() {
-            // Ignorar diretorio memory
-            if path.file_name().map_or(false, |n| n == "memory") {
-                continue;
+            if enter(&path) {
+                walk_dir(&path, enter, visit);
             }
-            files.extend(collect_jsonl_files(&path));
-        } else if path.extension().map_or(false, |e| e == "jsonl") {
-            files.push(path);
+        } else if let Ok(metadata) = entry.metadata() {
+            visit(&path, &metadata);
         }
     }
-
-    files
 }
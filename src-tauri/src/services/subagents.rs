@@ -0,0 +1,297 @@
+use crate::models::session::{SessionSubagents, SubagentRun, SubagentTypeTotals, TokenTotals};
+use crate::models::transcript::{ContentBlock, TranscriptEntry};
+use crate::services::pricing::UsageAccumulator;
+use crate::services::{session_archive, session_parser};
+use std::collections::{BTreeSet, HashMap};
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+/// Ferramentas que criam subagentes (Task nas versoes antigas, Agent nas novas)
+const AGENT_TOOLS: &[&str] = &["Task", "Agent"];
+
+/// Tool_use de criacao de subagente no transcript pai
+struct Spawn {
+    tool_use_id: String,
+    subagent_type: String,
+    description: Option<String>,
+    prompt: Option<String>,
+    entry_index: u32,
+    agent_id: Option<String>,
+    claimed: bool,
+}
+
+/// Arquivos de subagente de cada sessao de uma pasta de projeto.
+/// Formato atual: `<session>/subagents/agent-*.jsonl`.
+/// Formato antigo: `agent-*.jsonl` ao lado das sessoes, com o `sessionId` da sessao pai.
+pub fn files_by_session(project_dir: &Path) -> HashMap<String, Vec<PathBuf>> {
+    let mut result: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(project_dir) else {
+        return result;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if path.is_dir() {
+            let Ok(agents) = std::fs::read_dir(path.join("subagents")) else {
+                continue;
+            };
+            let files = agents
+                .flatten()
+                .map(|a| a.path())
+                .filter(|p| {
+                    p.file_name()
+                        .is_some_and(|n| session_archive::is_transcript(&n.to_string_lossy()))
+                });
+            result.entry(name).or_default().extend(files);
+        } else if is_agent_file(&name) {
+            if let Some(session_id) = first_session_id(&path) {
+                result.entry(session_id).or_default().push(path);
+            }
+        }
+    }
+
+    result
+}
+
+/// Transcripts de subagente das sessoes informadas, como (arquivo, arquivo da sessao pai).
+/// Le cada pasta de projeto uma vez.
+pub async fn files_with_parent(session_paths: Vec<String>) -> Result<Vec<(String, String)>, String> {
+    tokio::task::spawn_blocking(move || {
+        let mut by_dir: HashMap<PathBuf, HashMap<String, String>> = HashMap::new();
+        for session_path in session_paths {
+            let path = Path::new(&session_path);
+            let Some(dir) = path.parent() else {
+                continue;
+            };
+            let id = session_archive::session_id_from_path(path);
+            by_dir
+                .entry(dir.to_path_buf())
+                .or_default()
+                .insert(id, session_path);
+        }
+
+        let mut files = Vec::new();
+        for (dir, sessions) in by_dir {
+            for (id, agents) in files_by_session(&dir) {
+                let Some(parent) = sessions.get(&id) else {
+                    continue;
+                };
+                files.extend(
+                    agents
+                        .into_iter()
+                        .map(|a| (a.to_string_lossy().to_string(), parent.clone())),
+                );
+            }
+        }
+        files
+    })
+    .await
+    .map_err(|e| format!("Failed to list subagents: {}", e))
+}
+
+/// Transcript de subagente no formato antigo (nao e uma sessao propria)
+pub fn is_agent_file(filename: &str) -> bool {
+    filename.starts_with("agent-") && filename.ends_with(".jsonl")
+}
+
+/// Subagentes da sessao ligados aos tool_use que os criaram, com tokens e custo
+pub async fn read_subagents(session_path: &str) -> Result<SessionSubagents, String> {
+    let path = Path::new(session_path);
+    let session_id = session_archive::session_id_from_path(path);
+    let project_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    let parent = session_parser::read_transcript(path).await?;
+    let mut session_usage = UsageAccumulator::default();
+    for message in parent.iter().filter_map(TranscriptEntry::message) {
+        session_usage.add(message);
+    }
+    let mut spawns = find_spawns(&parent);
+
+    let dir = project_dir.clone();
+    let id = session_id.clone();
+    let mut files = tokio::task::spawn_blocking(move || files_by_session(&dir).remove(&id))
+        .await
+        .map_err(|e| format!("Failed to list subagents: {}", e))?
+        .unwrap_or_default();
+    files.sort();
+
+    let mut runs = Vec::new();
+    for file in files {
+        let entries = match session_parser::read_transcript(&file).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("[Rex] {}: {}", file.display(), e);
+                continue;
+            }
+        };
+        runs.push(to_run(&file, &entries, &mut spawns));
+    }
+    runs.sort_by(|a, b| a.first_timestamp.cmp(&b.first_timestamp));
+
+    let mut subagent_tokens = TokenTotals::default();
+    let mut by_type: Vec<SubagentTypeTotals> = Vec::new();
+    for run in &runs {
+        subagent_tokens.add(&run.tokens);
+        match by_type
+            .iter_mut()
+            .find(|t| t.subagent_type == run.subagent_type)
+        {
+            Some(totals) => {
+                totals.runs += 1;
+                totals.tokens.add(&run.tokens);
+            }
+            None => by_type.push(SubagentTypeTotals {
+                subagent_type: run.subagent_type.clone(),
+                runs: 1,
+                tokens: run.tokens.clone(),
+            }),
+        }
+    }
+    by_type.sort_by(|a, b| b.tokens.cost_usd.total_cmp(&a.tokens.cost_usd));
+
+    Ok(SessionSubagents {
+        session_id,
+        session_tokens: session_usage.totals,
+        subagent_tokens,
+        runs,
+        by_type,
+    })
+}
+
+/// Tool_use de Task/Agent do transcript pai, com o agentId devolvido no tool_result
+fn find_spawns(entries: &[TranscriptEntry]) -> Vec<Spawn> {
+    let mut spawns = Vec::new();
+    let mut agent_ids: HashMap<&str, String> = HashMap::new();
+
+    for (entry_index, entry) in entries.iter().enumerate() {
+        let Some(message_entry) = entry.message_entry() else {
+            continue;
+        };
+        let agent_id = message_entry
+            .tool_use_result
+            .as_ref()
+            .and_then(|r| r.get("agentId"))
+            .and_then(|v| v.as_str());
+
+        for block in message_entry.message.blocks() {
+            match block {
+                ContentBlock::ToolUse(tool) if AGENT_TOOLS.contains(&tool.name.as_str()) => {
+                    let input = |key: &str| {
+                        tool.input
+                            .get(key)
+                            .and_then(|v| v.as_str())
+                            .map(String::from)
+                    };
+                    spawns.push(Spawn {
+                        tool_use_id: tool.id.clone(),
+                        subagent_type: input("subagent_type")
+                            .unwrap_or_else(|| "general-purpose".to_string()),
+                        description: input("description"),
+                        prompt: input("prompt"),
+                        entry_index: entry_index as u32,
+                        agent_id: None,
+                        claimed: false,
+                    });
+                }
+                ContentBlock::ToolResult(result) => {
+                    if let Some(id) = agent_id {
+                        agent_ids.insert(result.tool_use_id.as_str(), id.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for spawn in &mut spawns {
+        spawn.agent_id = agent_ids.remove(spawn.tool_use_id.as_str());
+    }
+    spawns
+}
+
+/// Resume o transcript do subagente e liga ao tool_use pelo agentId ou, sem ele, pelo prompt
+fn to_run(file: &Path, entries: &[TranscriptEntry], spawns: &mut [Spawn]) -> SubagentRun {
+    let agent_id = entries
+        .iter()
+        .filter_map(TranscriptEntry::message_entry)
+        .find_map(|e| e.extra.get("agentId").and_then(|v| v.as_str()))
+        .map(String::from)
+        .unwrap_or_else(|| {
+            let stem = session_archive::session_id_from_path(file);
+            stem.trim_start_matches("agent-").to_string()
+        });
+
+    let prompt = entries
+        .iter()
+        .find_map(|e| match e {
+            TranscriptEntry::User(user) => Some(user.message.text()),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    let spawn = spawns
+        .iter()
+        .position(|s| !s.claimed && s.agent_id.as_deref() == Some(agent_id.as_str()))
+        .or_else(|| {
+            spawns.iter().position(|s| {
+                !s.claimed
+                    && s.prompt
+                        .as_deref()
+                        .is_some_and(|p| p.trim() == prompt.trim())
+            })
+        })
+        .map(|idx| {
+            spawns[idx].claimed = true;
+            &spawns[idx]
+        });
+
+    let mut usage = UsageAccumulator::default();
+    let mut models = BTreeSet::new();
+    let mut message_count = 0;
+    for message in entries.iter().filter_map(TranscriptEntry::message) {
+        message_count += 1;
+        usage.add(message);
+        if let Some(model) = &message.model {
+            models.insert(model.clone());
+        }
+    }
+
+    let timestamps: Vec<&str> = entries
+        .iter()
+        .filter_map(TranscriptEntry::timestamp)
+        .collect();
+
+    SubagentRun {
+        agent_id,
+        file_path: file.to_string_lossy().to_string(),
+        subagent_type: spawn
+            .map(|s| s.subagent_type.clone())
+            .unwrap_or_else(|| "unknown".to_string()),
+        description: spawn.and_then(|s| s.description.clone()),
+        tool_use_id: spawn.map(|s| s.tool_use_id.clone()),
+        parent_entry_index: spawn.map(|s| s.entry_index),
+        first_timestamp: timestamps.first().copied().unwrap_or_default().to_string(),
+        last_timestamp: timestamps.last().copied().unwrap_or_default().to_string(),
+        message_count,
+        models: models.into_iter().collect(),
+        tokens: usage.totals,
+    }
+}
+
+/// sessionId do primeiro entry que o informa (le so o inicio do arquivo)
+fn first_session_id(path: &Path) -> Option<String> {
+    let file = std::fs::File::open(path).ok()?;
+    std::io::BufReader::new(file)
+        .lines()
+        .take(5)
+        .map_while(Result::ok)
+        .find_map(|line| {
+            serde_json::from_str::<serde_json::Value>(&line)
+                .ok()?
+                .get("sessionId")?
+                .as_str()
+                .map(String::from)
+        })
+}
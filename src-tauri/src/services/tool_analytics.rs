@@ -0,0 +1,69 @@
+use crate::models::session::{ToolAnalytics, ToolAnalyticsOptions, ToolStats};
+use crate::services::index_tool_calls::{self, ToolStatsRow};
+use crate::services::session_parser;
+use std::path::Path;
+
+/// Estatisticas de ferramentas de uma sessao, de um projeto ou de todas as sessoes
+pub async fn tool_analytics(
+    claude_dir: &str,
+    options: &ToolAnalyticsOptions,
+    cache_dir: &Path,
+    data_dir: &Path,
+) -> Result<ToolAnalytics, String> {
+    let sessions = session_parser::sync_sessions(claude_dir, cache_dir, data_dir).await?;
+
+    let session_paths = match &options.session_path {
+        Some(path) => Some(vec![path.clone()]),
+        None => session_parser::session_paths_for_project(&sessions, options.project.as_deref()),
+    };
+    let rows = index_tool_calls::query(
+        data_dir,
+        session_paths,
+        options.since.clone(),
+        options.until.clone(),
+    )
+    .await?;
+
+    let mut analytics = ToolAnalytics::default();
+    for row in rows {
+        analytics.total_calls += row.calls;
+        analytics.total_errors += row.errors;
+        analytics.tools.push(to_stats(row));
+    }
+    Ok(analytics)
+}
+
+fn to_stats(row: ToolStatsRow) -> ToolStats {
+    // MCP: mcp__<server>__<tool>
+    let mcp_server = row
+        .tool_name
+        .strip_prefix("mcp__")
+        .and_then(|rest| rest.split("__").next())
+        .map(String::from);
+    let category = if row.server {
+        "server"
+    } else if mcp_server.is_some() {
+        "mcp"
+    } else {
+        "builtin"
+    };
+    let error_rate = if row.results > 0 {
+        row.errors as f64 / row.results as f64
+    } else {
+        0.0
+    };
+
+    ToolStats {
+        tool_name: row.tool_name,
+        category: category.to_string(),
+        mcp_server,
+        calls: row.calls,
+        errors: row.errors,
+        error_rate,
+        results: row.results,
+        avg_result_chars: row.avg_result_chars,
+        total_result_chars: row.total_result_chars,
+        avg_latency_ms: row.avg_latency_ms,
+        max_latency_ms: row.max_latency_ms,
+    }
+}
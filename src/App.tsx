@@ -4,6 +4,8 @@ import { AppLayout } from "@/components/layout/AppLayout";
 import { ConnectionPage } from "@/pages/ConnectionPage";
 import { DashboardPage } from "@/pages/DashboardPage";
 import { HistoryPage } from "@/pages/HistoryPage";
+import { CommandsPage } from "@/pages/CommandsPage";
+import { InsightsPage } from "@/pages/InsightsPage";
 import { ProjectsPage } from "@/pages/ProjectsPage";
 import { UsagePage } from "@/pages/UsagePage";
 import { SettingsPage } from "@/pages/SettingsPage";
@@ -42,6 +44,8 @@ export default function App() {
       <Route element={<AppLayout />}>
         <Route index element={<DashboardPage />} />
         <Route path={ROUTES.HISTORY} element={<HistoryPage />} />
+        <Route path={ROUTES.COMMANDS} element={<CommandsPage />} />
+        <Route path={ROUTES.INSIGHTS} element={<InsightsPage />} />
         <Route path={ROUTES.PROJECTS} element={<ProjectsPage />} />
         <Route path={ROUTES.USAGE} element={<UsagePage />} />
         <Route path={ROUTES.MCP} element={<McpStatusPage />} />
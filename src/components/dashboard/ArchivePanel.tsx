@@ -0,0 +1,91 @@
+import { useState } from "react";
+import { Icon } from "@/components/ui/Icon";
+import { Spinner } from "@/components/ui/Spinner";
+import { SegmentedControl } from "@/components/ui/SegmentedControl";
+import { archiveSessions } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { formatBytes } from "@/utils/formatters";
+import type { ArchiveFormat, ArchiveOptions, SessionMeta } from "@/types/session";
+
+interface ArchivePanelProps {
+  // Sessoes selecionadas; sem elas o painel arquiva por idade
+  sessions?: SessionMeta[];
+  onDone?: () => void;
+}
+
+const FORMATS: Array<{ label: string; value: ArchiveFormat }> = [
+  { label: "zstd", value: "zstd" },
+  { label: "gzip", value: "gzip" },
+];
+
+export function ArchivePanel({ sessions, onDone }: ArchivePanelProps) {
+  const [format, setFormat] = useState<ArchiveFormat>("zstd");
+  const [olderThanDays, setOlderThanDays] = useState(30);
+  const [isArchiving, setIsArchiving] = useState(false);
+  const [status, setStatus] = useState<string | null>(null);
+  const [errors, setErrors] = useState<string[]>([]);
+
+  // Sessoes ja arquivadas sao ignoradas pelo backend
+  const pending = sessions?.filter((s) => !s.archived);
+
+  const handleArchive = async () => {
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+
+    const options: ArchiveOptions = pending
+      ? { session_paths: pending.map((s) => s.file_path), format }
+      : { older_than_days: Math.max(olderThanDays, 0), format };
+
+    setIsArchiving(true);
+    setStatus(null);
+    setErrors([]);
+    try {
+      const report = await archiveSessions(claudeDir, options, useWsl, wslDistro || undefined);
+      const saved = report.original_bytes - report.archived_bytes;
+      const failed = report.errors.length > 0 ? ` · ${report.errors.length} failed` : "";
+      setStatus(
+        report.archived.length === 0
+          ? `Nothing archived${failed}`
+          : `Archived ${report.archived.length} sessions, saved ${formatBytes(saved)}${failed}`,
+      );
+      setErrors(report.errors);
+      onDone?.();
+    } catch (e) {
+      setStatus(e instanceof Error ? e.message : String(e));
+    } finally {
+      setIsArchiving(false);
+    }
+  };
+
+  return (
+    <div className="flex flex-wrap items-center gap-4 text-xs font-mono text-muted-subtle">
+      <SegmentedControl options={FORMATS} value={format} onChange={setFormat} />
+      {!pending && (
+        <label className="flex items-center gap-1.5">
+          Inactive for
+          <input
+            type="number"
+            min={0}
+            value={olderThanDays}
+            onChange={(e) => setOlderThanDays(Number(e.target.value) || 0)}
+            className="w-16 px-2 py-1 bg-input-bg border border-border-subtle rounded text-xs text-foreground"
+          />
+          days
+        </label>
+      )}
+      <button
+        onClick={handleArchive}
+        disabled={isArchiving || pending?.length === 0}
+        className="flex items-center gap-1.5 px-3 py-1.5 rounded text-xs font-semibold bg-transparent border border-primary/30 text-primary hover:bg-primary/10 hover:border-primary transition-all cursor-pointer disabled:opacity-50"
+      >
+        {isArchiving ? <Spinner size="sm" /> : <Icon name="inventory_2" size="sm" />}
+        {pending ? `Archive ${pending.length}` : "Archive old sessions"}
+      </button>
+      {status && (
+        <span className={errors.length > 0 ? "text-danger" : ""} title={errors.join("\n") || undefined}>
+          {status}
+        </span>
+      )}
+    </div>
+  );
+}
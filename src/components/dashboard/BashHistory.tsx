@@ -0,0 +1,127 @@
+import { useState, useEffect } from "react";
+import { Icon } from "@/components/ui/Icon";
+import { Spinner } from "@/components/ui/Spinner";
+import { Badge } from "@/components/ui/Badge";
+import { listBashCommands } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { formatRelativeTime } from "@/utils/formatters";
+import type { BashCommand, BashHistoryOptions } from "@/types/session";
+
+function statusBadge(cmd: BashCommand) {
+  if (cmd.interrupted) return <Badge variant="muted">interrupted</Badge>;
+  if (cmd.output === null) return <Badge variant="muted">no result</Badge>;
+  if (cmd.is_error || (cmd.exit_code ?? 0) !== 0) {
+    return <Badge variant="danger">{cmd.exit_code !== null ? `exit ${cmd.exit_code}` : "error"}</Badge>;
+  }
+  return <Badge variant="green">ok</Badge>;
+}
+
+export function BashHistory() {
+  const [commands, setCommands] = useState<BashCommand[]>([]);
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [options, setOptions] = useState<BashHistoryOptions>({});
+  const [debounced, setDebounced] = useState<BashHistoryOptions>({});
+  // Comando com a saida expandida (transcript + entry_index + command)
+  const [expanded, setExpanded] = useState<string | null>(null);
+
+  // Debounce dos campos de texto
+  useEffect(() => {
+    const timer = setTimeout(() => setDebounced(options), 400);
+    return () => clearTimeout(timer);
+  }, [options]);
+
+  useEffect(() => {
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+    let cancelled = false;
+    setIsLoading(true);
+    setError(null);
+
+    listBashCommands(claudeDir, debounced, useWsl, wslDistro || undefined)
+      .then((result) => {
+        if (!cancelled) setCommands(result);
+      })
+      .catch((e) => {
+        if (!cancelled) setError(e instanceof Error ? e.message : String(e));
+      })
+      .finally(() => {
+        if (!cancelled) setIsLoading(false);
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [debounced]);
+
+  const field = (placeholder: string, key: "query" | "project", width: string) => (
+    <input
+      className={`px-3 py-2 bg-input-bg border border-border-subtle rounded-lg text-sm text-foreground focus:outline-none focus:border-primary/50 placeholder-muted-subtle ${width}`}
+      placeholder={placeholder}
+      value={options[key] ?? ""}
+      onChange={(e) => setOptions((o) => ({ ...o, [key]: e.target.value || null }))}
+    />
+  );
+
+  return (
+    <section className="glass-card rounded-xl overflow-hidden flex flex-col min-h-[400px]">
+      {/* Filtros */}
+      <div className="p-6 border-b border-border-subtle flex flex-wrap items-center gap-3">
+        {field("Search commands...", "query", "w-72")}
+        {field("Project", "project", "w-48")}
+        <input
+          type="date"
+          value={options.since ?? ""}
+          onChange={(e) => setOptions((o) => ({ ...o, since: e.target.value || null }))}
+          className="py-2 px-2 bg-input-bg border border-border-subtle rounded-lg text-xs text-foreground-secondary font-mono"
+        />
+        <label className="flex items-center gap-1.5 text-xs font-mono text-muted-subtle cursor-pointer">
+          <input
+            type="checkbox"
+            checked={options.failed_only ?? false}
+            onChange={(e) => setOptions((o) => ({ ...o, failed_only: e.target.checked }))}
+          />
+          Failed only
+        </label>
+        {isLoading && <Spinner size="sm" />}
+        <span className="ml-auto text-xs text-muted-subtle font-mono">{commands.length} commands</span>
+      </div>
+
+      {error && <p className="px-6 py-3 text-xs text-danger">{error}</p>}
+
+      <div className="divide-y divide-border-subtle flex-1">
+        {!isLoading && commands.length === 0 && !error && (
+          <div className="px-6 py-12 text-center text-muted-subtle">No commands found</div>
+        )}
+        {commands.map((cmd) => {
+          const key = `${cmd.subagent_path ?? cmd.session_path}:${cmd.entry_index}:${cmd.command}`;
+          return (
+            <div key={key} className="px-6 py-3 text-xs font-mono">
+              <button
+                onClick={() => setExpanded(expanded === key ? null : key)}
+                className="w-full flex items-start gap-3 text-left cursor-pointer group"
+              >
+                <Icon name="chevron_right" size="sm" className={`text-muted-subtle transition-transform ${expanded === key ? "rotate-90" : ""}`} />
+                <div className="flex-1 min-w-0">
+                  <p className="text-foreground-secondary group-hover:text-foreground truncate">$ {cmd.command}</p>
+                  <p className="text-muted-subtle mt-0.5 truncate">
+                    {cmd.project_display} · {formatRelativeTime(cmd.timestamp)}
+                    {cmd.subagent_path && " · subagent"}
+                    {cmd.description && ` · ${cmd.description}`}
+                  </p>
+                </div>
+                {statusBadge(cmd)}
+              </button>
+              {expanded === key && (
+                <pre className="mt-2 ml-8 p-3 rounded bg-surface border border-border-subtle text-muted whitespace-pre-wrap break-all max-h-80 overflow-y-auto">
+                  {cmd.cwd && <span className="text-muted-subtle">{cmd.cwd}{"\n"}</span>}
+                  {cmd.output || "(no output)"}
+                </pre>
+              )}
+            </div>
+          );
+        })}
+      </div>
+    </section>
+  );
+}
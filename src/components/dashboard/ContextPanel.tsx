@@ -0,0 +1,136 @@
+import { useState, useEffect } from "react";
+import { AreaChart, Area, XAxis, YAxis, Tooltip, ReferenceLine, ResponsiveContainer } from "recharts";
+import { Spinner } from "@/components/ui/Spinner";
+import { Badge } from "@/components/ui/Badge";
+import { readContextTimeline } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { formatNumber, formatTokenCount } from "@/utils/formatters";
+import type { ContextPoint, ContextTimeline } from "@/types/session";
+
+interface ContextPanelProps {
+  sessionPath: string;
+}
+
+// Tooltip de um ponto da timeline
+function PointTooltip({ active, payload }: { active?: boolean; payload?: Array<{ payload: ContextPoint }> }) {
+  if (!active || !payload?.length) return null;
+  const point = payload[0].payload;
+  return (
+    <div className="bg-card border border-border rounded-lg px-3 py-2 shadow-elevation-3 backdrop-blur-lg text-xs">
+      <p className="text-[10px] text-muted-subtle font-mono mb-1">entry #{point.entry_index}</p>
+      <p className="text-foreground font-bold">
+        {formatTokenCount(point.context_tokens)} / {formatTokenCount(point.context_window)} (
+        {(point.occupancy * 100).toFixed(1)}%)
+      </p>
+      {point.delta_tokens !== 0 && (
+        <p className="text-muted">
+          {point.delta_tokens > 0 ? "+" : ""}
+          {formatTokenCount(point.delta_tokens)} since previous turn
+        </p>
+      )}
+    </div>
+  );
+}
+
+export function ContextPanel({ sessionPath }: ContextPanelProps) {
+  const [timeline, setTimeline] = useState<ContextTimeline | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const { useWsl, wslDistro } = useConnectionStore.getState();
+    let cancelled = false;
+    setTimeline(null);
+    setError(null);
+
+    readContextTimeline(sessionPath, useWsl, wslDistro || undefined)
+      .then((result) => {
+        if (!cancelled) setTimeline(result);
+      })
+      .catch((e) => {
+        if (!cancelled) setError(e instanceof Error ? e.message : String(e));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [sessionPath]);
+
+  if (error) {
+    return <p className="text-xs text-danger px-1 py-2">{error}</p>;
+  }
+
+  if (!timeline) {
+    return (
+      <div className="flex justify-center py-3">
+        <Spinner size="sm" />
+      </div>
+    );
+  }
+
+  if (timeline.points.length === 0) {
+    return <p className="text-xs text-muted-subtle font-mono px-1 py-2">No token usage recorded</p>;
+  }
+
+  return (
+    <div className="mb-4 rounded border border-border-subtle bg-surface p-3 text-xs font-mono space-y-3">
+      <p className="text-muted-subtle">
+        Peak {formatTokenCount(timeline.peak_tokens)} of {formatTokenCount(timeline.context_window)} ·{" "}
+        {timeline.compactions.length} compactions
+      </p>
+
+      <div className="h-[160px]">
+        <ResponsiveContainer width="100%" height="100%">
+          <AreaChart data={timeline.points} margin={{ top: 4, right: 4, bottom: 0, left: -10 }}>
+            <XAxis dataKey="entry_index" type="number" domain={["dataMin", "dataMax"]} hide />
+            <YAxis
+              domain={[0, timeline.context_window]}
+              tick={{ fontSize: 9, fill: "var(--color-muted-subtle)" }}
+              tickLine={false}
+              axisLine={false}
+              width={40}
+              tickFormatter={(v: number) => formatTokenCount(v)}
+            />
+            <Tooltip content={<PointTooltip />} cursor={{ stroke: "rgba(255,255,255,0.06)" }} />
+            {/* Limite da janela do modelo */}
+            <ReferenceLine y={timeline.context_window} stroke="var(--color-danger)" strokeDasharray="4 4" />
+            {timeline.compactions.map((c) => (
+              <ReferenceLine
+                key={c.entry_index}
+                x={c.entry_index}
+                stroke="var(--color-warning)"
+                strokeDasharray="2 2"
+                label={{ value: c.trigger ?? "compact", fontSize: 9, fill: "var(--color-warning)", position: "insideTopLeft" }}
+              />
+            ))}
+            <Area
+              type="stepAfter"
+              dataKey="context_tokens"
+              stroke="var(--color-primary)"
+              fill="var(--color-primary)"
+              fillOpacity={0.12}
+            />
+          </AreaChart>
+        </ResponsiveContainer>
+      </div>
+
+      {/* Turnos que mais aumentaram o contexto */}
+      {timeline.top_bloat.length > 0 && (
+        <div className="space-y-1.5">
+          <p className="text-muted-subtle uppercase tracking-wider text-[10px]">Biggest context jumps</p>
+          {timeline.top_bloat.map((b) => (
+            <div key={b.entry_index} className="flex items-start gap-2">
+              <span className="shrink-0 w-16 text-right text-danger">+{formatTokenCount(b.delta_tokens)}</span>
+              <Badge variant={b.cause === "tool_result" ? "accent" : "muted"}>{b.tool_name ?? b.cause}</Badge>
+              <span className="truncate text-muted" title={b.preview}>
+                {b.preview || "(assistant output)"}
+              </span>
+              {b.cause_chars > 0 && (
+                <span className="shrink-0 text-muted-subtle">{formatNumber(b.cause_chars)} chars</span>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -6,16 +6,24 @@ import { oneDark } from "react-syntax-highlighter/dist/esm/styles/prism";
 import { Icon } from "@/components/ui/Icon";
 import { Spinner } from "@/components/ui/Spinner";
 import { formatRelativeTime } from "@/utils/formatters";
-import type { SessionEntry } from "@/types/session";
+import type { SessionEntry, BranchKind } from "@/types/session";
 
 interface ConversationViewerProps {
   entries: SessionEntry[];
+  // entry_index do primeiro entry carregado
+  startIndex: number;
+  totalEntries: number;
+  hasMore: boolean;
+  branches: Map<string, BranchKind>;
   isLoading: boolean;
+  isLoadingMore: boolean;
   error: string | null;
+  onLoadMore: () => void;
+  onExpandBlock: (entryIndex: number, blockIndex: number) => Promise<void>;
+  // Cria uma sessao nova ate este entry e a retoma
+  onFork?: (uuid: string) => void;
 }
 
-const INITIAL_LIMIT = 500;
-
 // --- Tipos para blocos estruturados ---
 
 interface MessageBlock {
@@ -23,9 +31,17 @@ interface MessageBlock {
   content: string;
   toolName?: string;
   toolId?: string;
+  // Posicao do bloco em message.content (para buscar o conteudo completo)
+  blockIndex?: number;
+  // Conteudo cortado pelo backend; originalSize em bytes
+  truncated?: boolean;
+  originalSize?: number;
 }
 
 interface ParsedMessage {
+  entryIndex: number;
+  uuid?: string;
+  branch: BranchKind;
   role: "user" | "assistant";
   blocks: MessageBlock[];
   timestamp: string;
@@ -43,7 +59,7 @@ function parseBlocks(message: Record<string, unknown>): MessageBlock[] {
   if (!Array.isArray(content)) return [];
 
   const blocks: MessageBlock[] = [];
-  for (const item of content as Array<Record<string, unknown>>) {
+  (content as Array<Record<string, unknown>>).forEach((item, blockIndex) => {
     const itemType = item.type as string;
 
     if (itemType === "text" && typeof item.text === "string") {
@@ -78,21 +94,32 @@ function parseBlocks(message: Record<string, unknown>): MessageBlock[] {
           type: "tool_result",
           content: text,
           toolId: item.tool_use_id as string,
+          blockIndex,
+          truncated: item.truncated === true,
+          originalSize: typeof item.original_size === "number" ? item.original_size : undefined,
         });
       }
     }
-  }
+  });
 
   return blocks;
 }
 
-function parseMessages(entries: SessionEntry[]): ParsedMessage[] {
+function parseMessages(
+  entries: SessionEntry[],
+  startIndex: number,
+  branches: Map<string, BranchKind>,
+): ParsedMessage[] {
   return entries
-    .filter((e) => e.type === "user" || e.type === "assistant")
-    .map((e) => ({
+    .map((e, i) => ({ entry: e, entryIndex: startIndex + i }))
+    .filter(({ entry }) => entry.type === "user" || entry.type === "assistant")
+    .map(({ entry: e, entryIndex }) => ({
+      entryIndex,
+      uuid: e.uuid,
+      branch: (e.uuid && branches.get(e.uuid)) || "active",
       role: e.type as "user" | "assistant",
-      blocks: parseBlocks(e.message),
-      timestamp: e.timestamp,
+      blocks: parseBlocks(e.message ?? {}),
+      timestamp: e.timestamp ?? "",
     }))
     .filter((m) => {
       if (m.blocks.length === 0) return false;
@@ -292,7 +319,40 @@ function MarkdownContent({ text }: { text: string }) {
   );
 }
 
-function BlockRenderer({ block }: { block: MessageBlock }) {
+function TruncatedNotice({ block, onExpand }: { block: MessageBlock; onExpand?: () => Promise<void> }) {
+  const [isExpanding, setIsExpanding] = useState(false);
+  const [failed, setFailed] = useState(false);
+  const sizeKb = block.originalSize ? Math.ceil(block.originalSize / 1024) : null;
+
+  const handleExpand = async () => {
+    if (!onExpand) return;
+    setIsExpanding(true);
+    setFailed(false);
+    try {
+      await onExpand();
+    } catch {
+      setFailed(true);
+    } finally {
+      setIsExpanding(false);
+    }
+  };
+
+  return (
+    <button
+      onClick={handleExpand}
+      disabled={isExpanding}
+      className="mt-2 text-[11px] font-mono text-primary hover:underline cursor-pointer disabled:opacity-50"
+    >
+      {isExpanding
+        ? "Loading..."
+        : failed
+          ? "Failed to load full output, retry"
+          : `Output truncated${sizeKb ? ` (${sizeKb} KB)` : ""}, show full`}
+    </button>
+  );
+}
+
+function BlockRenderer({ block, onExpand }: { block: MessageBlock; onExpand?: () => Promise<void> }) {
   switch (block.type) {
     case "text":
       return <MarkdownContent text={block.content} />;
@@ -316,6 +376,7 @@ function BlockRenderer({ block }: { block: MessageBlock }) {
           <pre className="whitespace-pre-wrap break-all font-mono text-[11px] text-muted max-h-60 overflow-y-auto">
             {block.content}
           </pre>
+          {block.truncated && <TruncatedNotice block={block} onExpand={onExpand} />}
         </CollapsibleBlock>
       );
     default:
@@ -329,14 +390,20 @@ function SearchBar({
   value,
   onChange,
   resultCount,
+  activeOnly,
+  onActiveOnlyChange,
+  hasBranches,
 }: {
   value: string;
   onChange: (v: string) => void;
   resultCount: number | null;
+  activeOnly: boolean;
+  onActiveOnlyChange: (v: boolean) => void;
+  hasBranches: boolean;
 }) {
   return (
-    <div className="sticky top-0 z-10 bg-bg/80 backdrop-blur-md border-b border-border-subtle px-5 py-2.5">
-      <div className="relative group">
+    <div className="sticky top-0 z-10 bg-bg/80 backdrop-blur-md border-b border-border-subtle px-5 py-2.5 flex items-center gap-3">
+      <div className="relative group flex-1">
         <Icon name="search" className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-subtle text-base group-focus-within:text-primary transition-colors" />
         <input
           type="text"
@@ -351,15 +418,38 @@ function SearchBar({
           </span>
         )}
       </div>
+      {hasBranches && (
+        <label className="flex items-center gap-1.5 text-[11px] text-muted-subtle font-mono cursor-pointer whitespace-nowrap">
+          <input
+            type="checkbox"
+            checked={activeOnly}
+            onChange={(e) => onActiveOnlyChange(e.target.checked)}
+            className="accent-primary"
+          />
+          Active branch only
+        </label>
+      )}
     </div>
   );
 }
 
 // --- Componente principal ---
 
-export function ConversationViewer({ entries, isLoading, error }: ConversationViewerProps) {
-  const [limit, setLimit] = useState(INITIAL_LIMIT);
+export function ConversationViewer({
+  entries,
+  startIndex,
+  totalEntries,
+  hasMore,
+  branches,
+  isLoading,
+  isLoadingMore,
+  error,
+  onLoadMore,
+  onExpandBlock,
+  onFork,
+}: ConversationViewerProps) {
   const [search, setSearch] = useState("");
+  const [activeOnly, setActiveOnly] = useState(false);
   const [showScrollBtn, setShowScrollBtn] = useState(false);
   const containerRef = useRef<HTMLDivElement>(null);
 
@@ -397,9 +487,10 @@ export function ConversationViewer({ entries, isLoading, error }: ConversationVi
     );
   }
 
-  const messages = parseMessages(entries);
+  const messages = parseMessages(entries, startIndex, branches);
+  const hasBranches = messages.some((m) => m.branch !== "active");
 
-  if (messages.length === 0) {
+  if (messages.length === 0 && !hasMore) {
     return (
       <div className="flex flex-col items-center justify-center py-20 text-muted-subtle gap-3">
         <Icon name="chat_bubble_outline" className="text-[32px] opacity-40" />
@@ -408,16 +499,13 @@ export function ConversationViewer({ entries, isLoading, error }: ConversationVi
     );
   }
 
-  // Filtrar por busca
+  // Filtrar por ramo e por busca
   const searchLower = search.toLowerCase();
-  const filtered = search
-    ? messages.filter((m) =>
-        m.blocks.some((b) => b.content.toLowerCase().includes(searchLower)),
-      )
-    : messages;
-
-  const visible = filtered.slice(0, limit);
-  const hasMore = filtered.length > limit;
+  const filtered = messages.filter(
+    (m) =>
+      (!activeOnly || m.branch === "active") &&
+      (!search || m.blocks.some((b) => b.content.toLowerCase().includes(searchLower))),
+  );
 
   // Texto completo de uma mensagem (para copy)
   const getFullText = (msg: ParsedMessage) =>
@@ -432,17 +520,20 @@ export function ConversationViewer({ entries, isLoading, error }: ConversationVi
         value={search}
         onChange={setSearch}
         resultCount={search ? filtered.length : null}
+        activeOnly={activeOnly}
+        onActiveOnlyChange={setActiveOnly}
+        hasBranches={hasBranches}
       />
 
       <div className="flex flex-col gap-3 p-5">
-        {visible.map((msg, i) => {
+        {filtered.map((msg) => {
           const isUser = msg.role === "user";
           const fullText = getFullText(msg);
 
           return (
             <div
-              key={i}
-              className={`flex flex-col min-w-0 ${isUser ? "items-end" : "items-start"} max-w-[85%] ${isUser ? "self-end" : "self-start"}`}
+              key={msg.entryIndex}
+              className={`flex flex-col min-w-0 ${isUser ? "items-end" : "items-start"} max-w-[85%] ${isUser ? "self-end" : "self-start"} ${msg.branch !== "active" ? "opacity-60" : ""}`}
             >
               <div className="flex items-center gap-1.5 mb-1 px-1">
                 <Icon
@@ -457,6 +548,11 @@ export function ConversationViewer({ entries, isLoading, error }: ConversationVi
                     {formatRelativeTime(msg.timestamp)}
                   </span>
                 )}
+                {msg.branch !== "active" && (
+                  <span className="text-[10px] text-warning font-mono border border-warning/30 rounded px-1">
+                    {msg.branch === "abandoned" ? "abandoned branch" : "sidechain"}
+                  </span>
+                )}
               </div>
               <div
                 className={`group relative px-4 py-3 text-sm leading-relaxed overflow-hidden max-w-full ${
@@ -465,11 +561,30 @@ export function ConversationViewer({ entries, isLoading, error }: ConversationVi
                     : "bg-surface/80 border border-border-subtle rounded-2xl rounded-bl-sm text-foreground-secondary"
                 }`}
               >
-                {msg.blocks.map((block, j) => <BlockRenderer key={j} block={block} />)}
-
-                {fullText && (
-                  <div className="flex justify-end mt-1 -mb-1 opacity-0 group-hover:opacity-100 transition-opacity">
-                    <CopyButton text={fullText} />
+                {msg.blocks.map((block, j) => (
+                  <BlockRenderer
+                    key={j}
+                    block={block}
+                    onExpand={
+                      block.blockIndex !== undefined
+                        ? () => onExpandBlock(msg.entryIndex, block.blockIndex as number)
+                        : undefined
+                    }
+                  />
+                ))}
+
+                {(fullText || (onFork && msg.uuid)) && (
+                  <div className="flex justify-end gap-1 mt-1 -mb-1 opacity-0 group-hover:opacity-100 transition-opacity">
+                    {onFork && msg.uuid && (
+                      <button
+                        onClick={() => onFork(msg.uuid as string)}
+                        className="p-1 rounded text-muted-subtle hover:text-foreground hover:bg-foreground/10 transition-colors cursor-pointer"
+                        title="Fork from here and resume"
+                      >
+                        <Icon name="call_split" className="text-[14px]" />
+                      </button>
+                    )}
+                    {fullText && <CopyButton text={fullText} />}
                   </div>
                 )}
               </div>
@@ -479,10 +594,13 @@ export function ConversationViewer({ entries, isLoading, error }: ConversationVi
 
         {hasMore && (
           <button
-            onClick={() => setLimit((l) => l + INITIAL_LIMIT)}
-            className="self-center mt-2 px-4 py-2 text-xs font-medium text-primary hover:text-primary-light border border-primary/20 rounded-lg hover:bg-primary/5 transition-colors cursor-pointer"
+            onClick={onLoadMore}
+            disabled={isLoadingMore}
+            className="self-center mt-2 px-4 py-2 text-xs font-medium text-primary hover:text-primary-light border border-primary/20 rounded-lg hover:bg-primary/5 transition-colors cursor-pointer disabled:opacity-50"
           >
-            Load more ({filtered.length - limit} remaining)
+            {isLoadingMore
+              ? "Loading..."
+              : `Load more (${totalEntries - startIndex - entries.length} remaining)`}
           </button>
         )}
       </div>
@@ -0,0 +1,140 @@
+import { useState, useEffect } from "react";
+import { save } from "@tauri-apps/plugin-dialog";
+import { Icon } from "@/components/ui/Icon";
+import { Spinner } from "@/components/ui/Spinner";
+import { sessionDiff, exportSessionPatch } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import type { SessionDiff, TurnRange } from "@/types/session";
+
+interface DiffPanelProps {
+  sessionPath: string;
+  sessionId: string;
+}
+
+export function lineClass(line: string): string {
+  if (line.startsWith("@@")) return "text-primary-light";
+  if (line.startsWith("+")) return "text-primary";
+  if (line.startsWith("-")) return "text-danger";
+  return "text-muted-subtle";
+}
+
+export function DiffPanel({ sessionPath, sessionId }: DiffPanelProps) {
+  const [diff, setDiff] = useState<SessionDiff | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [range, setRange] = useState<TurnRange>({});
+  const [status, setStatus] = useState<string | null>(null);
+
+  useEffect(() => {
+    const { useWsl, wslDistro } = useConnectionStore.getState();
+    let cancelled = false;
+    setDiff(null);
+    setError(null);
+
+    sessionDiff(sessionPath, range, useWsl, wslDistro || undefined)
+      .then((result) => {
+        if (!cancelled) setDiff(result);
+      })
+      .catch((e) => {
+        if (!cancelled) setError(e instanceof Error ? e.message : String(e));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [sessionPath, range]);
+
+  const handleExport = async () => {
+    const { useWsl, wslDistro } = useConnectionStore.getState();
+    const outputPath = await save({
+      title: "Export patch",
+      defaultPath: `${sessionId}.patch`,
+      filters: [{ name: "Patch", extensions: ["patch"] }],
+    });
+    if (!outputPath) return;
+    try {
+      await exportSessionPatch(sessionPath, outputPath, range, useWsl, wslDistro || undefined);
+      setStatus("Exported");
+    } catch (e) {
+      setStatus(e instanceof Error ? e.message : String(e));
+    }
+  };
+
+  // Campo vazio remove o limite
+  const turnInput = (label: string, key: keyof TurnRange) => (
+    <label className="flex items-center gap-1.5">
+      {label}
+      <input
+        type="number"
+        min={1}
+        max={diff?.turn_count}
+        value={range[key] ?? ""}
+        onChange={(e) =>
+          setRange((r) => ({ ...r, [key]: e.target.value ? Number(e.target.value) : null }))
+        }
+        className="w-14 px-1.5 py-0.5 rounded bg-surface border border-border-subtle text-foreground"
+      />
+    </label>
+  );
+
+  if (error) {
+    return <p className="text-xs text-danger px-1 py-2">{error}</p>;
+  }
+
+  return (
+    <div className="mb-4 rounded border border-border-subtle bg-surface p-3 text-xs font-mono space-y-3">
+      <div className="flex flex-wrap items-center gap-4 text-muted-subtle">
+        {turnInput("From turn", "from_turn")}
+        {turnInput("To turn", "to_turn")}
+        {diff && (
+          <span>
+            {diff.files.length} files · <span className="text-primary">+{diff.additions}</span>{" "}
+            <span className="text-danger">-{diff.deletions}</span>
+          </span>
+        )}
+        <button
+          onClick={handleExport}
+          disabled={!diff || diff.files.length === 0}
+          className="flex items-center gap-1.5 px-3 py-1.5 rounded text-xs font-semibold bg-transparent border border-primary/30 text-primary hover:bg-primary/10 hover:border-primary transition-all cursor-pointer disabled:opacity-50"
+        >
+          <Icon name="download" size="sm" /> Export .patch
+        </button>
+        {status && <span>{status}</span>}
+      </div>
+
+      {!diff ? (
+        <div className="flex justify-center py-3">
+          <Spinner size="sm" />
+        </div>
+      ) : diff.files.length === 0 ? (
+        <p className="text-muted-subtle">No file changes</p>
+      ) : (
+        diff.files.map((file) => (
+          <details key={file.file_path} open={diff.files.length <= 3}>
+            <summary className="flex justify-between gap-2 cursor-pointer text-foreground-secondary">
+              <span className="truncate">
+                {file.file_path}
+                {file.created && " (new)"}
+              </span>
+              <span className="shrink-0">
+                {!file.complete && (
+                  <span className="text-warning mr-2" title="Original content unknown, showing edited fragments only">
+                    partial
+                  </span>
+                )}
+                <span className="text-primary">+{file.additions}</span>{" "}
+                <span className="text-danger">-{file.deletions}</span>
+              </span>
+            </summary>
+            <pre className="mt-1 overflow-x-auto">
+              {file.diff.split("\n").map((line, i) => (
+                <div key={i} className={lineClass(line)}>
+                  {line || " "}
+                </div>
+              ))}
+            </pre>
+          </details>
+        ))
+      )}
+    </div>
+  );
+}
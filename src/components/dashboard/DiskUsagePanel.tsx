@@ -0,0 +1,188 @@
+import { useState, useEffect } from "react";
+import { AreaChart, Area, XAxis, YAxis, Tooltip, ResponsiveContainer } from "recharts";
+import { Icon } from "@/components/ui/Icon";
+import { Spinner } from "@/components/ui/Spinner";
+import { Badge } from "@/components/ui/Badge";
+import { diskUsage } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { formatBytes, formatNumber, formatRelativeTime } from "@/utils/formatters";
+import type { DiskNode, DiskSnapshot, DiskUsageReport } from "@/types/session";
+
+// Projetos e arquivos exibidos nas tabelas
+const MAX_ROWS = 15;
+
+function HistoryTooltip({ active, payload }: { active?: boolean; payload?: Array<{ payload: DiskSnapshot }> }) {
+  if (!active || !payload?.length) return null;
+  const day = payload[0].payload;
+  return (
+    <div className="bg-card border border-border rounded-lg px-3 py-2 shadow-elevation-3 backdrop-blur-lg text-xs">
+      <p className="text-[10px] text-muted-subtle font-mono mb-1">{day.date}</p>
+      <p className="text-foreground font-bold">{formatBytes(day.bytes)}</p>
+      <p className="text-muted">{formatNumber(day.files)} files</p>
+    </div>
+  );
+}
+
+// Linha da arvore de tamanhos; a barra e proporcional ao pai
+function TreeRow({ node, parentBytes, depth }: { node: DiskNode; parentBytes: number; depth: number }) {
+  const [open, setOpen] = useState(depth === 0);
+  const share = parentBytes > 0 ? (node.bytes / parentBytes) * 100 : 100;
+  const expandable = node.children.length > 0;
+
+  return (
+    <>
+      <div className="flex items-center gap-2 py-1" style={{ paddingLeft: depth * 16 }}>
+        <button
+          onClick={() => setOpen((v) => !v)}
+          disabled={!expandable}
+          className="w-4 text-muted-subtle hover:text-foreground cursor-pointer disabled:cursor-default disabled:opacity-0"
+          aria-label={open ? "Collapse" : "Expand"}
+        >
+          <Icon name={open ? "expand_more" : "chevron_right"} size="sm" />
+        </button>
+        <Icon name={node.is_dir ? "folder" : "description"} size="sm" className="text-muted-subtle" />
+        <span className="truncate flex-1 text-foreground-secondary" title={node.path}>
+          {node.name}
+        </span>
+        <div className="w-24 h-1.5 rounded bg-surface overflow-hidden">
+          <div className="h-full bg-primary/60" style={{ width: `${share}%` }} />
+        </div>
+        <span className="w-20 text-right text-foreground-secondary">{formatBytes(node.bytes)}</span>
+        <span className="w-16 text-right text-muted-subtle">{formatNumber(node.files)}</span>
+      </div>
+      {open &&
+        node.children.map((child) => (
+          <TreeRow key={child.path} node={child} parentBytes={node.bytes} depth={depth + 1} />
+        ))}
+    </>
+  );
+}
+
+export function DiskUsagePanel() {
+  const [report, setReport] = useState<DiskUsageReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+    let cancelled = false;
+
+    diskUsage(claudeDir, useWsl, wslDistro || undefined)
+      .then((result) => {
+        if (!cancelled) setReport(result);
+      })
+      .catch((e) => {
+        if (!cancelled) setError(e instanceof Error ? e.message : String(e));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, []);
+
+  if (error) {
+    return <p className="text-xs text-danger px-1 py-2">{error}</p>;
+  }
+
+  if (!report) {
+    return (
+      <div className="flex justify-center py-3">
+        <Spinner size="sm" />
+      </div>
+    );
+  }
+
+  const orphaned = report.projects.filter((p) => p.orphaned);
+
+  return (
+    <div className="text-xs font-mono space-y-5">
+      <p className="text-muted-subtle">
+        {formatBytes(report.tree.bytes)} in {formatNumber(report.tree.files)} files
+        {orphaned.length > 0 && (
+          <span className="text-warning">
+            {" "}
+            · {formatBytes(report.orphaned_bytes)} in {orphaned.length} orphaned projects
+          </span>
+        )}
+      </p>
+
+      {report.history.length === 1 && (
+        <p className="text-muted-subtle">Size history is recorded each time this panel is loaded</p>
+      )}
+      {report.history.length > 1 && (
+        <div>
+          <p className="text-muted-subtle uppercase tracking-wider text-[10px] mb-1">Size history</p>
+          <div className="h-[120px]">
+            <ResponsiveContainer width="100%" height="100%">
+              <AreaChart data={report.history} margin={{ top: 4, right: 4, bottom: 0, left: -10 }}>
+                <XAxis
+                  dataKey="date"
+                  tick={{ fontSize: 9, fill: "var(--color-muted-subtle)" }}
+                  tickLine={false}
+                  axisLine={false}
+                  interval="preserveStartEnd"
+                />
+                <YAxis
+                  tick={{ fontSize: 9, fill: "var(--color-muted-subtle)" }}
+                  tickLine={false}
+                  axisLine={false}
+                  width={50}
+                  tickFormatter={(v: number) => formatBytes(v)}
+                />
+                <Tooltip content={<HistoryTooltip />} cursor={{ stroke: "rgba(255,255,255,0.06)" }} />
+                <Area
+                  type="stepAfter"
+                  dataKey="bytes"
+                  stroke="var(--color-primary)"
+                  fill="var(--color-primary)"
+                  fillOpacity={0.12}
+                />
+              </AreaChart>
+            </ResponsiveContainer>
+          </div>
+        </div>
+      )}
+
+      <div>
+        <p className="text-muted-subtle uppercase tracking-wider text-[10px] mb-1">Folders</p>
+        <TreeRow node={report.tree} parentBytes={report.tree.bytes} depth={0} />
+      </div>
+
+      <div className="grid lg:grid-cols-2 gap-6">
+        <div>
+          <p className="text-muted-subtle uppercase tracking-wider text-[10px] mb-1">Projects by size</p>
+          <div className="divide-y divide-border-subtle">
+            {report.projects.slice(0, MAX_ROWS).map((p) => (
+              <div key={p.slug} className="py-1.5 flex items-center gap-3">
+                <span className="truncate flex-1 text-foreground-secondary" title={p.project_path}>
+                  {p.project_display}
+                </span>
+                {p.orphaned && <Badge variant="danger">orphaned</Badge>}
+                <span className="text-muted-subtle">{formatNumber(p.sessions)} sessions</span>
+                {p.last_modified && (
+                  <span className="w-20 text-right text-muted-subtle">{formatRelativeTime(p.last_modified)}</span>
+                )}
+                <span className="w-16 text-right text-foreground-secondary">{formatBytes(p.bytes)}</span>
+              </div>
+            ))}
+          </div>
+        </div>
+        <div>
+          <p className="text-muted-subtle uppercase tracking-wider text-[10px] mb-1">Largest session files</p>
+          <div className="divide-y divide-border-subtle">
+            {report.largest_files.slice(0, MAX_ROWS).map((f) => (
+              <div key={f.path} className="py-1.5 flex items-center gap-3">
+                <span className="truncate flex-1 text-foreground-secondary" title={f.path}>
+                  {f.project_display} · {f.session_id.slice(0, 8)}
+                </span>
+                {f.subagent && <Badge variant="muted">subagent</Badge>}
+                {f.archived && <Badge variant="muted">archived</Badge>}
+                <span className="w-16 text-right text-foreground-secondary">{formatBytes(f.bytes)}</span>
+              </div>
+            ))}
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,103 @@
+import { useState } from "react";
+import { save } from "@tauri-apps/plugin-dialog";
+import { Icon } from "@/components/ui/Icon";
+import { Spinner } from "@/components/ui/Spinner";
+import { SegmentedControl } from "@/components/ui/SegmentedControl";
+import { exportSession, exportSessions } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import type { ExportFormat, SessionMeta } from "@/types/session";
+
+interface ExportPanelProps {
+  // Uma sessao exporta um arquivo; varias exportam um zip
+  sessions: SessionMeta[];
+  onDone?: () => void;
+}
+
+const FORMATS: Array<{ label: string; value: ExportFormat }> = [
+  { label: "Markdown", value: "markdown" },
+  { label: "HTML", value: "html" },
+  { label: "JSON", value: "json" },
+];
+
+const EXTENSIONS: Record<ExportFormat, string> = {
+  markdown: "md",
+  html: "html",
+  json: "json",
+};
+
+export function ExportPanel({ sessions, onDone }: ExportPanelProps) {
+  const [format, setFormat] = useState<ExportFormat>("markdown");
+  const [includeThinking, setIncludeThinking] = useState(false);
+  const [includeToolResults, setIncludeToolResults] = useState(true);
+  const [includeSidechains, setIncludeSidechains] = useState(false);
+  const [isExporting, setIsExporting] = useState(false);
+  const [status, setStatus] = useState<string | null>(null);
+
+  const isBatch = sessions.length > 1;
+
+  const handleExport = async () => {
+    const { useWsl, wslDistro } = useConnectionStore.getState();
+    const extension = isBatch ? "zip" : EXTENSIONS[format];
+    const outputPath = await save({
+      title: isBatch ? `Export ${sessions.length} sessions` : "Export session",
+      defaultPath: isBatch ? "sessions.zip" : `${sessions[0].id}.${extension}`,
+      filters: [{ name: extension.toUpperCase(), extensions: [extension] }],
+    });
+    if (!outputPath) return;
+
+    const options = {
+      format,
+      include_thinking: includeThinking,
+      include_tool_results: includeToolResults,
+      include_sidechains: includeSidechains,
+    };
+
+    setIsExporting(true);
+    setStatus(null);
+    try {
+      if (isBatch) {
+        const count = await exportSessions(
+          sessions.map((s) => s.file_path),
+          outputPath,
+          options,
+          useWsl,
+          wslDistro || undefined,
+        );
+        setStatus(`Exported ${count} sessions`);
+      } else {
+        await exportSession(sessions[0].file_path, outputPath, options, useWsl, wslDistro || undefined);
+        setStatus("Exported");
+      }
+      onDone?.();
+    } catch (e) {
+      setStatus(e instanceof Error ? e.message : String(e));
+    } finally {
+      setIsExporting(false);
+    }
+  };
+
+  const checkbox = (label: string, checked: boolean, onChange: (v: boolean) => void) => (
+    <label className="flex items-center gap-1.5 cursor-pointer">
+      <input type="checkbox" checked={checked} onChange={(e) => onChange(e.target.checked)} />
+      {label}
+    </label>
+  );
+
+  return (
+    <div className="flex flex-wrap items-center gap-4 text-xs font-mono text-muted-subtle">
+      <SegmentedControl options={FORMATS} value={format} onChange={setFormat} />
+      {checkbox("Thinking", includeThinking, setIncludeThinking)}
+      {checkbox("Tool results", includeToolResults, setIncludeToolResults)}
+      {checkbox("Sidechains", includeSidechains, setIncludeSidechains)}
+      <button
+        onClick={handleExport}
+        disabled={isExporting || sessions.length === 0}
+        className="flex items-center gap-1.5 px-3 py-1.5 rounded text-xs font-semibold bg-transparent border border-primary/30 text-primary hover:bg-primary/10 hover:border-primary transition-all cursor-pointer disabled:opacity-50"
+      >
+        {isExporting ? <Spinner size="sm" /> : <Icon name="download" size="sm" />}
+        {isBatch ? `Export ${sessions.length} as zip` : "Export"}
+      </button>
+      {status && <span>{status}</span>}
+    </div>
+  );
+}
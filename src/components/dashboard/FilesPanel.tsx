@@ -0,0 +1,120 @@
+import { useState, useEffect } from "react";
+import { Spinner } from "@/components/ui/Spinner";
+import { sessionFiles, sessionsForFile } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { formatRelativeTime } from "@/utils/formatters";
+import type { FileSession, SessionFiles, TouchedFile } from "@/types/session";
+
+interface FilesPanelProps {
+  sessionPath: string;
+}
+
+export function FilesPanel({ sessionPath }: FilesPanelProps) {
+  const [files, setFiles] = useState<SessionFiles | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  // Arquivo expandido e as sessoes que o tocaram
+  const [openFile, setOpenFile] = useState<string | null>(null);
+  const [history, setHistory] = useState<FileSession[] | null>(null);
+
+  useEffect(() => {
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+    let cancelled = false;
+    setFiles(null);
+    setError(null);
+
+    sessionFiles(claudeDir, sessionPath, useWsl, wslDistro || undefined)
+      .then((result) => {
+        if (!cancelled) setFiles(result);
+      })
+      .catch((e) => {
+        if (!cancelled) setError(e instanceof Error ? e.message : String(e));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [sessionPath]);
+
+  const toggleFile = async (filePath: string) => {
+    if (openFile === filePath) {
+      setOpenFile(null);
+      return;
+    }
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+    setOpenFile(filePath);
+    setHistory(null);
+    try {
+      setHistory(await sessionsForFile(claudeDir, filePath, useWsl, wslDistro || undefined));
+    } catch {
+      setHistory([]);
+    }
+  };
+
+  if (error) {
+    return <p className="text-xs text-danger px-1 py-2">{error}</p>;
+  }
+
+  if (!files) {
+    return (
+      <div className="flex justify-center py-3">
+        <Spinner size="sm" />
+      </div>
+    );
+  }
+
+  const renderFile = (file: TouchedFile) => (
+    <div key={file.file_path}>
+      <button
+        onClick={() => toggleFile(file.file_path)}
+        className="w-full flex justify-between gap-2 text-left text-foreground-secondary hover:text-foreground cursor-pointer"
+      >
+        <span className="truncate">{file.file_path}</span>
+        <span className="shrink-0 text-muted-subtle">
+          {file.modifications > 0 && `${file.modifications} edits`}
+          {file.modifications > 0 && file.reads > 0 && " · "}
+          {file.reads > 0 && `${file.reads} reads`}
+        </span>
+      </button>
+      {/* Outras sessoes que tocaram o arquivo, em ordem cronologica */}
+      {openFile === file.file_path && (
+        <div className="pl-4 py-1 text-muted-subtle">
+          {history === null ? (
+            <Spinner size="sm" />
+          ) : (
+            history.map((h) => (
+              <div key={h.session.file_path} className="flex justify-between gap-2">
+                <span className="truncate">{h.session.summary}</span>
+                <span className="shrink-0">
+                  {h.file.modifications > 0 ? "modified" : "read"} ·{" "}
+                  {formatRelativeTime(h.file.first_timestamp)}
+                </span>
+              </div>
+            ))
+          )}
+        </div>
+      )}
+    </div>
+  );
+
+  return (
+    <div className="mb-4 rounded border border-border-subtle bg-surface p-3 text-xs font-mono space-y-2">
+      {files.modified.length === 0 && files.read.length === 0 && (
+        <p className="text-muted-subtle">No files touched</p>
+      )}
+      {files.modified.length > 0 && (
+        <div className="space-y-1">
+          <p className="text-muted-subtle uppercase tracking-wider text-[10px]">Modified</p>
+          {files.modified.map(renderFile)}
+        </div>
+      )}
+      {files.read.length > 0 && (
+        <div className="space-y-1">
+          <p className="text-muted-subtle uppercase tracking-wider text-[10px]">Read</p>
+          {files.read.map(renderFile)}
+        </div>
+      )}
+    </div>
+  );
+}
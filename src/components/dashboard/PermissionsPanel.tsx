@@ -0,0 +1,195 @@
+import { useState, useEffect } from "react";
+import { Spinner } from "@/components/ui/Spinner";
+import { Badge } from "@/components/ui/Badge";
+import { lineClass } from "./DiffPanel";
+import { applyPermissionRules, permissionAnalysis, previewPermissionRules } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { formatNumber } from "@/utils/formatters";
+import type { PermissionOptions, PermissionPreview, PermissionReport, ProjectPermissions } from "@/types/session";
+
+interface PermissionsPanelProps {
+  options?: PermissionOptions;
+}
+
+// Regras por projeto exibidas na tabela
+const MAX_RULES = 15;
+
+function ProjectCard({ project }: { project: ProjectPermissions }) {
+  // Sugestoes marcadas para o settings.json (todas por padrao)
+  const [allow, setAllow] = useState<string[]>(project.suggested_allow);
+  const [deny, setDeny] = useState<string[]>(project.suggested_deny);
+  const [preview, setPreview] = useState<PermissionPreview | null>(null);
+  const [status, setStatus] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const hasSuggestions = project.suggested_allow.length + project.suggested_deny.length > 0;
+
+  const toggle = (list: string[], setList: (v: string[]) => void, rule: string) => {
+    setList(list.includes(rule) ? list.filter((r) => r !== rule) : [...list, rule]);
+    setPreview(null);
+    setStatus(null);
+  };
+
+  const run = async (apply: boolean) => {
+    const { useWsl, wslDistro } = useConnectionStore.getState();
+    setError(null);
+    try {
+      const call = apply ? applyPermissionRules : previewPermissionRules;
+      const result = await call(project.project_path, allow, deny, useWsl, wslDistro || undefined);
+      setPreview(result);
+      if (apply) setStatus(`Added ${result.added} rules to ${result.settings_path}`);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    }
+  };
+
+  const checkbox = (list: string[], setList: (v: string[]) => void, rule: string, variant: "green" | "danger") => (
+    <label key={rule} className="flex items-center gap-2 cursor-pointer">
+      <input type="checkbox" checked={list.includes(rule)} onChange={() => toggle(list, setList, rule)} />
+      <Badge variant={variant}>{variant === "green" ? "allow" : "deny"}</Badge>
+      <span className="text-foreground-secondary">{rule}</span>
+    </label>
+  );
+
+  return (
+    <div className="rounded border border-border-subtle bg-surface p-3 space-y-3">
+      <div className="flex items-center gap-3">
+        <span className="text-foreground font-bold truncate" title={project.project_path}>
+          {project.project_display}
+        </span>
+        <span className="ml-auto text-muted-subtle">
+          {formatNumber(project.executed)} executed · {formatNumber(project.rejected)} rejected ·{" "}
+          {formatNumber(project.interruptions)} interrupted
+        </span>
+      </div>
+
+      <table className="w-full">
+        <thead>
+          <tr className="text-[10px] uppercase tracking-wider text-muted-subtle text-left">
+            <th className="py-1.5 font-semibold">Rule</th>
+            <th className="py-1.5 font-semibold text-right">Executed</th>
+            <th className="py-1.5 font-semibold text-right">Rejected</th>
+            <th className="py-1.5 font-semibold text-right">Interrupted</th>
+          </tr>
+        </thead>
+        <tbody className="divide-y divide-border-subtle">
+          {project.rules.slice(0, MAX_RULES).map((rule) => (
+            <tr key={`${rule.tool_name}:${rule.rule}`} className="text-foreground-secondary">
+              <td className="py-1.5 pr-2">
+                <span className="flex items-center gap-2 min-w-0">
+                  <span className="truncate" title={rule.example}>
+                    {rule.rule || `${rule.tool_name}: ${rule.example}`}
+                  </span>
+                  {rule.existing && (
+                    <Badge variant={rule.existing === "allow" ? "green" : "danger"}>
+                      {rule.existing === "allow" ? "allowed" : "denied"}
+                    </Badge>
+                  )}
+                </span>
+              </td>
+              <td className="py-1.5 text-right">{formatNumber(rule.executed)}</td>
+              <td className={`py-1.5 text-right ${rule.rejected > 0 ? "text-danger" : ""}`}>
+                {formatNumber(rule.rejected)}
+              </td>
+              <td className={`py-1.5 text-right ${rule.interrupted > 0 ? "text-warning" : ""}`}>
+                {formatNumber(rule.interrupted)}
+              </td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+
+      {/* Regras sugeridas para .claude/settings.json */}
+      {hasSuggestions && (
+        <div className="space-y-1.5">
+          <p className="text-muted-subtle uppercase tracking-wider text-[10px]">Suggested rules</p>
+          {project.suggested_allow.map((rule) => checkbox(allow, setAllow, rule, "green"))}
+          {project.suggested_deny.map((rule) => checkbox(deny, setDeny, rule, "danger"))}
+          <div className="flex items-center gap-3 pt-1">
+            <button
+              onClick={() => run(false)}
+              disabled={allow.length + deny.length === 0}
+              className="px-3 py-1.5 rounded text-xs font-semibold border border-border text-muted hover:text-foreground transition-all cursor-pointer disabled:opacity-50"
+            >
+              Preview settings.json
+            </button>
+            <button
+              onClick={() => run(true)}
+              disabled={!preview || preview.added === 0}
+              className="px-3 py-1.5 rounded text-xs font-semibold bg-transparent border border-primary/30 text-primary hover:bg-primary/10 hover:border-primary transition-all cursor-pointer disabled:opacity-50"
+            >
+              Apply
+            </button>
+            {status && <span className="text-muted-subtle">{status}</span>}
+          </div>
+        </div>
+      )}
+
+      {error && <p className="text-danger">{error}</p>}
+      {preview && (
+        <div>
+          <p className="text-muted-subtle mb-1">
+            {preview.settings_path}
+            {preview.current === null && " (new file)"}
+          </p>
+          <pre className="overflow-x-auto max-h-80 p-2 rounded bg-card border border-border-subtle">
+            {(preview.added > 0 ? preview.diff : preview.proposed).split("\n").map((line, i) => (
+              <div key={i} className={lineClass(line)}>
+                {line || " "}
+              </div>
+            ))}
+          </pre>
+        </div>
+      )}
+    </div>
+  );
+}
+
+export function PermissionsPanel({ options }: PermissionsPanelProps) {
+  const [report, setReport] = useState<PermissionReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const key = JSON.stringify({ project: options?.project, since: options?.since, until: options?.until });
+
+  useEffect(() => {
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+    let cancelled = false;
+    setReport(null);
+    setError(null);
+
+    permissionAnalysis(claudeDir, JSON.parse(key), useWsl, wslDistro || undefined)
+      .then((result) => {
+        if (!cancelled) setReport(result);
+      })
+      .catch((e) => {
+        if (!cancelled) setError(e instanceof Error ? e.message : String(e));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [key]);
+
+  if (error) {
+    return <p className="text-xs text-danger px-1 py-2">{error}</p>;
+  }
+
+  if (!report) {
+    return (
+      <div className="flex justify-center py-3">
+        <Spinner size="sm" />
+      </div>
+    );
+  }
+
+  if (report.projects.length === 0) {
+    return <p className="text-xs text-muted-subtle font-mono px-1 py-2">No permission prompts recorded</p>;
+  }
+
+  return (
+    <div className="text-xs font-mono space-y-3">
+      {report.projects.map((project) => (
+        <ProjectCard key={`${key}:${project.project_path}`} project={project} />
+      ))}
+    </div>
+  );
+}
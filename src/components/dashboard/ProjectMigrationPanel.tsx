@@ -0,0 +1,140 @@
+import { useState } from "react";
+import { ask } from "@tauri-apps/plugin-dialog";
+import { Icon } from "@/components/ui/Icon";
+import { Spinner } from "@/components/ui/Spinner";
+import { Badge } from "@/components/ui/Badge";
+import { migrateProject } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { useSessionStore } from "@/stores/useSessionStore";
+import { useStatsStore } from "@/stores/useStatsStore";
+import { formatNumber } from "@/utils/formatters";
+import type { ProjectMigrationReport } from "@/types/session";
+
+const inputClass =
+  "flex-1 min-w-[16rem] px-2 py-1 bg-input-bg border border-border-subtle rounded text-xs text-foreground";
+
+export function ProjectMigrationPanel({ projectPaths }: { projectPaths: string[] }) {
+  const fetchSessions = useSessionStore((s) => s.fetch);
+  const fetchStats = useStatsStore((s) => s.fetch);
+  const [oldPath, setOldPath] = useState("");
+  const [newPath, setNewPath] = useState("");
+  const [report, setReport] = useState<ProjectMigrationReport | null>(null);
+  const [isRunning, setIsRunning] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  // Qualquer mudanca nos paths invalida o relatorio anterior
+  const update = (setter: (v: string) => void) => (value: string) => {
+    setter(value);
+    setReport(null);
+    setError(null);
+  };
+
+  const run = async (dryRun: boolean) => {
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+    if (!dryRun && report) {
+      const confirmed = await ask(
+        `Move ${report.moves.length} project folders from ${oldPath} to ${newPath}? A backup is taken first.`,
+        { title: "Migrate project", kind: "warning" },
+      );
+      if (!confirmed) return;
+    }
+
+    setIsRunning(true);
+    setError(null);
+    try {
+      const result = await migrateProject(
+        claudeDir,
+        { old_path: oldPath, new_path: newPath, dry_run: dryRun },
+        useWsl,
+        wslDistro || undefined,
+      );
+      setReport(result);
+      if (!dryRun) await Promise.all([fetchSessions(), fetchStats()]);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setIsRunning(false);
+    }
+  };
+
+  const summary = (r: ProjectMigrationReport) => {
+    const sessions = r.moves.reduce((sum, m) => sum + m.sessions, 0);
+    const entries = r.moves.reduce((sum, m) => sum + m.rewritten_entries, 0);
+    const failed = r.errors.length > 0 ? ` · ${r.errors.length} failed` : "";
+    const verb = r.dry_run ? "would move" : "moved";
+    return `${formatNumber(sessions)} sessions in ${r.moves.length} folders ${verb}, ${formatNumber(entries)} cwd entries, ${r.config_keys.length} .claude.json keys${failed}`;
+  };
+
+  return (
+    <div className="text-xs font-mono text-muted-subtle space-y-3">
+      <div className="flex flex-wrap items-center gap-3">
+        <input
+          list="project-migration-paths"
+          value={oldPath}
+          onChange={(e) => update(setOldPath)(e.target.value)}
+          placeholder="Old project path"
+          className={inputClass}
+        />
+        <datalist id="project-migration-paths">
+          {projectPaths.map((p) => (
+            <option key={p} value={p} />
+          ))}
+        </datalist>
+        <Icon name="arrow_forward" size="sm" />
+        <input
+          value={newPath}
+          onChange={(e) => update(setNewPath)(e.target.value)}
+          placeholder="New path"
+          className={inputClass}
+        />
+        <button
+          onClick={() => run(true)}
+          disabled={isRunning || !oldPath.trim() || !newPath.trim()}
+          className="px-3 py-1.5 rounded text-xs font-semibold border border-border text-muted hover:text-foreground transition-all cursor-pointer disabled:opacity-50"
+        >
+          Dry run
+        </button>
+        <button
+          onClick={() => run(false)}
+          disabled={isRunning || !report?.dry_run}
+          className="flex items-center gap-1.5 px-3 py-1.5 rounded text-xs font-semibold bg-transparent border border-danger/30 text-danger hover:bg-danger/10 hover:border-danger transition-all cursor-pointer disabled:opacity-50"
+        >
+          {isRunning ? <Spinner size="sm" /> : <Icon name="drive_file_move" size="sm" />}
+          Migrate
+        </button>
+      </div>
+
+      {error && <p className="text-danger">{error}</p>}
+      {report && (
+        <div className="space-y-2">
+          <p className={report.errors.length > 0 ? "text-danger" : "text-foreground-secondary"}>{summary(report)}</p>
+          {!report.new_path_exists && <p className="text-warning">New path does not exist on disk</p>}
+          {report.old_path_exists && <p className="text-warning">Old path still exists on disk</p>}
+          {report.backup_dir && (
+            <p className="truncate" title={report.backup_dir}>
+              Backup: {report.backup_dir}
+            </p>
+          )}
+          {report.errors.map((e) => (
+            <p key={e} className="text-danger truncate" title={e}>
+              {e}
+            </p>
+          ))}
+          <div className="divide-y divide-border-subtle">
+            {report.moves.map((m) => (
+              <div key={m.old_slug} className="py-1.5 flex items-center gap-3">
+                <span className="truncate flex-1" title={`${m.old_slug} → ${m.new_slug}`}>
+                  {m.old_path} → <span className="text-foreground-secondary">{m.new_path}</span>
+                </span>
+                {m.merge && <Badge variant="muted">merge</Badge>}
+                <span>{formatNumber(m.sessions)} sessions</span>
+                <span className="w-24 text-right">{formatNumber(m.rewritten_entries)} cwd</span>
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
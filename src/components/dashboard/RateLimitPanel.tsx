@@ -0,0 +1,139 @@
+import { useState, useEffect } from "react";
+import { BarChart, Bar, XAxis, YAxis, Tooltip, ResponsiveContainer } from "recharts";
+import { Spinner } from "@/components/ui/Spinner";
+import { Badge } from "@/components/ui/Badge";
+import { listRateLimits } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { formatNumber } from "@/utils/formatters";
+import type { RateLimitHit, RateLimitOptions, RateLimitReport } from "@/types/session";
+
+interface RateLimitPanelProps {
+  options?: RateLimitOptions;
+}
+
+const KIND_LABEL = {
+  usage_limit: "usage limit",
+  rate_limit: "429",
+} as const;
+
+function formatDateTime(isoDate: string): string {
+  return new Date(isoDate).toLocaleString("en-US", {
+    month: "short",
+    day: "numeric",
+    hour: "numeric",
+    minute: "2-digit",
+  });
+}
+
+function resetLabel(hit: RateLimitHit): string | null {
+  if (hit.reset_at) return `resets ${formatDateTime(hit.reset_at)}`;
+  if (hit.reset_hint) return `resets ${hit.reset_hint}`;
+  return null;
+}
+
+export function RateLimitPanel({ options }: RateLimitPanelProps) {
+  const [report, setReport] = useState<RateLimitReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const key = JSON.stringify({ project: options?.project, since: options?.since, until: options?.until });
+
+  useEffect(() => {
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+    let cancelled = false;
+    setReport(null);
+    setError(null);
+
+    listRateLimits(claudeDir, JSON.parse(key), useWsl, wslDistro || undefined)
+      .then((result) => {
+        if (!cancelled) setReport(result);
+      })
+      .catch((e) => {
+        if (!cancelled) setError(e instanceof Error ? e.message : String(e));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [key]);
+
+  if (error) {
+    return <p className="text-xs text-danger px-1 py-2">{error}</p>;
+  }
+
+  if (!report) {
+    return (
+      <div className="flex justify-center py-3">
+        <Spinner size="sm" />
+      </div>
+    );
+  }
+
+  if (report.hits.length === 0) {
+    return <p className="text-xs text-muted-subtle font-mono px-1 py-2">No limits hit</p>;
+  }
+
+  // Hora local em que os bloqueios acontecem
+  const hours = Array.from({ length: 24 }, (_, hour) => ({ hour, hits: 0 }));
+  for (const hit of report.hits) {
+    hours[new Date(hit.timestamp).getHours()].hits += 1;
+  }
+
+  const axis = { tick: { fontSize: 9, fill: "var(--color-muted-subtle)" }, tickLine: false, axisLine: false };
+
+  return (
+    <div className="text-xs font-mono space-y-4">
+      <p className="text-muted-subtle">
+        {formatNumber(report.usage_limits)} usage limits · {formatNumber(report.rate_limits)} rate limits (429)
+      </p>
+
+      <div className="grid md:grid-cols-2 gap-4">
+        <div>
+          <p className="text-muted-subtle uppercase tracking-wider text-[10px] mb-1">Per day</p>
+          <div className="h-[120px]">
+            <ResponsiveContainer width="100%" height="100%">
+              <BarChart data={report.days} margin={{ top: 4, right: 4, bottom: 0, left: -20 }}>
+                <XAxis dataKey="date" {...axis} interval="preserveStartEnd" />
+                <YAxis {...axis} width={30} allowDecimals={false} />
+                <Tooltip cursor={false} contentStyle={{ fontSize: 11 }} />
+                <Bar dataKey="usage_limits" name="usage limits" stackId="hits" fill="var(--color-danger)" barSize={6} />
+                <Bar dataKey="rate_limits" name="rate limits" stackId="hits" fill="var(--color-warning)" barSize={6} />
+              </BarChart>
+            </ResponsiveContainer>
+          </div>
+        </div>
+        <div>
+          <p className="text-muted-subtle uppercase tracking-wider text-[10px] mb-1">By hour of day</p>
+          <div className="h-[120px]">
+            <ResponsiveContainer width="100%" height="100%">
+              <BarChart data={hours} margin={{ top: 4, right: 4, bottom: 0, left: -20 }}>
+                <XAxis dataKey="hour" {...axis} interval={3} />
+                <YAxis {...axis} width={30} allowDecimals={false} />
+                <Tooltip cursor={false} contentStyle={{ fontSize: 11 }} />
+                <Bar dataKey="hits" fill="var(--color-primary)" radius={[3, 3, 0, 0]} barSize={6} />
+              </BarChart>
+            </ResponsiveContainer>
+          </div>
+        </div>
+      </div>
+
+      <div className="divide-y divide-border-subtle max-h-80 overflow-y-auto">
+        {report.hits.map((hit) => (
+          <div key={`${hit.subagent_path ?? hit.file_path}:${hit.entry_index}`} className="py-2 flex items-start gap-3">
+            <Badge variant={hit.kind === "usage_limit" ? "danger" : "muted"}>{KIND_LABEL[hit.kind]}</Badge>
+            <div className="flex-1 min-w-0">
+              <p className="text-foreground-secondary truncate" title={hit.message}>
+                {hit.message}
+              </p>
+              <p className="text-muted-subtle mt-0.5 truncate">
+                {formatDateTime(hit.timestamp)} · {hit.project_display} · {hit.session_id.slice(0, 8)}
+                {hit.subagent_path && " · subagent"}
+                {hit.model && ` · ${hit.model}`}
+              </p>
+            </div>
+            {resetLabel(hit) && <span className="shrink-0 text-muted">{resetLabel(hit)}</span>}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
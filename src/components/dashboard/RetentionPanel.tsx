@@ -0,0 +1,177 @@
+import { useState } from "react";
+import { ask } from "@tauri-apps/plugin-dialog";
+import { Icon } from "@/components/ui/Icon";
+import { Spinner } from "@/components/ui/Spinner";
+import { Badge } from "@/components/ui/Badge";
+import { SegmentedControl } from "@/components/ui/SegmentedControl";
+import { applyRetentionPolicy } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { useSessionStore } from "@/stores/useSessionStore";
+import { formatBytes, formatRelativeTime } from "@/utils/formatters";
+import type { RetentionAction, RetentionPolicy, RetentionReason, RetentionReport } from "@/types/session";
+
+const ACTIONS: Array<{ label: string; value: RetentionAction }> = [
+  { label: "Trash", value: "trash" },
+  { label: "Archive", value: "archive" },
+];
+
+const REASON_LABEL: Record<RetentionReason, string> = {
+  expired: "expired",
+  missing_project: "project gone",
+};
+
+// Candidatos exibidos no relatorio
+const MAX_CANDIDATES = 50;
+
+export function RetentionPanel() {
+  const fetchSessions = useSessionStore((s) => s.fetch);
+  const [useCleanupPeriod, setUseCleanupPeriod] = useState(true);
+  const [olderThanDays, setOlderThanDays] = useState(90);
+  const [missingProjects, setMissingProjects] = useState(false);
+  const [action, setAction] = useState<RetentionAction>("trash");
+  const [report, setReport] = useState<RetentionReport | null>(null);
+  const [isRunning, setIsRunning] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const policy: RetentionPolicy = {
+    use_cleanup_period: useCleanupPeriod,
+    older_than_days: useCleanupPeriod ? undefined : Math.max(olderThanDays, 0),
+    missing_projects: missingProjects,
+    action,
+  };
+
+  // Qualquer mudanca na politica invalida o relatorio anterior
+  const update = <T,>(setter: (v: T) => void) => (value: T) => {
+    setter(value);
+    setReport(null);
+  };
+
+  const run = async (dryRun: boolean) => {
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+    if (!dryRun && report) {
+      const verb = action === "trash" ? "Move" : "Archive";
+      const target = action === "trash" ? " to the trash" : "";
+      const confirmed = await ask(`${verb} ${report.candidates.length} sessions${target}?`, {
+        title: "Apply retention policy",
+        kind: "warning",
+      });
+      if (!confirmed) return;
+    }
+
+    setIsRunning(true);
+    setError(null);
+    try {
+      const result = await applyRetentionPolicy(
+        claudeDir,
+        { ...policy, dry_run: dryRun },
+        useWsl,
+        wslDistro || undefined,
+      );
+      setReport(result);
+      if (!dryRun) await fetchSessions();
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setIsRunning(false);
+    }
+  };
+
+  const summary = (r: RetentionReport) => {
+    if (!r.dry_run) {
+      const failed = r.errors.length > 0 ? ` · ${r.errors.length} failed` : "";
+      return `${r.action === "trash" ? "Deleted" : "Archived"} ${r.processed} sessions, freed ${formatBytes(r.freed_bytes)}${failed}`;
+    }
+    if (r.candidates.length === 0) return "Nothing matches this policy";
+    const freed = r.action === "trash" ? formatBytes(r.total_bytes) : `up to ${formatBytes(r.total_bytes)}`;
+    return `${r.candidates.length} sessions would be ${r.action === "trash" ? "deleted" : "archived"}, freeing ${freed}`;
+  };
+
+  return (
+    <div className="text-xs font-mono text-muted-subtle space-y-3">
+      <div className="flex flex-wrap items-center gap-4">
+        <SegmentedControl options={ACTIONS} value={action} onChange={update(setAction)} />
+        <label className="flex items-center gap-1.5 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={useCleanupPeriod}
+            onChange={(e) => update(setUseCleanupPeriod)(e.target.checked)}
+          />
+          cleanupPeriodDays{report && ` (${report.cleanup_period_days})`}
+        </label>
+        {!useCleanupPeriod && (
+          <label className="flex items-center gap-1.5">
+            Older than
+            <input
+              type="number"
+              min={0}
+              value={olderThanDays}
+              onChange={(e) => update(setOlderThanDays)(Number(e.target.value) || 0)}
+              className="w-16 px-2 py-1 bg-input-bg border border-border-subtle rounded text-xs text-foreground"
+            />
+            days
+          </label>
+        )}
+        <label className="flex items-center gap-1.5 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={missingProjects}
+            onChange={(e) => update(setMissingProjects)(e.target.checked)}
+          />
+          Missing project folders
+        </label>
+        <button
+          onClick={() => run(true)}
+          disabled={isRunning}
+          className="px-3 py-1.5 rounded text-xs font-semibold border border-border text-muted hover:text-foreground transition-all cursor-pointer disabled:opacity-50"
+        >
+          Dry run
+        </button>
+        <button
+          onClick={() => run(false)}
+          disabled={isRunning || !report?.dry_run || report.candidates.length === 0}
+          className="flex items-center gap-1.5 px-3 py-1.5 rounded text-xs font-semibold bg-transparent border border-danger/30 text-danger hover:bg-danger/10 hover:border-danger transition-all cursor-pointer disabled:opacity-50"
+        >
+          {isRunning ? <Spinner size="sm" /> : <Icon name={action === "trash" ? "delete" : "inventory_2"} size="sm" />}
+          Apply
+        </button>
+      </div>
+
+      {error && <p className="text-danger">{error}</p>}
+      {report && (
+        <div className="space-y-2">
+          <p className={report.errors.length > 0 ? "text-danger" : "text-foreground-secondary"}>
+            {summary(report)}
+          </p>
+          {report.errors.map((e) => (
+            <p key={e} className="text-danger truncate" title={e}>
+              {e}
+            </p>
+          ))}
+          {report.dry_run && report.candidates.length > 0 && (
+            <div className="divide-y divide-border-subtle max-h-64 overflow-y-auto">
+              {report.candidates.slice(0, MAX_CANDIDATES).map((c) => (
+                <div key={c.file_path} className="py-1.5 flex items-center gap-3">
+                  <span className="truncate flex-1" title={c.files.join("\n")}>
+                    {c.project_display} · {c.session_id.slice(0, 8)}
+                  </span>
+                  {c.archived && <Badge variant="muted">archived</Badge>}
+                  {c.reasons.map((r) => (
+                    <Badge key={r} variant={r === "expired" ? "default" : "danger"}>
+                      {REASON_LABEL[r]}
+                    </Badge>
+                  ))}
+                  <span className="w-20 text-right">{formatRelativeTime(c.last_timestamp)}</span>
+                  <span className="w-16 text-right text-foreground-secondary">{formatBytes(c.bytes)}</span>
+                </div>
+              ))}
+              {report.candidates.length > MAX_CANDIDATES && (
+                <p className="py-1.5">+{report.candidates.length - MAX_CANDIDATES} more</p>
+              )}
+            </div>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,28 +1,82 @@
 import { useState, useEffect } from "react";
 import { Icon } from "@/components/ui/Icon";
 import { Modal } from "@/components/ui/Modal";
+import { Badge } from "@/components/ui/Badge";
 import { ConversationViewer } from "./ConversationViewer";
+import { SubagentPanel } from "./SubagentPanel";
+import { FilesPanel } from "./FilesPanel";
+import { DiffPanel } from "./DiffPanel";
+import { ToolAnalyticsPanel } from "./ToolAnalyticsPanel";
+import { TimingPanel } from "./TimingPanel";
+import { ContextPanel } from "./ContextPanel";
+import { ExportPanel } from "./ExportPanel";
+import { TrashButton } from "./TrashButton";
+import { forkSession, restoreSession } from "@/services/api";
 import { useSessionStore } from "@/stores/useSessionStore";
+import { useConnectionStore } from "@/stores/useConnectionStore";
 import { useConversation } from "@/hooks/useConversation";
-import { formatRelativeTime } from "@/utils/formatters";
+import { formatRelativeTime, formatTokenCost, formatDuration } from "@/utils/formatters";
 import type { SessionMeta } from "@/types/session";
 
 interface SessionItemProps {
   session: SessionMeta;
+  // Selecao para exportacao em lote
+  selected: boolean;
+  onToggleSelect: () => void;
 }
 
-export function SessionItem({ session }: SessionItemProps) {
+export function SessionItem({ session, selected, onToggleSelect }: SessionItemProps) {
   const resume = useSessionStore((s) => s.resume);
+  const fetchSessions = useSessionStore((s) => s.fetch);
+  const [restoreError, setRestoreError] = useState<string | null>(null);
+  const [forkStatus, setForkStatus] = useState<string | null>(null);
   const [modalOpen, setModalOpen] = useState(false);
-  const { entries, isLoading, error, load, reset } = useConversation();
+  const [showSubagents, setShowSubagents] = useState(false);
+  const [showFiles, setShowFiles] = useState(false);
+  const [showDiff, setShowDiff] = useState(false);
+  const [showTools, setShowTools] = useState(false);
+  const [showContext, setShowContext] = useState(false);
+  const [showTiming, setShowTiming] = useState(false);
+  const conversation = useConversation();
+  const { load, reset } = conversation;
+
+  // Sessao arquivada volta a ser .jsonl antes de poder ser retomada
+  const handleRestore = async () => {
+    const { useWsl, wslDistro } = useConnectionStore.getState();
+    setRestoreError(null);
+    try {
+      await restoreSession(session.file_path, useWsl, wslDistro || undefined);
+      await fetchSessions();
+    } catch (e) {
+      setRestoreError(e instanceof Error ? e.message : String(e));
+    }
+  };
+
+  // Nova sessao ate o entry escolhido, retomada no terminal; a original nao muda
+  const handleFork = async (uuid: string) => {
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+    setForkStatus(null);
+    let forked = "";
+    try {
+      const fork = await forkSession(claudeDir, session.file_path, uuid, useWsl, wslDistro || undefined);
+      forked = `Forked ${fork.entries} entries into ${fork.session_id.slice(0, 8)}`;
+      setForkStatus(forked);
+      await fetchSessions();
+      await resume(fork.session_id, session.project_path);
+    } catch (e) {
+      const message = e instanceof Error ? e.message : String(e);
+      setForkStatus(forked ? `${forked}, but resume failed: ${message}` : message);
+    }
+  };
 
   useEffect(() => {
     if (modalOpen) {
-      load(session.id, session.project_path);
+      load(session.file_path);
     } else {
       reset();
     }
-  }, [modalOpen, session.id, session.project_path, load, reset]);
+  }, [modalOpen, session.file_path, load, reset]);
 
   return (
     <>
@@ -30,24 +84,49 @@ export function SessionItem({ session }: SessionItemProps) {
         {/* Diretorio */}
         <div className="col-span-4 min-w-0">
           <div className="flex items-center gap-3">
+            <input
+              type="checkbox"
+              checked={selected}
+              onChange={onToggleSelect}
+              aria-label="Select session"
+              className="cursor-pointer"
+            />
             <div className="w-9 h-9 rounded bg-surface border border-border-subtle flex items-center justify-center text-muted-subtle group-hover:text-primary transition-colors">
               <Icon name="code" size="sm" />
             </div>
-            <div className="truncate">
+            <div className="truncate" title={session.models.join(" → ")}>
               <p className="text-sm font-medium text-foreground-secondary font-mono truncate group-hover:text-foreground transition-colors">
                 {session.project_display}
               </p>
               <p className="text-xs text-muted-subtle mt-0.5">
-                {session.message_count} messages
+                {session.message_count} messages · {formatTokenCost(session.tokens)} ·{" "}
+                {formatDuration(session.duration_ms)}
+                {session.subagent_count > 0 && (
+                  <>
+                    {" · "}
+                    <button
+                      onClick={() => {
+                        setShowSubagents(true);
+                        setModalOpen(true);
+                      }}
+                      className="hover:text-primary transition-colors cursor-pointer"
+                    >
+                      {session.subagent_count} subagents
+                    </button>
+                  </>
+                )}
               </p>
             </div>
           </div>
         </div>
 
         {/* Resumo */}
-        <div className="col-span-4">
+        <div className="col-span-4 flex items-center gap-2 min-w-0">
+          {session.archived && <Badge variant="muted">archived</Badge>}
           <span className="inline-flex items-center px-2.5 py-1 rounded text-xs font-medium bg-surface text-foreground-secondary border border-border-subtle max-w-full">
-            <span className="truncate">{session.summary}</span>
+            <span className="truncate" title={session.first_prompt ?? undefined}>
+              {session.summary}
+            </span>
           </span>
         </div>
 
@@ -58,31 +137,119 @@ export function SessionItem({ session }: SessionItemProps) {
 
         {/* Acoes */}
         <div className="col-span-2 flex justify-end gap-2">
+          <TrashButton sessions={[session]} compact />
           <button
             onClick={() => setModalOpen(true)}
             className="flex items-center gap-1.5 px-3 py-1.5 rounded text-xs font-semibold font-mono bg-transparent border border-border-subtle text-muted hover:text-foreground hover:border-muted-subtle hover:bg-foreground/5 transition-all cursor-pointer"
           >
             <Icon name="visibility" size="sm" /> View
           </button>
-          <button
-            onClick={() => resume(session.id, session.project_path)}
-            className="flex items-center gap-2 px-3 py-1.5 rounded text-xs font-semibold font-mono bg-transparent border border-primary/30 text-primary hover:bg-primary/10 hover:border-primary hover:shadow-[0_0_10px_rgba(16,185,129,0.2)] transition-all cursor-pointer"
-          >
-            <span className="text-[10px]">&gt;_</span> Resume
-          </button>
+          {session.archived ? (
+            <button
+              onClick={handleRestore}
+              title={restoreError ?? "Decompress back to .jsonl"}
+              className={`flex items-center gap-1.5 px-3 py-1.5 rounded text-xs font-semibold font-mono bg-transparent border transition-all cursor-pointer ${
+                restoreError
+                  ? "border-danger/30 text-danger"
+                  : "border-primary/30 text-primary hover:bg-primary/10 hover:border-primary"
+              }`}
+            >
+              <Icon name="unarchive" size="sm" /> Restore
+            </button>
+          ) : (
+            <button
+              onClick={() => resume(session.id, session.project_path)}
+              className="flex items-center gap-2 px-3 py-1.5 rounded text-xs font-semibold font-mono bg-transparent border border-primary/30 text-primary hover:bg-primary/10 hover:border-primary hover:shadow-[0_0_10px_rgba(16,185,129,0.2)] transition-all cursor-pointer"
+            >
+              <span className="text-[10px]">&gt;_</span> Resume
+            </button>
+          )}
         </div>
       </div>
 
       <Modal
         open={modalOpen}
-        onClose={() => setModalOpen(false)}
+        onClose={() => {
+          setModalOpen(false);
+          setShowSubagents(false);
+          setShowFiles(false);
+          setShowDiff(false);
+          setShowTools(false);
+          setShowContext(false);
+          setShowTiming(false);
+          setForkStatus(null);
+        }}
         title={session.project_display}
         maxWidth="lg"
       >
+        {session.subagent_count > 0 && (
+          <button
+            onClick={() => setShowSubagents((v) => !v)}
+            className="mb-2 text-xs font-mono text-muted-subtle hover:text-foreground transition-colors cursor-pointer"
+          >
+            {showSubagents ? "Hide" : "Show"} {session.subagent_count} subagents
+          </button>
+        )}
+        {showSubagents && <SubagentPanel sessionPath={session.file_path} />}
+        <button
+          onClick={() => setShowFiles((v) => !v)}
+          className="mb-2 ml-4 text-xs font-mono text-muted-subtle hover:text-foreground transition-colors cursor-pointer"
+        >
+          {showFiles ? "Hide" : "Show"} files
+        </button>
+        <button
+          onClick={() => setShowDiff((v) => !v)}
+          className="mb-2 ml-4 text-xs font-mono text-muted-subtle hover:text-foreground transition-colors cursor-pointer"
+        >
+          {showDiff ? "Hide" : "Show"} changes
+        </button>
+        <button
+          onClick={() => setShowTools((v) => !v)}
+          className="mb-2 ml-4 text-xs font-mono text-muted-subtle hover:text-foreground transition-colors cursor-pointer"
+        >
+          {showTools ? "Hide" : "Show"} tools
+        </button>
+        <button
+          onClick={() => setShowContext((v) => !v)}
+          className="mb-2 ml-4 text-xs font-mono text-muted-subtle hover:text-foreground transition-colors cursor-pointer"
+        >
+          {showContext ? "Hide" : "Show"} context
+        </button>
+        <button
+          onClick={() => setShowTiming((v) => !v)}
+          className="mb-2 ml-4 text-xs font-mono text-muted-subtle hover:text-foreground transition-colors cursor-pointer"
+        >
+          {showTiming ? "Hide" : "Show"} timing
+        </button>
+        {showFiles && <FilesPanel sessionPath={session.file_path} />}
+        {showDiff && <DiffPanel sessionPath={session.file_path} sessionId={session.id} />}
+        {showContext && <ContextPanel sessionPath={session.file_path} />}
+        {showTools && (
+          <div className="mb-4 rounded border border-border-subtle bg-surface p-3">
+            <ToolAnalyticsPanel options={{ session_path: session.file_path }} />
+          </div>
+        )}
+        {showTiming && (
+          <div className="mb-4 rounded border border-border-subtle bg-surface p-3">
+            <TimingPanel options={{ session_path: session.file_path }} />
+          </div>
+        )}
+        <div className="mb-4">
+          <ExportPanel sessions={[session]} />
+        </div>
+        {forkStatus && <p className="mb-2 text-xs font-mono text-muted-subtle">{forkStatus}</p>}
         <ConversationViewer
-          entries={entries}
-          isLoading={isLoading}
-          error={error}
+          entries={conversation.entries}
+          startIndex={conversation.startIndex}
+          totalEntries={conversation.totalEntries}
+          hasMore={conversation.hasMore}
+          branches={conversation.branches}
+          isLoading={conversation.isLoading}
+          isLoadingMore={conversation.isLoadingMore}
+          error={conversation.error}
+          onLoadMore={conversation.loadMore}
+          onExpandBlock={conversation.expandBlock}
+          onFork={handleFork}
         />
       </Modal>
     </>
@@ -4,17 +4,58 @@ import { Spinner } from "@/components/ui/Spinner";
 import { Badge } from "@/components/ui/Badge";
 import { SkeletonSessionRow } from "@/components/ui/Skeleton";
 import { SessionItem } from "./SessionItem";
+import { ExportPanel } from "./ExportPanel";
+import { ArchivePanel } from "./ArchivePanel";
+import { RetentionPanel } from "./RetentionPanel";
+import { TrashButton } from "./TrashButton";
 import { useSessionStore } from "@/stores/useSessionStore";
-import type { SessionMeta } from "@/types/session";
+import type { SessionMeta, SessionSortKey } from "@/types/session";
 
 const PAGE_SIZE = 5;
 
+const SORT_OPTIONS: Array<{ label: string; value: SessionSortKey }> = [
+  { label: "Last active", value: "last_active" },
+  { label: "Started", value: "started" },
+  { label: "Cost", value: "cost" },
+  { label: "Tokens", value: "tokens" },
+  { label: "Duration", value: "duration" },
+  { label: "Messages", value: "messages" },
+];
+
 export function SessionList() {
-  const { sessions, isLoading, searchResults, isSearching, searchInContent, clearSearch } =
-    useSessionStore();
+  const {
+    sessions,
+    isLoading,
+    searchResults,
+    searchTotal,
+    searchTruncated,
+    searchCursor,
+    isSearching,
+    searchInContent,
+    loadMoreResults,
+    clearSearch,
+    listOptions,
+    setListOptions,
+    fetch,
+  } = useSessionStore();
   const [filter, setFilter] = useState("");
   const [debouncedFilter, setDebouncedFilter] = useState("");
   const [page, setPage] = useState(0);
+  // Sessoes selecionadas para exportacao, por file_path
+  const [selected, setSelected] = useState<Map<string, SessionMeta>>(new Map());
+  const [showCleanup, setShowCleanup] = useState(false);
+
+  const toggleSelected = (session: SessionMeta) => {
+    setSelected((prev) => {
+      const next = new Map(prev);
+      if (next.has(session.file_path)) {
+        next.delete(session.file_path);
+      } else {
+        next.set(session.file_path, session);
+      }
+      return next;
+    });
+  };
 
   // Debounce para busca backend
   useEffect(() => {
@@ -40,7 +81,8 @@ export function SessionList() {
       ? sessions.filter(
           (s) =>
             s.project_path.toLowerCase().includes(filterLower) ||
-            s.summary.toLowerCase().includes(filterLower),
+            s.summary.toLowerCase().includes(filterLower) ||
+            (s.first_prompt?.toLowerCase().includes(filterLower) ?? false),
         )
       : sessions;
 
@@ -83,36 +125,108 @@ export function SessionList() {
             <span className="text-primary/80">~/.claude</span>
           </p>
         </div>
-        <div className="relative w-full sm:w-auto group">
-          {isSearching ? (
-            <div className="absolute left-3 top-2.5">
-              <Spinner size="sm" />
-            </div>
-          ) : (
-            <Icon
-              name="search"
-              size="sm"
-              className="absolute left-3 top-2.5 text-muted-subtle group-focus-within:text-primary transition-colors"
-            />
-          )}
-          <input
-            className="pl-10 pr-4 py-2 bg-input-bg border border-border-subtle rounded-lg text-sm text-foreground focus:outline-none focus:border-primary/50 focus:ring-1 focus:ring-primary/50 w-full sm:w-72 placeholder-muted-subtle transition-all"
-            placeholder="Search sessions and content..."
-            value={filter}
+        <div className="flex items-center gap-2 w-full sm:w-auto">
+          {/* Ordenacao feita pelo backend */}
+          <select
+            value={listOptions.sort_by ?? "last_active"}
             onChange={(e) => {
-              setFilter(e.target.value);
+              setListOptions({ ...listOptions, sort_by: e.target.value as SessionSortKey });
               setPage(0);
             }}
-          />
+            className="py-2 px-2 bg-input-bg border border-border-subtle rounded-lg text-xs text-foreground-secondary font-mono focus:outline-none focus:border-primary/50 cursor-pointer"
+          >
+            {SORT_OPTIONS.map((opt) => (
+              <option key={opt.value} value={opt.value}>
+                {opt.label}
+              </option>
+            ))}
+          </select>
+          <button
+            onClick={() => setListOptions({ ...listOptions, ascending: !listOptions.ascending })}
+            title={listOptions.ascending ? "Ascending" : "Descending"}
+            className="p-2 rounded-lg border border-border-subtle text-muted-subtle hover:text-foreground cursor-pointer"
+          >
+            <Icon name={listOptions.ascending ? "arrow_upward" : "arrow_downward"} size="sm" />
+          </button>
+          <button
+            onClick={() => setShowCleanup((v) => !v)}
+            title="Archive and clean up old sessions"
+            className={`p-2 rounded-lg border border-border-subtle hover:text-foreground cursor-pointer ${
+              showCleanup ? "text-primary" : "text-muted-subtle"
+            }`}
+          >
+            <Icon name="inventory_2" size="sm" />
+          </button>
+          <div className="relative w-full sm:w-auto group">
+            {isSearching ? (
+              <div className="absolute left-3 top-2.5">
+                <Spinner size="sm" />
+              </div>
+            ) : (
+              <Icon
+                name="search"
+                size="sm"
+                className="absolute left-3 top-2.5 text-muted-subtle group-focus-within:text-primary transition-colors"
+              />
+            )}
+            <input
+              className="pl-10 pr-4 py-2 bg-input-bg border border-border-subtle rounded-lg text-sm text-foreground focus:outline-none focus:border-primary/50 focus:ring-1 focus:ring-primary/50 w-full sm:w-72 placeholder-muted-subtle transition-all"
+              placeholder="Search sessions and content..."
+              value={filter}
+              onChange={(e) => {
+                setFilter(e.target.value);
+                setPage(0);
+              }}
+            />
+          </div>
         </div>
       </div>
 
       {/* Info de resultados de conteudo */}
       {searchResults.length > 0 && filter.length >= 2 && (
-        <div className="px-6 py-2 bg-primary/5 border-b border-border-subtle">
+        <div className="px-6 py-2 bg-primary/5 border-b border-border-subtle flex items-center justify-between">
           <span className="text-xs text-primary font-mono">
-            Found in {searchResults.length} session{searchResults.length !== 1 ? "s" : ""} content
+            Found in {searchTotal} session{searchTotal !== 1 ? "s" : ""} content
+            {searchTruncated && " (top matches only, refine the query)"}
           </span>
+          {searchCursor && (
+            <button
+              onClick={() => loadMoreResults()}
+              disabled={isSearching}
+              className="text-xs text-primary font-mono hover:underline cursor-pointer disabled:opacity-50"
+            >
+              Load more
+            </button>
+          )}
+        </div>
+      )}
+
+      {/* Arquivamento por idade e politica de retencao */}
+      {showCleanup && (
+        <div className="px-6 py-2 bg-primary/5 border-b border-border-subtle space-y-3">
+          <ArchivePanel onDone={fetch} />
+          <RetentionPanel />
+        </div>
+      )}
+
+      {/* Exportacao, arquivamento e exclusao das sessoes selecionadas */}
+      {selected.size > 0 && (
+        <div className="px-6 py-2 bg-primary/5 border-b border-border-subtle flex flex-wrap items-center justify-between gap-4">
+          <ExportPanel sessions={[...selected.values()]} onDone={() => setSelected(new Map())} />
+          <ArchivePanel
+            sessions={[...selected.values()]}
+            onDone={() => {
+              setSelected(new Map());
+              fetch();
+            }}
+          />
+          <TrashButton sessions={[...selected.values()]} onDone={() => setSelected(new Map())} />
+          <button
+            onClick={() => setSelected(new Map())}
+            className="text-xs text-muted-subtle font-mono hover:text-foreground cursor-pointer"
+          >
+            Clear selection
+          </button>
         </div>
       )}
 
@@ -139,7 +253,11 @@ export function SessionList() {
         ) : (
           paginated.map((session) => (
             <div key={session.id}>
-              <SessionItem session={session} />
+              <SessionItem
+                session={session}
+                selected={selected.has(session.file_path)}
+                onToggleSelect={() => toggleSelected(session)}
+              />
               {matchMap.has(session.id) && (
                 <div className="px-6 pb-3 -mt-2">
                   <Badge variant="green">
@@ -0,0 +1,96 @@
+import { useState, useEffect } from "react";
+import { Spinner } from "@/components/ui/Spinner";
+import { readSessionSubagents } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { formatTokenCost, formatTokenCount } from "@/utils/formatters";
+import type { SessionSubagents, TokenTotals } from "@/types/session";
+
+interface SubagentPanelProps {
+  sessionPath: string;
+}
+
+function totalTokens(tokens: TokenTotals): number {
+  return (
+    tokens.input_tokens +
+    tokens.output_tokens +
+    tokens.cache_creation_input_tokens +
+    tokens.cache_read_input_tokens
+  );
+}
+
+export function SubagentPanel({ sessionPath }: SubagentPanelProps) {
+  const [data, setData] = useState<SessionSubagents | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const { useWsl, wslDistro } = useConnectionStore.getState();
+    let cancelled = false;
+    setData(null);
+    setError(null);
+
+    readSessionSubagents(sessionPath, useWsl, wslDistro || undefined)
+      .then((result) => {
+        if (!cancelled) setData(result);
+      })
+      .catch((e) => {
+        if (!cancelled) setError(e instanceof Error ? e.message : String(e));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [sessionPath]);
+
+  if (error) {
+    return <p className="text-xs text-danger px-1 py-2">{error}</p>;
+  }
+
+  if (!data) {
+    return (
+      <div className="flex justify-center py-3">
+        <Spinner size="sm" />
+      </div>
+    );
+  }
+
+  return (
+    <div className="mb-4 rounded border border-border-subtle bg-surface p-3 text-xs font-mono">
+      {/* Gasto da sessao vs delegado a subagentes */}
+      <div className="flex gap-6 text-muted-subtle mb-2">
+        <span>
+          Session: {formatTokenCount(totalTokens(data.session_tokens))} tokens ·{" "}
+          {formatTokenCost(data.session_tokens)}
+        </span>
+        <span>
+          Subagents: {formatTokenCount(totalTokens(data.subagent_tokens))} tokens ·{" "}
+          {formatTokenCost(data.subagent_tokens)}
+        </span>
+      </div>
+
+      <div className="space-y-1">
+        {data.by_type.map((group) => (
+          <div key={group.subagent_type}>
+            <div className="flex justify-between text-foreground-secondary">
+              <span>
+                {group.subagent_type} <span className="text-muted-subtle">×{group.runs}</span>
+              </span>
+              <span>
+                {formatTokenCount(totalTokens(group.tokens))} · {formatTokenCost(group.tokens)}
+              </span>
+            </div>
+            {data.runs
+              .filter((run) => run.subagent_type === group.subagent_type)
+              .map((run) => (
+                <div key={run.agent_id} className="flex justify-between pl-4 text-muted-subtle">
+                  <span className="truncate">{run.description || run.agent_id}</span>
+                  <span className="shrink-0 pl-2">
+                    {run.message_count} msgs · {formatTokenCost(run.tokens)}
+                  </span>
+                </div>
+              ))}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,210 @@
+import { useState, useEffect } from "react";
+import { BarChart, Bar, XAxis, YAxis, Tooltip, ResponsiveContainer } from "recharts";
+import { Spinner } from "@/components/ui/Spinner";
+import { sessionTiming } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { formatDuration, formatLatency, formatNumber, formatRelativeTime } from "@/utils/formatters";
+import type { DayTiming, TimingOptions, TimingReport, TimingTotals } from "@/types/session";
+
+interface TimingPanelProps {
+  // Escopo fixo (sessao, projeto, datas); sem ele o painel analisa todas as sessoes
+  options?: TimingOptions;
+}
+
+// Tooltip de um dia do grafico
+function DayTooltip({ active, payload }: { active?: boolean; payload?: Array<{ payload: DayTiming }> }) {
+  if (!active || !payload?.length) return null;
+  const day = payload[0].payload;
+  return (
+    <div className="bg-card border border-border rounded-lg px-3 py-2 shadow-elevation-3 backdrop-blur-lg text-xs">
+      <p className="text-[10px] text-muted-subtle font-mono mb-1">{day.date}</p>
+      <p className="text-foreground font-bold">{formatDuration(day.totals.active_ms)} active</p>
+      <p className="text-muted">
+        {formatNumber(day.totals.turns)} turns · {formatDuration(day.totals.wait_ms)} waiting
+      </p>
+    </div>
+  );
+}
+
+function Totals({ totals }: { totals: TimingTotals }) {
+  const items = [
+    ["Active", formatDuration(totals.active_ms)],
+    ["Idle", formatDuration(totals.idle_ms)],
+    ["Waiting", formatDuration(totals.wait_ms)],
+    ["Turns", formatNumber(totals.turns)],
+    ["Avg latency", formatLatency(totals.avg_latency_ms)],
+    ["Max latency", formatLatency(totals.max_latency_ms)],
+  ];
+  return (
+    <div className="grid grid-cols-3 md:grid-cols-6 gap-3">
+      {items.map(([label, value]) => (
+        <div key={label}>
+          <p className="text-[10px] uppercase tracking-wider text-muted-subtle">{label}</p>
+          <p className="text-foreground font-bold">{value}</p>
+        </div>
+      ))}
+    </div>
+  );
+}
+
+// Linhas de uma tabela de rollup (projeto ou sessao)
+function RollupTable({ title, rows }: { title: string; rows: Array<{ key: string; label: string; totals: TimingTotals }> }) {
+  if (rows.length === 0) return null;
+  return (
+    <div>
+      <p className="text-muted-subtle uppercase tracking-wider text-[10px] mb-1">{title}</p>
+      <table className="w-full">
+        <thead>
+          <tr className="text-[10px] uppercase tracking-wider text-muted-subtle text-left">
+            <th className="py-1.5 font-semibold">Name</th>
+            <th className="py-1.5 font-semibold text-right">Active</th>
+            <th className="py-1.5 font-semibold text-right">Waiting</th>
+            <th className="py-1.5 font-semibold text-right">Turns</th>
+            <th className="py-1.5 font-semibold text-right">Avg latency</th>
+          </tr>
+        </thead>
+        <tbody className="divide-y divide-border-subtle">
+          {rows.map((row) => (
+            <tr key={row.key} className="text-foreground-secondary">
+              <td className="py-1.5 pr-2 truncate max-w-[240px]" title={row.key}>
+                {row.label}
+              </td>
+              <td className="py-1.5 text-right">{formatDuration(row.totals.active_ms)}</td>
+              <td className="py-1.5 text-right">{formatDuration(row.totals.wait_ms)}</td>
+              <td className="py-1.5 text-right">{formatNumber(row.totals.turns)}</td>
+              <td className="py-1.5 text-right">{formatLatency(row.totals.avg_latency_ms)}</td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+}
+
+export function TimingPanel({ options }: TimingPanelProps) {
+  const [report, setReport] = useState<TimingReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [idleMinutes, setIdleMinutes] = useState(5);
+  const key = JSON.stringify({ ...options, idle_threshold_secs: Math.max(idleMinutes, 0) * 60 });
+
+  useEffect(() => {
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+    let cancelled = false;
+    setReport(null);
+    setError(null);
+
+    sessionTiming(claudeDir, JSON.parse(key), useWsl, wslDistro || undefined)
+      .then((result) => {
+        if (!cancelled) setReport(result);
+      })
+      .catch((e) => {
+        if (!cancelled) setError(e instanceof Error ? e.message : String(e));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [key]);
+
+  const threshold = (
+    <label className="flex items-center gap-1.5 text-muted-subtle">
+      Idle after
+      <input
+        type="number"
+        min={1}
+        value={idleMinutes}
+        onChange={(e) => setIdleMinutes(Number(e.target.value) || 1)}
+        className="w-14 px-2 py-1 bg-input-bg border border-border-subtle rounded text-xs text-foreground"
+      />
+      min
+    </label>
+  );
+
+  if (error) {
+    return <p className="text-xs text-danger px-1 py-2">{error}</p>;
+  }
+
+  if (!report) {
+    return (
+      <div className="flex justify-center py-3">
+        <Spinner size="sm" />
+      </div>
+    );
+  }
+
+  const single = report.sessions.length === 1 && report.sessions[0].turns.length > 0;
+
+  return (
+    <div className="text-xs font-mono space-y-4">
+      <div className="flex items-center gap-3">
+        <p className="text-muted-subtle">{formatNumber(report.totals.sessions)} sessions</p>
+        <span className="ml-auto">{threshold}</span>
+      </div>
+
+      {report.totals.turns === 0 && report.totals.active_ms === 0 ? (
+        <p className="text-muted-subtle">No activity recorded</p>
+      ) : (
+        <>
+          <Totals totals={report.totals} />
+
+          {report.days.length > 1 && (
+            <div className="h-[130px]">
+              <ResponsiveContainer width="100%" height="100%">
+                <BarChart data={report.days} margin={{ top: 4, right: 4, bottom: 0, left: -10 }}>
+                  <XAxis
+                    dataKey="date"
+                    tick={{ fontSize: 9, fill: "var(--color-muted-subtle)" }}
+                    tickLine={false}
+                    axisLine={false}
+                    interval="preserveStartEnd"
+                  />
+                  <YAxis
+                    tick={{ fontSize: 9, fill: "var(--color-muted-subtle)" }}
+                    tickLine={false}
+                    axisLine={false}
+                    width={40}
+                    tickFormatter={(v: number) => formatDuration(v)}
+                  />
+                  <Tooltip content={<DayTooltip />} cursor={false} />
+                  <Bar dataKey="totals.active_ms" fill="var(--color-primary)" radius={[3, 3, 0, 0]} barSize={6} />
+                  <Bar dataKey="totals.wait_ms" fill="var(--color-warning)" radius={[3, 3, 0, 0]} barSize={6} />
+                </BarChart>
+              </ResponsiveContainer>
+            </div>
+          )}
+
+          {/* Latencia de cada turno da sessao */}
+          {single ? (
+            <div className="space-y-1 max-h-64 overflow-y-auto">
+              {report.sessions[0].turns.map((turn) => (
+                <div key={turn.entry_index} className="flex items-center gap-3">
+                  <span className="w-16 text-muted-subtle">#{turn.entry_index}</span>
+                  <span className="flex-1 text-muted">{formatRelativeTime(turn.timestamp)}</span>
+                  <span className={turn.latency_ms > 60_000 ? "text-warning" : "text-foreground-secondary"}>
+                    {formatLatency(turn.latency_ms)}
+                  </span>
+                </div>
+              ))}
+            </div>
+          ) : (
+            <>
+              <RollupTable
+                title="Projects"
+                rows={report.projects.map((p) => ({ key: p.project_path, label: p.project_display, totals: p.totals }))}
+              />
+              <RollupTable
+                title="Sessions"
+                rows={report.sessions.slice(0, 20).map((s) => ({
+                  key: s.file_path,
+                  label: `${s.project_display} · ${s.session_id.slice(0, 8)}`,
+                  totals: s.totals,
+                }))}
+              />
+            </>
+          )}
+        </>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,101 @@
+import { useState, useEffect } from "react";
+import { Spinner } from "@/components/ui/Spinner";
+import { Badge } from "@/components/ui/Badge";
+import { toolAnalytics } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { formatLatency, formatNumber } from "@/utils/formatters";
+import type { ToolAnalytics, ToolAnalyticsOptions } from "@/types/session";
+
+interface ToolAnalyticsPanelProps {
+  // Escopo fixo (sessao, projeto, datas); sem ele o painel analisa todas as sessoes
+  options?: ToolAnalyticsOptions;
+}
+
+const CATEGORY_VARIANT = {
+  builtin: "default",
+  mcp: "accent",
+  server: "green",
+} as const;
+
+export function ToolAnalyticsPanel({ options }: ToolAnalyticsPanelProps) {
+  const [analytics, setAnalytics] = useState<ToolAnalytics | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const key = JSON.stringify(options ?? {});
+
+  useEffect(() => {
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+    let cancelled = false;
+    setAnalytics(null);
+    setError(null);
+
+    toolAnalytics(claudeDir, JSON.parse(key), useWsl, wslDistro || undefined)
+      .then((result) => {
+        if (!cancelled) setAnalytics(result);
+      })
+      .catch((e) => {
+        if (!cancelled) setError(e instanceof Error ? e.message : String(e));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [key]);
+
+  if (error) {
+    return <p className="text-xs text-danger px-1 py-2">{error}</p>;
+  }
+
+  if (!analytics) {
+    return (
+      <div className="flex justify-center py-3">
+        <Spinner size="sm" />
+      </div>
+    );
+  }
+
+  if (analytics.tools.length === 0) {
+    return <p className="text-xs text-muted-subtle font-mono px-1 py-2">No tool calls</p>;
+  }
+
+  return (
+    <div className="text-xs font-mono">
+      <p className="text-muted-subtle mb-2">
+        {formatNumber(analytics.total_calls)} calls · {formatNumber(analytics.total_errors)} errors
+      </p>
+      <table className="w-full">
+        <thead>
+          <tr className="text-[10px] uppercase tracking-wider text-muted-subtle text-left">
+            <th className="py-1.5 font-semibold">Tool</th>
+            <th className="py-1.5 font-semibold text-right">Calls</th>
+            <th className="py-1.5 font-semibold text-right">Error rate</th>
+            <th className="py-1.5 font-semibold text-right">Avg result</th>
+            <th className="py-1.5 font-semibold text-right">Avg latency</th>
+            <th className="py-1.5 font-semibold text-right">Max latency</th>
+          </tr>
+        </thead>
+        <tbody className="divide-y divide-border-subtle">
+          {analytics.tools.map((tool) => (
+            <tr key={tool.tool_name} className="text-foreground-secondary">
+              <td className="py-1.5 pr-2">
+                <span className="flex items-center gap-2 min-w-0">
+                  <span className="truncate" title={tool.tool_name}>
+                    {tool.tool_name}
+                  </span>
+                  <Badge variant={CATEGORY_VARIANT[tool.category]}>{tool.mcp_server ?? tool.category}</Badge>
+                </span>
+              </td>
+              <td className="py-1.5 text-right">{formatNumber(tool.calls)}</td>
+              <td className={`py-1.5 text-right ${tool.error_rate > 0.1 ? "text-danger" : ""}`}>
+                {(tool.error_rate * 100).toFixed(1)}%
+              </td>
+              <td className="py-1.5 text-right">{formatNumber(Math.round(tool.avg_result_chars))} chars</td>
+              <td className="py-1.5 text-right">{formatLatency(tool.avg_latency_ms)}</td>
+              <td className="py-1.5 text-right">{formatLatency(tool.max_latency_ms)}</td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+}
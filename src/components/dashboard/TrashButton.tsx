@@ -0,0 +1,91 @@
+import { useState } from "react";
+import { ask } from "@tauri-apps/plugin-dialog";
+import { Icon } from "@/components/ui/Icon";
+import { Spinner } from "@/components/ui/Spinner";
+import { trashSessions } from "@/services/api";
+import { useConnectionStore } from "@/stores/useConnectionStore";
+import { useSessionStore } from "@/stores/useSessionStore";
+import { formatBytes } from "@/utils/formatters";
+import type { SessionMeta } from "@/types/session";
+
+interface TrashButtonProps {
+  sessions: SessionMeta[];
+  // So o icone (linha da lista)
+  compact?: boolean;
+  onDone?: () => void;
+}
+
+export function TrashButton({ sessions, compact, onDone }: TrashButtonProps) {
+  const fetchSessions = useSessionStore((s) => s.fetch);
+  const [isDeleting, setIsDeleting] = useState(false);
+  const [status, setStatus] = useState<string | null>(null);
+  const [errors, setErrors] = useState<string[]>([]);
+
+  const label = sessions.length === 1 ? "this session" : `${sessions.length} sessions`;
+
+  const handleTrash = async () => {
+    const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
+    if (!claudeDir) return;
+    const confirmed = await ask(`Move ${label} to the trash? Subagents, file history and todos go with it.`, {
+      title: "Delete sessions",
+      kind: "warning",
+    });
+    if (!confirmed) return;
+
+    setIsDeleting(true);
+    setStatus(null);
+    setErrors([]);
+    try {
+      const report = await trashSessions(
+        claudeDir,
+        sessions.map((s) => s.file_path),
+        useWsl,
+        wslDistro || undefined,
+      );
+      const failed = report.errors.length > 0 ? ` · ${report.errors.length} failed` : "";
+      setStatus(`Deleted ${report.session_ids.length}, freed ${formatBytes(report.freed_bytes)}${failed}`);
+      setErrors(report.errors);
+      await fetchSessions();
+      // Com falhas a selecao fica para mostrar os erros
+      if (report.errors.length === 0) onDone?.();
+    } catch (e) {
+      setStatus(e instanceof Error ? e.message : String(e));
+    } finally {
+      setIsDeleting(false);
+    }
+  };
+
+  if (compact) {
+    return (
+      <button
+        onClick={handleTrash}
+        disabled={isDeleting}
+        title={status ?? "Move to trash"}
+        aria-label="Move to trash"
+        className={`p-1.5 rounded border border-border-subtle hover:text-danger hover:border-danger/30 transition-all cursor-pointer disabled:opacity-50 ${
+          errors.length > 0 ? "text-danger" : "text-muted-subtle"
+        }`}
+      >
+        {isDeleting ? <Spinner size="sm" /> : <Icon name="delete" size="sm" />}
+      </button>
+    );
+  }
+
+  return (
+    <div className="flex items-center gap-3 text-xs font-mono text-muted-subtle">
+      <button
+        onClick={handleTrash}
+        disabled={isDeleting || sessions.length === 0}
+        className="flex items-center gap-1.5 px-3 py-1.5 rounded text-xs font-semibold bg-transparent border border-danger/30 text-danger hover:bg-danger/10 hover:border-danger transition-all cursor-pointer disabled:opacity-50"
+      >
+        {isDeleting ? <Spinner size="sm" /> : <Icon name="delete" size="sm" />}
+        Move {sessions.length} to trash
+      </button>
+      {status && (
+        <span className={errors.length > 0 ? "text-danger" : ""} title={errors.join("\n") || undefined}>
+          {status}
+        </span>
+      )}
+    </div>
+  );
+}
@@ -7,6 +7,8 @@ import { useAccountStore } from "@/stores/useAccountStore";
 const navItems = [
   { to: ROUTES.DASHBOARD, icon: "dashboard", label: "Dashboard" },
   { to: ROUTES.HISTORY, icon: "history", label: "History" },
+  { to: ROUTES.COMMANDS, icon: "terminal", label: "Commands" },
+  { to: ROUTES.INSIGHTS, icon: "insights", label: "Insights" },
   { to: ROUTES.PROJECTS, icon: "folder_open", label: "Projects" },
   { to: ROUTES.USAGE, icon: "bar_chart", label: "Usage & Limits" },
   { to: ROUTES.MCP, icon: "hub", label: "MCP Servers" },
@@ -1,39 +1,72 @@
 import { useState, useCallback, useRef } from "react";
-import { readSession } from "@/services/api";
+import { readSession, readSessionTree, readContentBlock } from "@/services/api";
 import { useConnectionStore } from "@/stores/useConnectionStore";
-import type { SessionEntry } from "@/types/session";
+import type { SessionEntry, BranchKind } from "@/types/session";
+
+const WINDOW_SIZE = 200;
 
 interface UseConversationReturn {
   entries: SessionEntry[];
+  startIndex: number;
+  totalEntries: number;
+  hasMore: boolean;
+  // Ramo de cada entry (por uuid); vazio ate a arvore carregar
+  branches: Map<string, BranchKind>;
   isLoading: boolean;
+  isLoadingMore: boolean;
   error: string | null;
-  load: (sessionId: string, projectPath: string) => Promise<void>;
+  load: (sessionPath: string) => Promise<void>;
+  loadMore: () => Promise<void>;
+  expandBlock: (entryIndex: number, blockIndex: number) => Promise<void>;
   reset: () => void;
 }
 
 export function useConversation(): UseConversationReturn {
   const [entries, setEntries] = useState<SessionEntry[]>([]);
+  const [startIndex, setStartIndex] = useState(0);
+  const [totalEntries, setTotalEntries] = useState(0);
+  const [hasMore, setHasMore] = useState(false);
+  const [branches, setBranches] = useState<Map<string, BranchKind>>(new Map());
   const [isLoading, setIsLoading] = useState(false);
+  const [isLoadingMore, setIsLoadingMore] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const loadId = useRef(0);
+  const sessionPathRef = useRef<string | null>(null);
+  // Offset do proximo entry a carregar (fim da janela atual)
+  const nextOffset = useRef(0);
 
-  const load = useCallback(async (sessionId: string, projectPath: string) => {
+  const load = useCallback(async (sessionPath: string) => {
     const { claudeDir, useWsl, wslDistro } = useConnectionStore.getState();
     if (!claudeDir) return;
 
-    // Re-encode: "Home/User/project" -> "Home-User-project"
-    const encodedProject = projectPath.replace(/\//g, "-");
-    const sessionPath = `${claudeDir}/projects/${encodedProject}/${sessionId}.jsonl`;
-
     const currentId = ++loadId.current;
+    sessionPathRef.current = sessionPath;
     setIsLoading(true);
     setError(null);
+    setBranches(new Map());
+
+    // Arvore carrega em paralelo; falha so deixa de marcar os ramos
+    readSessionTree(sessionPath, useWsl, wslDistro || undefined)
+      .then((tree) => {
+        if (currentId !== loadId.current) return;
+        setBranches(new Map(tree.nodes.map((n) => [n.uuid, n.branch])));
+      })
+      .catch(() => {});
 
     try {
-      const result = await readSession(sessionPath, useWsl, wslDistro || undefined);
+      const page = await readSession(
+        sessionPath,
+        { offset: 0, limit: WINDOW_SIZE },
+        useWsl,
+        wslDistro || undefined,
+      );
       // Ignora se outra chamada foi feita depois
       if (currentId !== loadId.current) return;
-      setEntries(result);
+      setEntries(page.entries);
+      setStartIndex(page.start_index);
+      setTotalEntries(page.total_entries);
+      setHasMore(page.has_after);
+      nextOffset.current = page.start_index + page.entries.length;
     } catch (e) {
       if (currentId !== loadId.current) return;
       setError(e instanceof Error ? e.message : String(e));
@@ -44,12 +77,97 @@ export function useConversation(): UseConversationReturn {
     }
   }, []);
 
+  const loadMore = useCallback(async () => {
+    const { useWsl, wslDistro } = useConnectionStore.getState();
+    const sessionPath = sessionPathRef.current;
+    if (!sessionPath) return;
+
+    const currentId = loadId.current;
+    setIsLoadingMore(true);
+
+    try {
+      const page = await readSession(
+        sessionPath,
+        { offset: nextOffset.current, limit: WINDOW_SIZE },
+        useWsl,
+        wslDistro || undefined,
+      );
+      if (currentId !== loadId.current) return;
+      setEntries((prev) => [...prev, ...page.entries]);
+      setTotalEntries(page.total_entries);
+      setHasMore(page.has_after);
+      nextOffset.current = page.start_index + page.entries.length;
+    } catch (e) {
+      if (currentId !== loadId.current) return;
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      if (currentId === loadId.current) {
+        setIsLoadingMore(false);
+      }
+    }
+  }, []);
+
+  // Busca o conteudo completo de um bloco truncado pelo backend
+  const expandBlock = useCallback(
+    async (entryIndex: number, blockIndex: number) => {
+      const { useWsl, wslDistro } = useConnectionStore.getState();
+      const sessionPath = sessionPathRef.current;
+      if (!sessionPath) return;
+
+      const currentId = loadId.current;
+      const full = await readContentBlock(
+        sessionPath,
+        entryIndex,
+        blockIndex,
+        useWsl,
+        wslDistro || undefined,
+      );
+      if (currentId !== loadId.current) return;
+
+      setEntries((prev) =>
+        prev.map((entry, i) => {
+          if (startIndex + i !== entryIndex) return entry;
+          const content = entry.message.content;
+          if (!Array.isArray(content)) return entry;
+          const blocks = [...content];
+          blocks[blockIndex] = full.block;
+          return {
+            ...entry,
+            toolUseResult: full.tool_use_result ?? entry.toolUseResult,
+            message: { ...entry.message, content: blocks },
+          };
+        }),
+      );
+    },
+    [startIndex],
+  );
+
   const reset = useCallback(() => {
     loadId.current++;
+    sessionPathRef.current = null;
+    nextOffset.current = 0;
     setEntries([]);
+    setStartIndex(0);
+    setTotalEntries(0);
+    setHasMore(false);
+    setBranches(new Map());
     setIsLoading(false);
+    setIsLoadingMore(false);
     setError(null);
   }, []);
 
-  return { entries, isLoading, error, load, reset };
+  return {
+    entries,
+    startIndex,
+    totalEntries,
+    hasMore,
+    branches,
+    isLoading,
+    isLoadingMore,
+    error,
+    load,
+    loadMore,
+    expandBlock,
+    reset,
+  };
 }
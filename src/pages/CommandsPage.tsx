@@ -0,0 +1,22 @@
+import { AnimateIn } from "@/components/ui/AnimateIn";
+import { BashHistory } from "@/components/dashboard/BashHistory";
+
+export function CommandsPage() {
+  return (
+    <>
+      <AnimateIn>
+        <header className="mb-8">
+          <p className="text-xs font-medium text-primary mb-2 font-mono tracking-widest uppercase opacity-80">
+            All Projects
+          </p>
+          <h2 className="text-3xl font-bold text-foreground tracking-tight font-display">
+            Commands
+          </h2>
+        </header>
+      </AnimateIn>
+      <AnimateIn delay={80}>
+        <BashHistory />
+      </AnimateIn>
+    </>
+  );
+}
@@ -0,0 +1,92 @@
+import { useState, useEffect } from "react";
+import { AnimateIn } from "@/components/ui/AnimateIn";
+import { Icon } from "@/components/ui/Icon";
+import { ToolAnalyticsPanel } from "@/components/dashboard/ToolAnalyticsPanel";
+import { TimingPanel } from "@/components/dashboard/TimingPanel";
+import { RateLimitPanel } from "@/components/dashboard/RateLimitPanel";
+import { PermissionsPanel } from "@/components/dashboard/PermissionsPanel";
+import type { ToolAnalyticsOptions } from "@/types/session";
+
+export function InsightsPage() {
+  const [scope, setScope] = useState<ToolAnalyticsOptions>({});
+  const [debounced, setDebounced] = useState<ToolAnalyticsOptions>({});
+
+  // Debounce do filtro de projeto
+  useEffect(() => {
+    const timer = setTimeout(() => setDebounced(scope), 400);
+    return () => clearTimeout(timer);
+  }, [scope]);
+
+  const dateInput = (key: "since" | "until") => (
+    <input
+      type="date"
+      value={scope[key] ?? ""}
+      onChange={(e) => setScope((s) => ({ ...s, [key]: e.target.value || null }))}
+      className="py-2 px-2 bg-input-bg border border-border-subtle rounded-lg text-xs text-foreground-secondary font-mono"
+    />
+  );
+
+  return (
+    <>
+      <AnimateIn>
+        <header className="mb-8">
+          <p className="text-xs font-medium text-primary mb-2 font-mono tracking-widest uppercase opacity-80">
+            Across Sessions
+          </p>
+          <h2 className="text-3xl font-bold text-foreground tracking-tight font-display">
+            Insights
+          </h2>
+        </header>
+      </AnimateIn>
+      <AnimateIn delay={80}>
+        <div className="flex flex-wrap items-center gap-3 mb-6">
+          <input
+            className="px-3 py-2 bg-input-bg border border-border-subtle rounded-lg text-sm text-foreground focus:outline-none focus:border-primary/50 placeholder-muted-subtle w-64"
+            placeholder="Project"
+            value={scope.project ?? ""}
+            onChange={(e) => setScope((s) => ({ ...s, project: e.target.value || null }))}
+          />
+          {dateInput("since")}
+          <span className="text-xs text-muted-subtle font-mono">to</span>
+          {dateInput("until")}
+        </div>
+      </AnimateIn>
+      <AnimateIn delay={120}>
+        <section className="glass-card rounded-xl p-6">
+          <h3 className="text-lg font-bold text-foreground flex items-center gap-2 font-display mb-4">
+            <Icon name="build" className="text-primary" />
+            Tool Calls
+          </h3>
+          <ToolAnalyticsPanel options={debounced} />
+        </section>
+      </AnimateIn>
+      <AnimateIn delay={160}>
+        <section className="glass-card rounded-xl p-6 mt-6">
+          <h3 className="text-lg font-bold text-foreground flex items-center gap-2 font-display mb-4">
+            <Icon name="schedule" className="text-primary" />
+            Time
+          </h3>
+          <TimingPanel options={debounced} />
+        </section>
+      </AnimateIn>
+      <AnimateIn delay={200}>
+        <section className="glass-card rounded-xl p-6 mt-6">
+          <h3 className="text-lg font-bold text-foreground flex items-center gap-2 font-display mb-4">
+            <Icon name="block" className="text-primary" />
+            Rate Limits
+          </h3>
+          <RateLimitPanel options={debounced} />
+        </section>
+      </AnimateIn>
+      <AnimateIn delay={240}>
+        <section className="glass-card rounded-xl p-6 mt-6">
+          <h3 className="text-lg font-bold text-foreground flex items-center gap-2 font-display mb-4">
+            <Icon name="lock" className="text-primary" />
+            Permissions
+          </h3>
+          <PermissionsPanel options={debounced} />
+        </section>
+      </AnimateIn>
+    </>
+  );
+}
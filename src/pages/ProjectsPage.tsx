@@ -3,6 +3,8 @@ import { AnimateIn } from "@/components/ui/AnimateIn";
 import { Icon } from "@/components/ui/Icon";
 import { Card } from "@/components/ui/Card";
 import { Badge } from "@/components/ui/Badge";
+import { DiskUsagePanel } from "@/components/dashboard/DiskUsagePanel";
+import { ProjectMigrationPanel } from "@/components/dashboard/ProjectMigrationPanel";
 import { useSessionStore } from "@/stores/useSessionStore";
 import { useStatsStore } from "@/stores/useStatsStore";
 import { formatRelativeTime, formatCurrency, formatTokenCount, formatDuration } from "@/utils/formatters";
@@ -238,6 +240,26 @@ export function ProjectsPage() {
           })}
         </div>
       )}
+
+      <AnimateIn delay={120}>
+        <section className="glass-card rounded-xl p-6 mt-6">
+          <h3 className="text-lg font-bold text-foreground flex items-center gap-2 font-display mb-4">
+            <Icon name="storage" className="text-primary" />
+            Disk Usage
+          </h3>
+          <DiskUsagePanel />
+        </section>
+      </AnimateIn>
+
+      <AnimateIn delay={160}>
+        <section className="glass-card rounded-xl p-6 mt-6">
+          <h3 className="text-lg font-bold text-foreground flex items-center gap-2 font-display mb-4">
+            <Icon name="drive_file_move" className="text-primary" />
+            Move Project
+          </h3>
+          <ProjectMigrationPanel projectPaths={projects.map((p) => p.path)} />
+        </section>
+      </AnimateIn>
     </>
   );
 }
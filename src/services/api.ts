@@ -1,6 +1,43 @@
 import { invoke } from "@tauri-apps/api/core";
 import type { UsageResponse } from "@/types/usage";
-import type { SessionMeta, SessionEntry, SearchMatch, PlatformInfo } from "@/types/session";
+import type {
+  SessionMeta,
+  SessionWindow,
+  ReadSessionOptions,
+  ConversationTree,
+  ContextTimeline,
+  SessionSubagents,
+  SessionListOptions,
+  SessionFiles,
+  FileSession,
+  SessionDiff,
+  TurnRange,
+  BashCommand,
+  BashHistoryOptions,
+  ToolAnalytics,
+  ToolAnalyticsOptions,
+  TimingOptions,
+  TimingReport,
+  RateLimitOptions,
+  RateLimitReport,
+  PermissionOptions,
+  PermissionReport,
+  PermissionPreview,
+  ArchiveOptions,
+  ArchiveReport,
+  RestoredSession,
+  TrashReport,
+  RetentionPolicy,
+  RetentionReport,
+  DiskUsageReport,
+  ForkedSession,
+  FullContentBlock,
+  ProjectMigrationOptions,
+  ProjectMigrationReport,
+  ExportOptions,
+  SearchPage,
+  PlatformInfo,
+} from "@/types/session";
 import type { AccountInfo } from "@/types/account";
 import type { ProjectMetrics, GlobalStats } from "@/types/stats";
 
@@ -10,27 +47,247 @@ export async function fetchUsage(token: string): Promise<UsageResponse> {
 
 export async function listSessions(
   claudeDir: string,
+  options?: SessionListOptions,
   useWsl?: boolean,
   wslDistro?: string,
 ): Promise<SessionMeta[]> {
-  return invoke<SessionMeta[]>("list_sessions", { claudeDir, useWsl, wslDistro });
+  return invoke<SessionMeta[]>("list_sessions", { claudeDir, options, useWsl, wslDistro });
 }
 
 export async function readSession(
   sessionPath: string,
+  options: ReadSessionOptions = {},
   useWsl?: boolean,
   wslDistro?: string,
-): Promise<SessionEntry[]> {
-  return invoke<SessionEntry[]>("read_session", { sessionPath, useWsl, wslDistro });
+): Promise<SessionWindow> {
+  return invoke<SessionWindow>("read_session", { sessionPath, ...options, useWsl, wslDistro });
+}
+
+export async function readSessionTree(
+  sessionPath: string,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<ConversationTree> {
+  return invoke<ConversationTree>("read_session_tree", { sessionPath, useWsl, wslDistro });
+}
+
+export async function readSessionSubagents(
+  sessionPath: string,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<SessionSubagents> {
+  return invoke<SessionSubagents>("read_session_subagents", { sessionPath, useWsl, wslDistro });
+}
+
+export async function exportSession(
+  sessionPath: string,
+  outputPath: string,
+  options: ExportOptions,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<void> {
+  return invoke<void>("export_session", { sessionPath, outputPath, options, useWsl, wslDistro });
+}
+
+export async function exportSessions(
+  sessionPaths: string[],
+  outputPath: string,
+  options: ExportOptions,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<number> {
+  return invoke<number>("export_sessions", { sessionPaths, outputPath, options, useWsl, wslDistro });
+}
+
+export async function sessionFiles(
+  claudeDir: string,
+  sessionPath: string,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<SessionFiles> {
+  return invoke<SessionFiles>("session_files", { claudeDir, sessionPath, useWsl, wslDistro });
+}
+
+export async function sessionsForFile(
+  claudeDir: string,
+  filePath: string,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<FileSession[]> {
+  return invoke<FileSession[]>("sessions_for_file", { claudeDir, filePath, useWsl, wslDistro });
+}
+
+export async function readContextTimeline(
+  sessionPath: string,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<ContextTimeline> {
+  return invoke<ContextTimeline>("read_context_timeline", { sessionPath, useWsl, wslDistro });
+}
+
+export async function sessionDiff(
+  sessionPath: string,
+  range?: TurnRange,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<SessionDiff> {
+  return invoke<SessionDiff>("session_diff", { sessionPath, range, useWsl, wslDistro });
+}
+
+export async function exportSessionPatch(
+  sessionPath: string,
+  outputPath: string,
+  range?: TurnRange,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<SessionDiff> {
+  return invoke<SessionDiff>("export_session_patch", { sessionPath, outputPath, range, useWsl, wslDistro });
+}
+
+export async function listBashCommands(
+  claudeDir: string,
+  options?: BashHistoryOptions,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<BashCommand[]> {
+  return invoke<BashCommand[]>("list_bash_commands", { claudeDir, options, useWsl, wslDistro });
+}
+
+export async function toolAnalytics(
+  claudeDir: string,
+  options?: ToolAnalyticsOptions,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<ToolAnalytics> {
+  return invoke<ToolAnalytics>("tool_analytics", { claudeDir, options, useWsl, wslDistro });
+}
+
+export async function sessionTiming(
+  claudeDir: string,
+  options?: TimingOptions,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<TimingReport> {
+  return invoke<TimingReport>("session_timing", { claudeDir, options, useWsl, wslDistro });
+}
+
+export async function listRateLimits(
+  claudeDir: string,
+  options?: RateLimitOptions,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<RateLimitReport> {
+  return invoke<RateLimitReport>("list_rate_limits", { claudeDir, options, useWsl, wslDistro });
+}
+
+export async function permissionAnalysis(
+  claudeDir: string,
+  options?: PermissionOptions,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<PermissionReport> {
+  return invoke<PermissionReport>("permission_analysis", { claudeDir, options, useWsl, wslDistro });
+}
+
+export async function previewPermissionRules(
+  projectPath: string,
+  allow: string[],
+  deny: string[],
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<PermissionPreview> {
+  return invoke<PermissionPreview>("preview_permission_rules", { projectPath, allow, deny, useWsl, wslDistro });
+}
+
+export async function applyPermissionRules(
+  projectPath: string,
+  allow: string[],
+  deny: string[],
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<PermissionPreview> {
+  return invoke<PermissionPreview>("apply_permission_rules", { projectPath, allow, deny, useWsl, wslDistro });
+}
+
+export async function archiveSessions(
+  claudeDir: string,
+  options: ArchiveOptions,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<ArchiveReport> {
+  return invoke<ArchiveReport>("archive_sessions", { claudeDir, options, useWsl, wslDistro });
+}
+
+export async function restoreSession(path: string, useWsl?: boolean, wslDistro?: string): Promise<RestoredSession> {
+  return invoke<RestoredSession>("restore_session", { path, useWsl, wslDistro });
+}
+
+export async function trashSessions(
+  claudeDir: string,
+  sessionPaths: string[],
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<TrashReport> {
+  return invoke<TrashReport>("trash_sessions", { claudeDir, sessionPaths, useWsl, wslDistro });
+}
+
+export async function applyRetentionPolicy(
+  claudeDir: string,
+  policy: RetentionPolicy,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<RetentionReport> {
+  return invoke<RetentionReport>("apply_retention_policy", { claudeDir, policy, useWsl, wslDistro });
+}
+
+export async function diskUsage(claudeDir: string, useWsl?: boolean, wslDistro?: string): Promise<DiskUsageReport> {
+  return invoke<DiskUsageReport>("disk_usage", { claudeDir, useWsl, wslDistro });
+}
+
+export async function forkSession(
+  claudeDir: string,
+  sessionPath: string,
+  uuid: string,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<ForkedSession> {
+  return invoke<ForkedSession>("fork_session", { claudeDir, sessionPath, uuid, useWsl, wslDistro });
+}
+
+export async function migrateProject(
+  claudeDir: string,
+  options: ProjectMigrationOptions,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<ProjectMigrationReport> {
+  return invoke<ProjectMigrationReport>("migrate_project", { claudeDir, options, useWsl, wslDistro });
+}
+
+export async function readContentBlock(
+  sessionPath: string,
+  entryIndex: number,
+  blockIndex: number,
+  useWsl?: boolean,
+  wslDistro?: string,
+): Promise<FullContentBlock> {
+  return invoke<FullContentBlock>("read_content_block", {
+    sessionPath,
+    entryIndex,
+    blockIndex,
+    useWsl,
+    wslDistro,
+  });
 }
 
 export async function searchSessions(
   claudeDir: string,
   query: string,
+  cursor?: string,
+  limit?: number,
   useWsl?: boolean,
   wslDistro?: string,
-): Promise<SearchMatch[]> {
-  return invoke<SearchMatch[]>("search_sessions", { claudeDir, query, useWsl, wslDistro });
+): Promise<SearchPage> {
+  return invoke<SearchPage>("search_sessions", { claudeDir, query, cursor, limit, useWsl, wslDistro });
 }
 
 export async function resumeSession(
@@ -1,28 +1,43 @@
 import { create } from "zustand";
 import { listSessions, resumeSession, searchSessions } from "@/services/api";
 import { useConnectionStore } from "./useConnectionStore";
-import type { SessionMeta, SearchMatch } from "@/types/session";
+import type { SessionMeta, SearchMatch, SessionListOptions } from "@/types/session";
 
 interface SessionState {
   sessions: SessionMeta[];
   isLoading: boolean;
   error: string | null;
+  // Ordenacao e filtros aplicados pelo backend
+  listOptions: SessionListOptions;
 
   searchResults: SearchMatch[];
+  searchTotal: number;
+  searchTruncated: boolean;
+  searchQuery: string;
+  searchCursor: string | null;
   isSearching: boolean;
 
   fetch: () => Promise<void>;
+  setListOptions: (options: SessionListOptions) => Promise<void>;
   resume: (sessionId: string, projectPath: string) => Promise<void>;
   searchInContent: (query: string) => Promise<void>;
+  loadMoreResults: () => Promise<void>;
   clearSearch: () => void;
 }
 
-export const useSessionStore = create<SessionState>((set) => ({
+const SEARCH_PAGE_SIZE = 50;
+
+export const useSessionStore = create<SessionState>((set, get) => ({
   sessions: [],
   isLoading: false,
   error: null,
+  listOptions: {},
 
   searchResults: [],
+  searchTotal: 0,
+  searchTruncated: false,
+  searchQuery: "",
+  searchCursor: null,
   isSearching: false,
 
   fetch: async () => {
@@ -31,7 +46,12 @@ export const useSessionStore = create<SessionState>((set) => ({
 
     set({ isLoading: true, error: null });
     try {
-      const sessions = await listSessions(claudeDir, useWsl, wslDistro || undefined);
+      const sessions = await listSessions(
+        claudeDir,
+        get().listOptions,
+        useWsl,
+        wslDistro || undefined,
+      );
       set({ sessions, isLoading: false });
     } catch (e) {
       set({
@@ -41,6 +61,11 @@ export const useSessionStore = create<SessionState>((set) => ({
     }
   },
 
+  setListOptions: async (options: SessionListOptions) => {
+    set({ listOptions: options });
+    await get().fetch();
+  },
+
   resume: async (sessionId: string, projectPath: string) => {
     const { useWsl, wslDistro } = useConnectionStore.getState();
     await resumeSession(sessionId, projectPath, useWsl, wslDistro || undefined);
@@ -49,20 +74,68 @@ export const useSessionStore = create<SessionState>((set) => ({
   searchInContent: async (query: string) => {
     const { claudeDir, useWsl, wslDistro, isConnected } = useConnectionStore.getState();
     if (!isConnected || !claudeDir || query.length < 2) {
-      set({ searchResults: [], isSearching: false });
+      set({ searchResults: [], searchTotal: 0, searchTruncated: false, searchCursor: null, isSearching: false });
       return;
     }
 
+    set({ isSearching: true, searchQuery: query });
+    try {
+      const page = await searchSessions(
+        claudeDir,
+        query,
+        undefined,
+        SEARCH_PAGE_SIZE,
+        useWsl,
+        wslDistro || undefined,
+      );
+      // Ignora resposta de uma busca ja substituida
+      if (get().searchQuery !== query) return;
+      set({
+        searchResults: page.results,
+        searchTotal: page.total_sessions,
+        searchTruncated: page.truncated,
+        searchCursor: page.next_cursor,
+        isSearching: false,
+      });
+    } catch {
+      set({ searchResults: [], searchTotal: 0, searchTruncated: false, searchCursor: null, isSearching: false });
+    }
+  },
+
+  loadMoreResults: async () => {
+    const { claudeDir, useWsl, wslDistro, isConnected } = useConnectionStore.getState();
+    const { searchQuery, searchCursor, isSearching } = get();
+    if (!isConnected || !claudeDir || !searchCursor || isSearching) return;
+
     set({ isSearching: true });
     try {
-      const results = await searchSessions(claudeDir, query, useWsl, wslDistro || undefined);
-      set({ searchResults: results, isSearching: false });
+      const page = await searchSessions(
+        claudeDir,
+        searchQuery,
+        searchCursor,
+        SEARCH_PAGE_SIZE,
+        useWsl,
+        wslDistro || undefined,
+      );
+      if (get().searchQuery !== searchQuery) return;
+      set((state) => ({
+        searchResults: [...state.searchResults, ...page.results],
+        searchCursor: page.next_cursor,
+        isSearching: false,
+      }));
     } catch {
-      set({ searchResults: [], isSearching: false });
+      set({ isSearching: false });
     }
   },
 
   clearSearch: () => {
-    set({ searchResults: [], isSearching: false });
+    set({
+      searchResults: [],
+      searchTotal: 0,
+      searchTruncated: false,
+      searchQuery: "",
+      searchCursor: null,
+      isSearching: false,
+    });
   },
 }));
@@ -2,15 +2,606 @@ export interface SessionMeta {
   id: string;
   project_path: string;
   project_display: string;
+  file_path: string;
+  // Titulo: ultimo resumo do Claude Code, primeiro prompt ou ultima resposta
   summary: string;
+  first_prompt: string | null;
+  latest_summary: string | null;
+  first_timestamp: string;
   last_timestamp: string;
+  duration_ms: number;
   message_count: number;
+  subagent_count: number;
+  // Tokens e custo do transcript da sessao (sem subagentes)
+  tokens: TokenTotals;
+  models: string[];
+  model_switches: number;
+  // Transcript comprimido (.jsonl.zst/.gz); precisa ser restaurado para retomar
+  archived: boolean;
 }
 
+export type SessionSortKey = "last_active" | "started" | "duration" | "cost" | "tokens" | "messages";
+
+export interface SessionListOptions {
+  sort_by?: SessionSortKey;
+  ascending?: boolean;
+  model?: string;
+  project?: string;
+  min_cost_usd?: number;
+  min_tokens?: number;
+  // Datas YYYY-MM-DD da ultima atividade
+  since?: string;
+  until?: string;
+}
+
+// Linha do transcript JSONL, no formato original do Claude Code
 export interface SessionEntry {
-  type: string;
-  message: Record<string, unknown>;
+  type: "user" | "assistant" | "system" | "summary" | (string & {});
+  uuid?: string;
+  parentUuid?: string | null;
+  isSidechain?: boolean;
+  isMeta?: boolean;
+  sessionId?: string;
+  timestamp?: string;
+  cwd?: string;
+  gitBranch?: string;
+  version?: string;
+  // user / assistant
+  message?: Record<string, unknown>;
+  // system
+  subtype?: string;
+  content?: string;
+  // summary
+  summary?: string;
+  leafUuid?: string;
+  [key: string]: unknown;
+}
+
+export type ReadDirection = "forward" | "backward";
+
+export type ExportFormat = "markdown" | "html" | "json";
+
+export interface ExportOptions {
+  format: ExportFormat;
+  include_thinking: boolean;
+  include_tool_results: boolean;
+  // Inclui sidechains e transcripts de subagentes
+  include_sidechains: boolean;
+}
+
+// Bloco completo de um entry (read_content_block), com o toolUseResult completo
+export interface FullContentBlock {
+  block: Record<string, unknown>;
+  tool_use_result: unknown;
+}
+
+export interface SessionWindow {
+  entries: SessionEntry[];
+  start_index: number;
+  total_entries: number;
+  has_before: boolean;
+  has_after: boolean;
+}
+
+export interface ReadSessionOptions {
+  offset?: number;
+  cursor?: string;
+  limit?: number;
+  direction?: ReadDirection;
+}
+
+export type BranchKind = "active" | "abandoned" | "sidechain";
+
+export interface TreeNode {
+  uuid: string;
+  parent_uuid: string | null;
+  entry_index: number;
+  entry_type: string;
+  timestamp: string;
+  branch: BranchKind;
+}
+
+export interface ConversationBranch {
+  leaf_uuid: string;
+  fork_uuid: string | null;
+  uuids: string[];
+  message_count: number;
+  first_timestamp: string;
+  last_timestamp: string;
+  preview: string;
+}
+
+export interface ConversationTree {
+  active_leaf: string | null;
+  active_path: string[];
+  abandoned: ConversationBranch[];
+  sidechains: ConversationBranch[];
+  nodes: TreeNode[];
+}
+
+// Tokens e custo estimado (precos publicos por modelo)
+export interface TokenTotals {
+  input_tokens: number;
+  output_tokens: number;
+  cache_creation_input_tokens: number;
+  cache_read_input_tokens: number;
+  cost_usd: number;
+  // Tokens de modelos sem preco conhecido
+  unpriced_tokens: number;
+}
+
+export interface SubagentRun {
+  agent_id: string;
+  file_path: string;
+  subagent_type: string;
+  description: string | null;
+  tool_use_id: string | null;
+  parent_entry_index: number | null;
+  first_timestamp: string;
+  last_timestamp: string;
+  message_count: number;
+  models: string[];
+  tokens: TokenTotals;
+}
+
+export interface SubagentTypeTotals {
+  subagent_type: string;
+  runs: number;
+  tokens: TokenTotals;
+}
+
+export interface SessionSubagents {
+  session_id: string;
+  session_tokens: TokenTotals;
+  subagent_tokens: TokenTotals;
+  runs: SubagentRun[];
+  by_type: SubagentTypeTotals[];
+}
+
+// Arquivo lido ou modificado numa sessao (Read/Edit/Write/MultiEdit/NotebookEdit)
+export interface TouchedFile {
+  file_path: string;
+  reads: number;
+  modifications: number;
+  first_timestamp: string;
+  last_timestamp: string;
+}
+
+export interface SessionFiles {
+  modified: TouchedFile[];
+  read: TouchedFile[];
+}
+
+export interface FileSession {
+  session: SessionMeta;
+  file: TouchedFile;
+}
+
+// Turnos contados a partir de 1, inclusivo
+export interface TurnRange {
+  from_turn?: number | null;
+  to_turn?: number | null;
+}
+
+export interface FileDiff {
+  file_path: string;
+  diff: string;
+  additions: number;
+  deletions: number;
+  edit_count: number;
+  created: boolean;
+  // false quando so os trechos editados eram conhecidos
+  complete: boolean;
+}
+
+export interface SessionDiff {
+  session_id: string;
+  turn_count: number;
+  files: FileDiff[];
+  additions: number;
+  deletions: number;
+}
+
+export interface BashHistoryOptions {
+  // Substring do path do projeto
+  project?: string | null;
+  failed_only?: boolean;
+  // Substring do comando ou da descricao
+  query?: string | null;
+  // Datas YYYY-MM-DD (inclusivas)
+  since?: string | null;
+  until?: string | null;
+  limit?: number | null;
+}
+
+export interface BashCommand {
+  session_id: string;
+  session_path: string;
+  project_path: string;
+  project_display: string;
+  // Transcript do subagente que rodou o comando (null = transcript da sessao)
+  subagent_path: string | null;
+  entry_index: number;
+  timestamp: string;
+  command: string;
+  description: string | null;
+  cwd: string | null;
+  // Saida truncada; null se o comando nao terminou
+  output: string | null;
+  is_error: boolean;
+  exit_code: number | null;
+  interrupted: boolean;
+}
+
+// Escopo: uma sessao, um projeto (substring do path) ou tudo
+export interface ToolAnalyticsOptions {
+  session_path?: string | null;
+  project?: string | null;
+  since?: string | null;
+  until?: string | null;
+}
+
+export interface ToolStats {
+  tool_name: string;
+  category: "builtin" | "mcp" | "server";
+  mcp_server: string | null;
+  calls: number;
+  errors: number;
+  error_rate: number;
+  results: number;
+  avg_result_chars: number;
+  total_result_chars: number;
+  avg_latency_ms: number | null;
+  max_latency_ms: number | null;
+}
+
+export interface ToolAnalytics {
+  total_calls: number;
+  total_errors: number;
+  tools: ToolStats[];
+}
+
+export interface ContextPoint {
+  entry_index: number;
+  uuid: string | null;
+  timestamp: string;
+  model: string;
+  context_tokens: number;
+  output_tokens: number;
+  context_window: number;
+  occupancy: number;
+  // Crescimento desde a resposta anterior (0 apos inicio ou compact)
+  delta_tokens: number;
+}
+
+export interface CompactBoundary {
+  entry_index: number;
+  uuid: string | null;
+  timestamp: string;
+  trigger: "manual" | "auto" | null;
+  pre_tokens: number | null;
+}
+
+export interface ContextBloat {
+  entry_index: number;
+  delta_tokens: number;
+  cause_entry_index: number | null;
+  cause: "tool_result" | "prompt" | "assistant";
+  tool_name: string | null;
+  cause_chars: number;
+  preview: string;
+}
+
+export interface ContextTimeline {
+  session_id: string;
+  points: ContextPoint[];
+  compactions: CompactBoundary[];
+  top_bloat: ContextBloat[];
+  peak_tokens: number;
+  context_window: number;
+}
+
+export interface TimingOptions {
+  session_path?: string | null;
+  project?: string | null;
+  since?: string | null;
+  until?: string | null;
+  idle_threshold_secs?: number | null;
+}
+
+export interface TurnTiming {
+  entry_index: number;
+  timestamp: string;
+  latency_ms: number;
+}
+
+export interface TimingTotals {
+  sessions: number;
+  turns: number;
+  wait_ms: number;
+  avg_latency_ms: number;
+  max_latency_ms: number;
+  active_ms: number;
+  idle_ms: number;
+}
+
+export interface SessionTiming {
+  session_id: string;
+  file_path: string;
+  project_display: string;
+  totals: TimingTotals;
+  turns: TurnTiming[];
+}
+
+export interface ProjectTiming {
+  project_path: string;
+  project_display: string;
+  totals: TimingTotals;
+}
+
+export interface DayTiming {
+  date: string;
+  totals: TimingTotals;
+}
+
+export interface TimingReport {
+  idle_threshold_ms: number;
+  totals: TimingTotals;
+  sessions: SessionTiming[];
+  projects: ProjectTiming[];
+  days: DayTiming[];
+}
+
+export interface RateLimitOptions {
+  project?: string | null;
+  since?: string | null;
+  until?: string | null;
+}
+
+export interface RateLimitHit {
+  session_id: string;
+  file_path: string;
+  project_path: string;
+  project_display: string;
+  // Transcript do subagente onde o erro ocorreu (null = transcript da sessao)
+  subagent_path: string | null;
+  entry_index: number;
+  timestamp: string;
+  kind: "usage_limit" | "rate_limit";
+  model: string | null;
+  message: string;
+  reset_at: string | null;
+  reset_hint: string | null;
+}
+
+export interface RateLimitDay {
+  date: string;
+  usage_limits: number;
+  rate_limits: number;
+}
+
+export interface RateLimitReport {
+  usage_limits: number;
+  rate_limits: number;
+  hits: RateLimitHit[];
+  days: RateLimitDay[];
+}
+
+export interface PermissionOptions {
+  project?: string | null;
+  since?: string | null;
+  until?: string | null;
+}
+
+export interface PermissionRuleStats {
+  rule: string;
+  tool_name: string;
+  example: string;
+  executed: number;
+  rejected: number;
+  interrupted: number;
+  last_seen: string;
+  existing: "allow" | "deny" | null;
+}
+
+export interface ProjectPermissions {
+  project_path: string;
+  project_display: string;
+  executed: number;
+  rejected: number;
+  interruptions: number;
+  rules: PermissionRuleStats[];
+  suggested_allow: string[];
+  suggested_deny: string[];
+}
+
+export interface PermissionReport {
+  projects: ProjectPermissions[];
+}
+
+export interface PermissionPreview {
+  settings_path: string;
+  current: string | null;
+  proposed: string;
+  diff: string;
+  added: number;
+}
+
+export type ArchiveFormat = "zstd" | "gzip";
+
+export interface ArchiveOptions {
+  session_paths?: string[];
+  project?: string;
+  // Sessoes sem atividade ha mais de N dias
+  older_than_days?: number;
+  format?: ArchiveFormat;
+}
+
+export interface ArchivedSession {
+  session_id: string;
+  source: string;
+  archive: string;
+  original_bytes: number;
+  archived_bytes: number;
+}
+
+export interface ArchiveReport {
+  archived: ArchivedSession[];
+  errors: string[];
+  original_bytes: number;
+  archived_bytes: number;
+}
+
+export interface RestoredSession {
+  session_id: string;
+  file_path: string;
+  bytes: number;
+}
+
+export interface TrashReport {
+  session_ids: string[];
+  files: number;
+  freed_bytes: number;
+  errors: string[];
+}
+
+export type RetentionAction = "trash" | "archive";
+
+export type RetentionReason = "expired" | "missing_project";
+
+export interface RetentionPolicy {
+  older_than_days?: number;
+  // Usa cleanupPeriodDays do ~/.claude/settings.json como limite de idade
+  use_cleanup_period?: boolean;
+  missing_projects?: boolean;
+  project?: string;
+  action?: RetentionAction;
+  format?: ArchiveFormat;
+  dry_run?: boolean;
+}
+
+export interface RetentionCandidate {
+  session_id: string;
+  project_path: string;
+  project_display: string;
+  file_path: string;
+  last_timestamp: string;
+  archived: boolean;
+  reasons: RetentionReason[];
+  files: string[];
+  bytes: number;
+}
+
+export interface RetentionReport {
+  dry_run: boolean;
+  action: RetentionAction;
+  cleanup_period_days: number;
+  max_age_days: number | null;
+  candidates: RetentionCandidate[];
+  // No archive e o maximo que pode ser liberado
+  total_bytes: number;
+  processed: number;
+  freed_bytes: number;
+  errors: string[];
+}
+
+export interface DiskNode {
+  name: string;
+  path: string;
+  is_dir: boolean;
+  bytes: number;
+  files: number;
+  // Maiores primeiro
+  children: DiskNode[];
+}
+
+export interface ProjectDiskUsage {
+  slug: string;
+  project_path: string;
+  project_display: string;
+  bytes: number;
+  files: number;
+  sessions: number;
+  // Pasta do projeto nao existe mais
+  orphaned: boolean;
+  last_modified: string | null;
+}
+
+export interface LargeSessionFile {
+  path: string;
+  session_id: string;
+  project_display: string;
+  bytes: number;
+  archived: boolean;
+  subagent: boolean;
+  modified: string | null;
+}
+
+// Tamanho total do diretorio num dia (gravado a cada analise)
+export interface DiskSnapshot {
+  date: string;
+  bytes: number;
+  files: number;
+}
+
+export interface DiskUsageReport {
+  tree: DiskNode;
+  projects: ProjectDiskUsage[];
+  largest_files: LargeSessionFile[];
+  history: DiskSnapshot[];
+  orphaned_bytes: number;
+}
+
+export interface ForkedSession {
+  session_id: string;
+  source_session_id: string;
+  file_path: string;
+  // Entries copiados (caminho da raiz ate o entry escolhido)
+  entries: number;
+}
+
+// Projeto movido ou renomeado: sessoes de old_path (e subpastas) passam para new_path
+export interface ProjectMigrationOptions {
+  old_path: string;
+  new_path: string;
+  dry_run?: boolean;
+}
+
+export interface ProjectFolderMove {
+  old_path: string;
+  new_path: string;
+  old_slug: string;
+  new_slug: string;
+  sessions: number;
+  // Transcripts da pasta (sessoes e subagentes)
+  files: number;
+  // Entries com cwd reescrito
+  rewritten_entries: number;
+  // A pasta de destino ja existe; os arquivos sao juntados
+  merge: boolean;
+}
+
+export interface ProjectMigrationReport {
+  dry_run: boolean;
+  moves: ProjectFolderMove[];
+  config_path: string | null;
+  // Chaves de projects no .claude.json que mudam de path
+  config_keys: string[];
+  old_path_exists: boolean;
+  new_path_exists: boolean;
+  backup_dir: string | null;
+  errors: string[];
+}
+
+export interface SearchHit {
+  entry_index: number;
+  uuid: string | null;
+  entry_type: string;
   timestamp: string;
+  scope: "text" | "tool_use" | "tool_result";
+  snippet: string;
+  // Transcript do subagente onde o match ocorreu (null = transcript da sessao)
+  subagent_path: string | null;
 }
 
 export interface SearchMatch {
@@ -18,6 +609,18 @@ export interface SearchMatch {
   matched_text: string;
   entry_type: string;
   match_count: number;
+  matched_scope: "text" | "tool_use" | "tool_result";
+  matched_filters: string[];
+  hits: SearchHit[];
+}
+
+export interface SearchPage {
+  results: SearchMatch[];
+  total_sessions: number;
+  total_hits: number;
+  // Mais hits que o limite do indice: so os mais relevantes entram
+  truncated: boolean;
+  next_cursor: string | null;
 }
 
 export interface PlatformInfo {
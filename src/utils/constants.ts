@@ -2,6 +2,8 @@ export const ROUTES = {
   CONNECT: "/connect",
   DASHBOARD: "/",
   HISTORY: "/history",
+  COMMANDS: "/commands",
+  INSIGHTS: "/insights",
   PROJECTS: "/projects",
   USAGE: "/usage",
   MCP: "/mcp",
@@ -1,3 +1,5 @@
+import type { TokenTotals } from "@/types/session";
+
 export function formatTimeUntil(isoDate: string | null): string {
   if (!isoDate) return "--";
   const target = new Date(isoDate).getTime();
@@ -53,6 +55,12 @@ export function formatCurrency(value: number | null): string {
   return `$${value.toFixed(2)}`;
 }
 
+// Custo estimado; tokens de modelos sem preco conhecido aparecem a parte
+export function formatTokenCost(tokens: TokenTotals): string {
+  const cost = formatCurrency(tokens.cost_usd);
+  return tokens.unpriced_tokens > 0 ? `${cost} + ${formatTokenCount(tokens.unpriced_tokens)} unpriced` : cost;
+}
+
 export function formatTokenCount(value: number | null): string {
   if (value == null) return "--";
   if (value >= 1_000_000) return `${(value / 1_000_000).toFixed(1)}M`;
@@ -74,3 +82,19 @@ export function formatNumber(value: number | null): string {
   if (value == null) return "--";
   return value.toLocaleString("en-US");
 }
+
+// Latencias curtas (tool calls, turnos) com precisao abaixo de 1 minuto
+export function formatLatency(ms: number | null): string {
+  if (ms == null) return "--";
+  if (ms < 1000) return `${Math.round(ms)}ms`;
+  if (ms < 60_000) return `${(ms / 1000).toFixed(1)}s`;
+  return formatDuration(ms);
+}
+
+export function formatBytes(bytes: number | null): string {
+  if (bytes == null) return "--";
+  if (bytes >= 1024 ** 3) return `${(bytes / 1024 ** 3).toFixed(1)} GB`;
+  if (bytes >= 1024 ** 2) return `${(bytes / 1024 ** 2).toFixed(1)} MB`;
+  if (bytes >= 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${bytes} B`;
+}